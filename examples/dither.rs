@@ -0,0 +1,59 @@
+use framework::prelude::*;
+use std::sync::Arc;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo
+{
+    /// photo reduced to each palette
+    versions: Vec<Arc<Image>>,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.assets().set_root("examples/res");
+
+        app.create_canvas("dithering", (800, 600));
+
+        let trees = app
+            .load_image("trees.jpg")?
+            .get();
+
+        let reduce = |palette: &Palette, dither|
+        {
+            let mut img = Image::clone(&trees);
+            img.quantize(palette, dither);
+            Arc::new(img)
+        };
+
+        Ok(Self
+        {
+            versions: vec!
+            [
+                reduce(&Palette::one_bit(), Dither::Atkinson),
+                reduce(&Palette::game_boy(), Dither::Bayer(4)),
+                reduce(&Palette::pico8(), Dither::BlueNoise),
+                reduce(&Palette::k_means(&*trees, 8, 8), Dither::FloydSteinberg),
+            ],
+        })
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        // 2x2 grid
+        let (w, h) = (c.width() / 2, c.height() / 2);
+
+        for (i, img) in self.versions.iter().enumerate()
+        {
+            let (x, y) = ((i % 2 * w) as i32, (i / 2 * h) as i32);
+
+            c.fill(Pattern::new(img.clone()).offset(v![x as f32, y as f32]));
+            c.rect(v![x, y], v![w as i32, h as i32]);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+/// side of the bouncing square, in pixels
+const SIZE: f32 = 20.0;
+
+struct Foo
+{
+    /// position before the last update
+    prev: Vec2<f32>,
+    /// position after the last update
+    pos: Vec2<f32>,
+    /// pixels per second
+    vel: Vec2<f32>,
+    /// position interpolated for this frame
+    shown: Vec2<f32>,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.create_canvas("fixed step", (400, 300));
+
+        // a deliberately slow simulation, still drawn smoothly
+        app.time().fixed_step(1.0 / 10.0);
+        // frame time graph
+        app.show_stats(true);
+
+        Ok(Self
+        {
+            prev: v![0.0, 0.0],
+            pos: v![0.0, 0.0],
+            vel: v![240.0, 150.0],
+            shown: v![0.0, 0.0],
+        })
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        let dt = app.time().dt();
+
+        self.prev = self.pos;
+        self.pos += self.vel * dt;
+
+        // bounce off the edges
+        let max = v![400.0 - SIZE, 300.0 - SIZE];
+        for i in 0..2
+        {
+            if self.pos[i] < 0.0 || self.pos[i] > max[i]
+            {
+                self.vel[i] = -self.vel[i];
+                self.pos[i] = self.pos[i].max(0.0).min(max[i]);
+            }
+        }
+    }
+
+    fn pre_draw(&mut self, app: &mut App)
+    {
+        self.shown = self.prev + (self.pos - self.prev) * app.time().alpha();
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(c!("black"));
+
+        c.no_stroke();
+        c.fill(c!("tomato"));
+        c.rect(self.shown.as_(), v![SIZE as i32, SIZE as i32]);
+    }
+}
@@ -0,0 +1,44 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo;
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.create_canvas("gradients", (600, 400));
+
+        Ok(Foo)
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        // vertical sky background
+        c.background(Gradient::linear(v![0.0, 0.0], v![0.0, 400.0])
+            .stop(0.0, c!("midnightblue"))
+            .stop(1.0, c!("lightsalmon"))
+            .space(ColourSpace::Oklab));
+
+        c.no_stroke();
+
+        // repeating radial rings
+        c.fill(Gradient::radial(v![150.0, 200.0], 40.0)
+            .stop(0.0, c!("gold"))
+            .stop(1.0, c!("crimson"))
+            .spread(Spread::Reflect));
+        c.rect(v![50, 100], v![200, 200]);
+
+        // colour wheel
+        c.fill(Gradient::conic(v![450.0, 200.0], 0.0)
+            .stop(0.0, c!("red"))
+            .stop(0.5, c!("cyan"))
+            .stop(1.0, c!("red"))
+            .space(ColourSpace::Hsl));
+        c.triangle(v![450, 50], v![580, 320], v![320, 320]);
+    }
+}
@@ -1,19 +1,19 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<MyGuiSketch>();
+    framework::run::<MyGuiSketch>()
 }
 
 struct MyGuiSketch(f32);
 
 impl Sketch for MyGuiSketch
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
         app.create_canvas("GUI!", v![600, 400]);
 
-        MyGuiSketch(0.0)
+        Ok(MyGuiSketch(0.0))
     }
 
     fn gui(&mut self, gui: &mut Gui)
@@ -1,23 +1,22 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<Baz>();
+    framework::run::<Baz>()
 }
 
 struct Baz(Image);
 
 impl Sketch for Baz
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
-        let img = app
-            .load_image("examples/res/trees.jpg")
-            .unwrap();
+        // embedded in the binary, no need to ship `examples/res`
+        let img = include_image!("res/trees.jpg");
 
         app.create_canvas("happy little trees", img.size());
 
-        Self(img)
+        Ok(Self(img))
     }
 
     fn draw(&mut self, c: &mut Canvas)
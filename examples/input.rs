@@ -1,22 +1,30 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<Baz>();
+    framework::run::<Baz>()
 }
 
 struct Baz
 {
+    /// the only canvas
+    canvas: CanvasId,
     mouse_pos: Vec2<i32>,
+    /// is the window fullscreen?
+    fullscreen: bool,
+    /// is the cursor hidden?
+    hidden: bool,
+    /// was the window focused last update?
+    focused: bool,
 }
 
 impl Sketch for Baz
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
-        app.create_canvas("baz", (600, 400));
+        let canvas = app.create_canvas("baz", (600, 400));
 
-        Self { mouse_pos: v![0, 0] }
+        Ok(Self { canvas, mouse_pos: v![0, 0], fullscreen: false, hidden: false, focused: false })
     }
 
     fn update(&mut self, app: &mut App)
@@ -24,6 +32,29 @@ impl Sketch for Baz
         app.time().print_current_frame_rate();
 
         self.mouse_pos = app.mouse().position();
+
+        // F for fullscreen, H to hide the cursor
+        if app.keys().pressed(btn!("f"))
+        {
+            self.fullscreen = !self.fullscreen;
+            app.set_fullscreen(self.canvas, self.fullscreen);
+        }
+        if app.keys().pressed(btn!("h"))
+        {
+            self.hidden = !self.hidden;
+            app.show_cursor(self.canvas, !self.hidden);
+        }
+
+        // show whether the window is focused
+        if app.is_focused(self.canvas) != self.focused
+        {
+            self.focused = !self.focused;
+            app.set_title(self.canvas, match self.focused
+            {
+                true => "baz",
+                false => "baz (unfocused)",
+            });
+        }
     }
 
     fn draw(&mut self, c: &mut Canvas)
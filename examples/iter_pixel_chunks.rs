@@ -1,23 +1,26 @@
 use framework::prelude::*;
 
-fn main()
+use std::path::Path;
+
+fn main() -> framework::Result<()>
 {
-    framework::run::<Foo>();
+    framework::run::<Foo>()
 }
 
-struct Foo(Image, usize);
+struct Foo(Handle<Image>, usize);
 
 impl Sketch for Foo
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
+        app.assets().set_root("examples/res");
+
         let img = app
-            .load_image("examples/res/trees.jpg")
-            .unwrap();
+            .load::<Image>("trees.jpg")?;
 
-        app.create_canvas("chunks!", img.size());
+        app.create_canvas("chunks!", img.get().size());
 
-        Self(img, 0)
+        Ok(Self(img, 0))
     }
 
     fn update(&mut self, app: &mut App)
@@ -28,6 +31,17 @@ impl Sketch for Foo
         }
     }
 
+    fn asset_reloaded(&mut self, _: &mut App, path: &Path, result: framework::Result<()>)
+    {
+        match result
+        {
+            // trees.jpg was edited, start over
+            Ok(()) => self.1 = 0,
+            // keep showing the previous version
+            Err(e) => println!("couldn't reload {}: {}", path.display(), e),
+        }
+    }
+
     fn draw(&mut self, c: &mut Canvas)
     {
         // erase everything only the first time
@@ -38,6 +52,7 @@ impl Sketch for Foo
 
         // go through each chunk
         for (i, chunk) in self.0
+            .get()
             .iter_pixel_chunks(v![40, 40].into())
             .enumerate()
         {
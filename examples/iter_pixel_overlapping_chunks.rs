@@ -1,23 +1,24 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<Foo>();
+    framework::run::<Foo>()
 }
 
-struct Foo(Image, usize);
+struct Foo(Handle<Image>, usize);
 
 impl Sketch for Foo
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
+        app.assets().set_root("examples/res");
+
         let img = app
-            .load_image("examples/res/trees.jpg")
-            .unwrap();
+            .load_image("trees.jpg")?;
 
-        app.create_canvas("chunks!", img.size());
+        app.create_canvas("chunks!", img.get().size());
 
-        Self(img, 0)
+        Ok(Self(img, 0))
     }
 
     fn update(&mut self, app: &mut App)
@@ -38,6 +39,7 @@ impl Sketch for Foo
 
         // go through each chunk
         for (i, chunk) in self.0
+            .get()
             .iter_pixel_overlapping_chunks(v![40, 40].into(), v![7, 7])
             .enumerate()
         {
@@ -0,0 +1,58 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo
+{
+    /// photo, still loading at first
+    trees: Pending<Image>,
+    /// how much has loaded, from 0.0 to 1.0
+    progress: f32,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.assets().set_root("examples/res");
+        app.create_canvas("loading...", (640, 427));
+
+        Ok(Self
+        {
+            trees: app.load_async("trees.jpg"),
+            progress: 0.0,
+        })
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        self.progress = app.assets().progress();
+
+        if let Some(e) = self.trees.take_error()
+        {
+            eprintln!("couldn't load the trees: {}", e);
+        }
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        match self.trees.handle()
+        {
+            Some(trees) => c.image(&*trees.get(), v![0, 0]),
+            None =>
+            {
+                c.background(c!("black"));
+
+                // loading bar
+                c.no_stroke();
+                c.fill(c!("dimgrey"));
+                c.rect(v![120, 200], v![400, 20]);
+                c.fill(c!("white"));
+                c.rect(v![120, 200], v![(400.0 * self.progress) as i32, 20]);
+            }
+        }
+    }
+}
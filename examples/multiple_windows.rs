@@ -1,36 +1,81 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<Bar>();
+    framework::run::<Bar>()
 }
 
-struct Bar(CanvasId, CanvasId, CanvasId);
+/// how many times bigger the preview shows the main canvas
+const ZOOM: i32 = 4;
+
+struct Bar
+{
+    /// canvas the preview zooms into
+    main: CanvasId,
+    /// angle of the spinning triangle, in radians
+    angle: f32,
+}
 
 impl Sketch for Bar
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
-        let c1 = app.create_canvas("Bar 1", (400, 300));
-        let c2 = app.create_canvas("Bar 2", (200, 200));
-        let c3 = app.create_canvas("Bar 3", (600, 400));
+        // each canvas is drawn by its own function
+        let main = app.canvas("Bar", (400, 300))
+            .draw(Bar::draw_main)
+            .build();
+        app.canvas("Bar preview", (200, 200))
+            .draw(Bar::draw_preview)
+            .build();
+        app.canvas("Bar 3", (200, 100))
+            .draw(|_: &mut Bar, c: &mut Canvas, _: &mut Canvases| c.background(c!("seagreen")))
+            .build();
 
-        Bar(c1, c2, c3)
+        Ok(Bar { main, angle: 0.0 })
     }
 
-    fn draw(&mut self, c: &mut Canvas)
+    fn update(&mut self, app: &mut App)
     {
-        if c.id() == &self.0
-        {
-            c.background(c!("mediumslateblue"));
-        }
-        else if c.id() == &self.1
+        self.angle += app.time().dt();
+    }
+}
+
+impl Bar
+{
+    /// spinning triangle
+    fn draw_main(&mut self, c: &mut Canvas, _: &mut Canvases)
+    {
+        let point = |offset: f32| v!
+        [
+            200 + ((self.angle + offset).cos() * 120.0) as i32,
+            150 + ((self.angle + offset).sin() * 120.0) as i32
+        ];
+
+        c.background(c!("mediumslateblue"));
+        c.stroke(c!("white"));
+        c.fill(c!("goldenrod"));
+        c.triangle(point(0.0), point(2.1), point(4.2));
+    }
+
+    /// zoomed in copy of the main canvas' centre, as it was last
+    /// drawn
+    fn draw_preview(&mut self, c: &mut Canvas, canvases: &mut Canvases)
+    {
+        let main = match canvases.get(self.main)
         {
-            c.background(c!("goldenrod"));
-        }
-        else if c.id() == &self.2
+            Some(main) => main,
+            None => return c.background(c!("black")),
+        };
+        // top-left corner of the zoomed in region
+        let corner = (main.size().as_::<i32>() - c.size().as_::<i32>() / ZOOM) / 2;
+        let corner = v![corner.w, corner.h];
+
+        for y in 0..c.height() as i32
         {
-            c.background(c!("seagreen"));
+            for x in 0..c.width() as i32
+            {
+                c[v![x, y]] = main[corner + v![x, y] / ZOOM];
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,56 @@
+use framework::prelude::*;
+use framework::math::Mat3;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo
+{
+    /// tiled 8x8 checkerboard
+    checker: Pattern,
+    /// tiled photo
+    trees: Pattern,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.assets().set_root("examples/res");
+
+        app.create_canvas("patterns", (600, 400));
+
+        // nothing moves, so only draw when needed
+        app.no_loop();
+
+        // 2x2 checkerboard, scaled up when drawn
+        let mut tile = app.create_image((2, 2));
+        tile.background(c!("darkslategrey"));
+        tile[v![0, 0]] = c!("teal");
+        tile[v![1, 1]] = c!("teal");
+
+        let trees = app
+            .load_image("trees.jpg")?
+            .get();
+
+        Ok(Self
+        {
+            checker: Pattern::new(tile).scale(v![16.0, 16.0]),
+            trees: Pattern::new(trees)
+                .scale(v![0.25, 0.25])
+                .transform(Mat3::rotation_z(0.2))
+                .sampling(Sampling::Bilinear),
+        })
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(self.checker.clone());
+
+        c.stroke(c!("white"));
+        c.fill(self.trees.clone());
+        c.triangle(v![300, 30], v![560, 370], v![40, 370]);
+    }
+}
@@ -0,0 +1,66 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+/// canvas size, in pixels
+const SIZE: Vec2<i32> = v![160, 120];
+/// side of the bouncing square, in pixels
+const SIDE: i32 = 12;
+
+struct Foo
+{
+    /// top-left corner of the bouncing square
+    pos: Vec2<f32>,
+    /// velocity of the square, in pixels per second
+    vel: Vec2<f32>,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        // 8x8 icon, a square like the one bouncing around
+        let mut icon = app.create_image((8, 8));
+        icon.background(c!("midnightblue"));
+        icon.no_stroke();
+        icon.fill(c!("gold"));
+        icon.rect(v![2, 2], v![4, 4]);
+
+        // low resolution canvas, shown at 4x in a 640x480 window
+        app.canvas("retro", SIZE.as_())
+            .scale(4)
+            .resizable(false)
+            .icon(icon)
+            .build();
+
+        Ok(Self { pos: v![20.0, 30.0], vel: v![50.0, 35.0] })
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        self.pos += self.vel * app.time().dt();
+
+        // bounce off the edges
+        let max = (SIZE - SIDE).as_::<f32>();
+        for i in 0..2
+        {
+            if self.pos[i] < 0.0 || self.pos[i] > max[i]
+            {
+                self.vel[i] = -self.vel[i];
+                self.pos[i] = self.pos[i].max(0.0).min(max[i]);
+            }
+        }
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(c!("midnightblue"));
+
+        c.no_stroke();
+        c.fill(c!("gold"));
+        c.rect(self.pos.as_(), v![SIDE, SIDE]);
+    }
+}
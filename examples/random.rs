@@ -0,0 +1,100 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+/// colours squares are picked from
+const PALETTE: [Rgba<u8>; 4] =
+[
+    c!("coral"),
+    c!("gold"),
+    c!("mediumseagreen"),
+    c!("steelblue"),
+];
+
+struct Foo
+{
+    /// squares' position, size and colour
+    squares: Vec<(Vec2<i32>, Vec2<i32>, Rgba<u8>)>,
+    /// lines' ends and colour
+    lines: Vec<(Vec2<i32>, Vec2<i32>, Rgba<u8>)>,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.create_canvas("press space for a new seed", (400, 400));
+
+        // output only changes with the seed
+        app.no_loop();
+
+        let mut foo = Self { squares: vec![], lines: vec![] };
+        foo.generate(app);
+        Ok(foo)
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        if app.keys().pressed(btn!(" "))
+        {
+            let seed = app.random_range(0..u64::MAX);
+
+            app.random_seed(seed);
+            self.generate(app);
+        }
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(c!("black"));
+
+        for &(a, b, col) in &self.lines
+        {
+            c.stroke(col);
+            c.line(a, b);
+        }
+        c.no_stroke();
+        for &(pos, size, col) in &self.squares
+        {
+            c.fill(col);
+            c.rect(pos, size);
+        }
+    }
+}
+
+impl Foo
+{
+    /// generate the whole piece from the app's current seed
+    fn generate(&mut self, app: &mut App)
+    {
+        println!("seed: {}", app.random().seed());
+
+        // rays from the centre
+        self.lines = (0..60)
+            .map(|_|
+            {
+                let end = app.random_in_circle(v![200.0, 200.0], 190.0);
+
+                (v![200, 200], end.as_(), app.random_colour())
+            })
+            .collect();
+
+        // squares clustered around the centre
+        self.squares = (0..80)
+            .map(|_|
+            {
+                let pos = v![app.random_gaussian(200.0, 60.0), app.random_gaussian(200.0, 60.0)];
+                let size = app.random_range(4..16);
+                let col = *app.random_choice(&PALETTE).unwrap();
+
+                (pos.as_::<i32>() - size / 2, v![size, size], col)
+            })
+            .collect();
+
+        // mix up which squares overlap which
+        app.shuffle(&mut self.squares);
+    }
+}
@@ -0,0 +1,66 @@
+use framework::prelude::*;
+use std::time::Duration;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo
+{
+    /// frames recorded so far, if recording
+    recording: Option<AnimatedImage>,
+    /// delay of the last frame
+    delta: Duration,
+    /// angle of the spinning triangle, in radians
+    angle: f32,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.create_canvas("press R to start/stop recording", (200, 200));
+
+        Ok(Self { recording: None, delta: Duration::default(), angle: 0.0 })
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        self.delta = app.time().delta();
+        self.angle += app.time().dt() * 2.0;
+
+        if app.keys().pressed(btn!("r"))
+        {
+            match self.recording.take()
+            {
+                None => self.recording = Some(AnimatedImage::new()),
+                Some(rec) =>
+                {
+                    rec.save_gif("recording.gif", Dither::FloydSteinberg).unwrap();
+                    println!("saved {} frames to recording.gif", rec.len());
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(c!("midnightblue"));
+
+        // spinning triangle
+        let point = |offset: f32| v!
+        [
+            100 + ((self.angle + offset).cos() * 80.0) as i32,
+            100 + ((self.angle + offset).sin() * 80.0) as i32
+        ];
+        c.stroke(c!("white"));
+        c.fill(c!("orange"));
+        c.triangle(point(0.0), point(2.1), point(4.2));
+
+        if let Some(rec) = &mut self.recording
+        {
+            rec.push(c, self.delta);
+        }
+    }
+}
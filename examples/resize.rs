@@ -0,0 +1,55 @@
+use framework::prelude::*;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo;
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        // resize each window to see how its canvas fills it
+        app.canvas("letterbox", (160, 120))
+            .scale(3)
+            .resize_policy(ResizePolicy::Letterbox)
+            .build();
+        app.canvas("stretch", (160, 120))
+            .scale(3)
+            .resize_policy(ResizePolicy::Stretch)
+            .build();
+        app.canvas("follow window", (160, 120))
+            .scale(3)
+            .resize_policy(ResizePolicy::FollowWindow)
+            .build();
+
+        Ok(Foo)
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        let size = c.size().as_::<i32>();
+        let (w, h) = (size.w, size.h);
+
+        c.background(c!("darkslategrey"));
+
+        // border, showing the canvas' edges
+        c.stroke(c!("white"));
+        c.no_fill();
+        c.rect(v![0, 0], v![w, h]);
+
+        // diamond touching each edge's middle
+        c.stroke(c!("gold"));
+        c.line(v![w / 2, 0], v![w - 1, h / 2]);
+        c.line(v![w - 1, h / 2], v![w / 2, h - 1]);
+        c.line(v![w / 2, h - 1], v![0, h / 2]);
+        c.line(v![0, h / 2], v![w / 2, 0]);
+    }
+
+    fn resized(&mut self, _: &mut App, id: CanvasId, size: Extent2<usize>)
+    {
+        println!("{:?} is now {}x{}", id, size.w, size.h);
+    }
+}
@@ -1,19 +1,19 @@
 use framework::prelude::*;
 
-fn main()
+fn main() -> framework::Result<()>
 {
-    framework::run::<Foo>();
+    framework::run::<Foo>()
 }
 
 struct Foo;
 
 impl Sketch for Foo
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
         app.create_canvas("Foo", (400, 300));
 
-        Foo
+        Ok(Foo)
     }
 
     fn draw(&mut self, c: &mut Canvas)
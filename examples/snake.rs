@@ -1,8 +1,10 @@
 use framework::prelude::*;
 
-fn main()
+use std::time::Duration;
+
+fn main() -> framework::Result<()>
 {
-    framework::run::<SnakeGame>();
+    framework::run::<SnakeGame>()
 }
 
 /// (width, height) tiles in the game
@@ -11,8 +13,12 @@ const GRID_SIZE: Vec2<i32> = v![61, 61];
 const TILE_SIZE: Vec2<i32> = v![8, 8];
 /// starting length of the snake
 const START_LEN: usize = 5;
-/// period between food generation, in tick(frames)
-const FOOD_RATE: u64 = 60;
+/// period between snake moves
+const STEP_TIME: Duration = Duration::from_millis(50);
+/// period between food generation
+const FOOD_TIME: Duration = Duration::from_secs(3);
+/// how long food takes to pop in
+const POP_TIME: Duration = Duration::from_millis(400);
 
 struct SnakeGame
 {
@@ -22,19 +28,28 @@ struct SnakeGame
     /// snake's direction, either ±[1, 0] or ±[0, 1]
     /// starts at [0, 0] for no direction
     dir: Vec2<i32>,
+    /// direction the snake turns to on its next move
+    next: Vec2<i32>,
+
+    /// position of food in map, and its size growing
+    /// from nothing
+    food: Vec<(Vec2<i32>, Tween<f32>)>,
 
-    /// position of food in map
-    food: Vec<Vec2<i32>>,
+    /// fires every time the snake moves
+    step: TimerId,
+    /// fires every time food appears
+    spawn: TimerId,
 }
 
 impl Sketch for SnakeGame
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
         app.create_canvas("snake", (GRID_SIZE * TILE_SIZE).as_());
-        app.time().frame_rate(20.0);
+        // F5 to pause, F6 to step, F7/F8 to slow down or speed up
+        app.time().hotkeys(true);
 
-        Self::default()
+        Ok(Self::new(app.every(STEP_TIME), app.every(FOOD_TIME)))
     }
 
     fn draw(&mut self, c: &mut Canvas)
@@ -55,10 +70,12 @@ impl Sketch for SnakeGame
         // food style
         c.fill(c!("darksalmon"));
 
-        // draw food
-        for tile in &self.food
+        // draw food, centred in its tile
+        for (tile, pop) in &self.food
         {
-            c.rect(tile * TILE_SIZE, TILE_SIZE);
+            let size = (TILE_SIZE.as_::<f32>() * pop.value()).as_::<i32>();
+
+            c.rect(tile * TILE_SIZE + (TILE_SIZE - size) / 2, size);
         }
     }
 
@@ -67,39 +84,53 @@ impl Sketch for SnakeGame
         // time
         app.time().print_current_frame_rate();
 
-        // input
-        if app.keys().pressed(btn!("left"))  && self.dir.x !=  1 { self.dir = v![-1, 0]; }
-        if app.keys().pressed(btn!("right")) && self.dir.x != -1 { self.dir = v![ 1, 0]; }
-        if app.keys().pressed(btn!("up"))    && self.dir.y !=  1 { self.dir = v![ 0,-1]; }
-        if app.keys().pressed(btn!("down"))  && self.dir.y != -1 { self.dir = v![ 0, 1]; }
+        // input, applied on the next move
+        if app.keys().pressed(btn!("left"))  && self.dir.x !=  1 { self.next = v![-1, 0]; }
+        if app.keys().pressed(btn!("right")) && self.dir.x != -1 { self.next = v![ 1, 0]; }
+        if app.keys().pressed(btn!("up"))    && self.dir.y !=  1 { self.next = v![ 0,-1]; }
+        if app.keys().pressed(btn!("down"))  && self.dir.y != -1 { self.next = v![ 0, 1]; }
+
+        // pop food in
+        let delta = app.time().delta();
+        for (_, pop) in &mut self.food
+        {
+            pop.update(delta);
+        }
 
         // no movement
-        if self.dir == v![0, 0]
+        if self.next == v![0, 0]
         {   
             return;
         }
 
         // generate food
-        if (app.time().tick() - 1) % FOOD_RATE == 0
+        if app.fired(self.spawn)
         {
             // predicates:
             // 1. food cannot intersect other food
             // 2. food cannot intersect snake
             let pos = loop
             {
-                let x = app.random().gen_range(0..GRID_SIZE.x);
-                let y = app.random().gen_range(0..GRID_SIZE.y);
+                let x = app.random_range(0..GRID_SIZE.x);
+                let y = app.random_range(0..GRID_SIZE.y);
                 
                 let pos = v![x, y];
 
                 if !self.snake.iter().any(|&t| t == pos)
-                && !self.food.iter().any(|&f| f == pos)
+                && !self.food.iter().any(|(f, _)| *f == pos)
                 {
                     break pos;
                 }
             };
-            self.food.push(pos);
+            self.food.push((pos, Tween::new(0.0, 1.0, POP_TIME).ease(Ease::BackOut)));
+        }
+
+        // not time to move yet
+        if !app.fired(self.step)
+        {
+            return;
         }
+        self.dir = self.next;
 
         // move head
         self.snake[0] += self.dir;
@@ -121,14 +152,14 @@ impl Sketch for SnakeGame
         {
             println!("GAME OVER!\nscore: {}", self.snake.len());
 
-            return *self = Self::default();
+            return *self = Self::new(self.step, self.spawn);
         }
 
         // find food at head
         let food = self.food
             .iter()
             .enumerate()
-            .find(|(_, &t)| t == self.snake[0]);
+            .find(|(_, (t, _))| *t == self.snake[0]);
 
         // eat food
         if let Some((ind, _)) = food
@@ -148,9 +179,11 @@ impl Sketch for SnakeGame
     }
 }
 
-impl Default for SnakeGame
+impl SnakeGame
 {
-    fn default() -> Self
+    /// start a new game, moving and spawning food with
+    /// the timers given
+    fn new(step: TimerId, spawn: TimerId) -> Self
     {
         Self
         {
@@ -159,7 +192,10 @@ impl Default for SnakeGame
             snake: [GRID_SIZE / 2; START_LEN].into(),
             // doesn't move at first
             dir: v![0, 0],
-            food: vec![]
+            next: v![0, 0],
+            food: vec![],
+            step,
+            spawn,
         }
     }
 }
\ No newline at end of file
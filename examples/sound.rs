@@ -1,40 +1,41 @@
-#[cfg(feature = "audio")]
+#[cfg(feature = "sound")]
 use framework::prelude::*;
 
-#[cfg(feature = "audio")]
-fn main()
+#[cfg(feature = "sound")]
+fn main() -> framework::Result<()>
 {
-    framework::run::<Foo>();
+    framework::run::<Foo>()
 }
 
-#[cfg(not(feature = "audio"))]
+#[cfg(not(feature = "sound"))]
 fn main()
 {
-    panic!("audio feature must be enabled!");
+    panic!("sound feature must be enabled!");
 }
 
-#[cfg(feature = "audio")]
-struct Foo(Track<f32>);
+#[cfg(feature = "sound")]
+struct Foo(Handle<Track<f32>>);
 
-#[cfg(feature = "audio")]
+#[cfg(feature = "sound")]
 impl Sketch for Foo
 {
-    fn setup(app: &mut App) -> Self
+    fn setup(app: &mut App) -> framework::Result<Self>
     {
+        app.assets().set_root("examples/res");
+
         app.create_canvas("piano!", (300, 200));
 
         let song = app
-            .load_sound("examples/res/piano.ogg")
-            .unwrap();
+            .load_sound("piano.ogg")?;
 
-        Self(song)
+        Ok(Self(song))
     }
 
     fn update(&mut self, app: &mut App)
     {
         if app.keys().pressed(btn!(" "))
         {
-            self.0.toggle_play();
+            self.0.get().toggle_play();
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use framework::prelude::*;
+use std::time::Duration;
+
+fn main() -> framework::Result<()>
+{
+    framework::run::<Foo>()
+}
+
+struct Foo
+{
+    /// each animation's frames are a 4x4 grid cut out of the photo
+    anims: Vec<Animation>,
+}
+
+impl Sketch for Foo
+{
+    fn setup(app: &mut App) -> framework::Result<Self>
+    {
+        app.assets().set_root("examples/res");
+
+        let img = app
+            .load_image("trees.jpg")?
+            .get();
+        let size = (img.width() / 4, img.height() / 4);
+
+        app.create_canvas("sprites", (size.0 * 3, size.1));
+
+        let sheet = std::sync::Arc::new(SpriteSheet::grid(Image::clone(&img), size));
+        let frame_time = Duration::from_millis(150);
+
+        Ok(Self
+        {
+            anims: vec!
+            [
+                Animation::new(sheet.clone(), 0..16, frame_time),
+                Animation::new(sheet.clone(), 0..16, frame_time).playback(Playback::PingPong),
+                Animation::new(sheet, 0..16, frame_time).playback(Playback::Once),
+            ],
+        })
+    }
+
+    fn update(&mut self, app: &mut App)
+    {
+        let delta = app.time().delta();
+        let restart = app.keys().pressed(btn!(" "));
+
+        for anim in &mut self.anims
+        {
+            anim.update(delta);
+
+            if restart
+            {
+                anim.restart();
+            }
+        }
+    }
+
+    fn draw(&mut self, c: &mut Canvas)
+    {
+        c.background(c!("black"));
+
+        let w = (c.width() / 3) as i32;
+        for (i, anim) in self.anims.iter().enumerate()
+        {
+            anim.draw(c, v![i as i32 * w, 0]);
+        }
+    }
+}
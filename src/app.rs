@@ -1,17 +1,23 @@
 use std::collections::HashMap;
+use std::time::{ Duration, Instant };
 use std::path::Path;
 
 use winit::event_loop::{ ControlFlow, EventLoopWindowTarget as WindowTarget };
-use winit::window::WindowId;
-use winit::event::Event;
+use winit::window::{ WindowId, Fullscreen };
+use winit::dpi::LogicalSize;
+use winit::event::{ Event, WindowEvent, KeyboardInput, ElementState };
 
-use rand::prelude::ThreadRng;
+use rand::distributions::uniform::{ SampleRange, SampleUniform };
 
-use crate::input::{ Input, Mouse, Keys, Time, ProcessedEvent };
-use crate::draw::{ CanvasId, Window, Image };
-#[cfg(feature = "audio")]
+use crate::input::{ Input, Mouse, Keys, Time, Timers, TimerId, FrameStats, Phase, ProcessedEvent };
+use crate::draw::{ CanvasId, CanvasBuilder, CanvasConfig, Canvases, DrawFn, Window, Image };
+#[cfg(feature = "sound")]
 use crate::sound::{ Audio, Track, Sample };
-use crate::math::Extent2;
+use crate::math::{ Extent2, Rgba, Vec2 };
+use crate::assets::{ Assets, Asset, Handle, Pending, LoadContext };
+use crate::error::{ Error, WindowError };
+use crate::gui;
+use crate::util::Random;
 use crate::Sketch;
 
 /// handle to the application running this `Sketch`.
@@ -24,20 +30,61 @@ pub struct App
     input: Input,
     /// time state
     time: Time,
+    /// timers scheduled by the sketch
+    timers: Timers,
+    /// time spent on each phase of recent frames
+    stats: FrameStats,
+    /// is the frame time overlay shown?
+    show_stats: bool,
 
     /// windows currently open
     windows: Windows,
-    /// window creation requests
-    requests: Vec<(CanvasId, String, Extent2<usize>)>,
+    /// window creation and change requests, oldest first
+    requests: Vec<Request>,
     /// next window request ID
     next: CanvasId,
 
-    /// audio context
-    #[cfg(feature = "audio")]
-    audio: Audio,
+    /// audio context, if an output device could be connected to.
+    /// loaded `Track`s play on it for as long as it's kept alive
+    #[cfg(feature = "sound")]
+    _audio: Option<Audio>,
+    /// loaded assets
+    assets: Assets,
+
+    /// are update and draw called every frame?
+    looping: bool,
+    /// was a redraw asked for while not looping?
+    redraw: bool,
 
     /// extra utility: random
-    rand: ThreadRng,
+    rand: Random,
+}
+
+/// a window to open or change, queued until
+/// `App::process_requests`
+enum Request
+{
+    /// open a new window
+    Create(CanvasId, CanvasConfig),
+    /// change an open window
+    Change(CanvasId, Change),
+}
+
+/// a change to an open window
+enum Change
+{
+    /// set the title
+    Title(String),
+    /// resize the window, in logical pixels
+    WindowSize(Extent2<usize>),
+    /// resize the pixel buffer
+    BufferSize(Extent2<usize>),
+    /// enter or leave borderless fullscreen
+    Fullscreen(bool),
+    /// show or hide the cursor over the window
+    CursorVisible(bool),
+    /// lock the cursor inside the window, or release it
+    CursorGrab(bool),
 }
 
 /// double-key'd hashmap of `CanvasId` and `WindowId`s
@@ -53,32 +100,74 @@ pub(crate) struct Windows
 
 impl App
 {
-    /// create a new app
-    pub(super) fn new() -> Self
+    /// create a new app. sketches still run without an audio
+    /// output device, but loading sounds then fails
+    pub(super) fn new() -> Result<Self, Error>
     {
-        Self
+        #[cfg(feature = "sound")]
+        let audio = Audio::new().ok();
+        let assets = Assets::new(LoadContext
+        {
+            #[cfg(feature = "sound")]
+            audio: audio.as_ref().map(|audio| audio.handle.clone()),
+        });
+
+        Ok(Self
         {
             input: Input::new(),
             time: Time::new(),
+            timers: Timers::new(),
+            stats: FrameStats::new(),
+            show_stats: false,
             windows: Windows::default(),
             requests: Vec::new(),
             next: CanvasId::zero(),
-            #[cfg(feature = "audio")]
-            audio: Audio::new(),
-            rand: ThreadRng::default(),
-        }
+            #[cfg(feature = "sound")]
+            _audio: audio,
+            assets,
+            looping: true,
+            // always draw the first frame
+            redraw: true,
+            rand: Random::new(),
+        })
     }
 
     /// process an incoming winit event and interact with
     /// `Sketch`, inner `Time`, `Input`, and windows accordingly.
-    pub(super) fn process_event(
+    /// errors are returned for `run` to exit with
+    pub(super) fn process_event<T: Sketch>(
         &mut self,
-        sketch: &mut impl Sketch,
+        sketch: &mut T,
         event: Event<()>,
         window_target: &WindowTarget<()>,
         control_flow: &mut ControlFlow
-    )
+    ) -> Result<(), Error>
     {
+        if let Event::WindowEvent { window_id, event } = &event
+        {
+            match event
+            {
+                // time's debugging hotkeys, once per press. the OS
+                // repeats presses while a key is held, but the key's
+                // already down by then
+                WindowEvent::KeyboardInput { input: KeyboardInput
+                {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(key),
+                    ..
+                }, .. } if !self.input.keys().down(*key) => self.time.hotkey(*key),
+                // winit can't be asked for focus, so keep track of it
+                WindowEvent::Focused(focused) =>
+                {
+                    if let Some(window) = self.windows.get_mut(window_id)
+                    {
+                        window.focused = *focused;
+                    }
+                }
+                _ => { }
+            }
+        }
+
         match self.input.process(&event, &mut self.windows)
         {
             // notify `Pixels` of the window resize
@@ -87,6 +176,9 @@ impl App
                 if let Some(window) = self.windows.get_mut(&id)
                 {
                     window.resize(w, h);
+
+                    let (id, size) = (window.id, window.size);
+                    sketch.resized(self, id, size);
                 }
             }
             // close window and quit if needed
@@ -98,77 +190,216 @@ impl App
                 // quit?
                 if self.windows.is_empty()
                 {
-                    return *control_flow = ControlFlow::Exit;
+                    *control_flow = ControlFlow::Exit;
                 }
             }
             // render to the given window
             ProcessedEvent::ShouldRender(id) =>
             {
-                // get the frame
-                if let Some(window) = self.windows.get_mut(&id)
+                // take the window out while rendering, so its draw
+                // function can borrow the others
+                if let Some(mut window) = self.windows.take(&id)
                 {
-                    // prepare GUI frame
-                    window.gui.prepare(&self.time);
-
-                    // update buffer
-                    sketch.draw(&mut window.get_frame());
-                    // draw gui
-                    sketch.gui(&mut window.get_gui());
+                    let result = self.render(sketch, &mut window);
 
-                    let (pixels, gui) = (&mut window.pixels, &mut window.gui);
-
-                    // render
-                    if pixels.render_with(|encoder, target, context|
-                    {
-                        // render pixels
-                        context.scaling_renderer.render(encoder, target);
-
-                        // render GUI
-                        gui.render(encoder, target, context);
-                    })
-                    .map_err(|e| eprintln!("pixels.render() failed: {}", e))
-                    .is_err()
-                    {
-                        return *control_flow = ControlFlow::Exit;
-                    }
+                    self.windows.restore(window);
+                    result?;
                 }
             }
             // update the Sketch state and request new events
             ProcessedEvent::ShouldUpdate =>
             {
-                // update time
-                if self.time.update()
+                // cache assets loaded in the background
+                self.assets.finish_loading();
+
+                // swap in assets changed on disk
+                for (path, result) in self.assets.reload_changed()
+                {
+                    sketch.asset_reloaded(self, &path, result);
+                }
+
+                // not looping: sleep until asked to draw or until
+                // input arrives
+                if !self.looping && !self.redraw && !self.input.received
+                {
+                    *control_flow = ControlFlow::Wait;
+                    return Ok(());
+                }
+                // frame rate limit not reached yet: sleep until it is
+                if let Some(next) = self.time.next_frame()
+                {
+                    if Instant::now() < next
+                    {
+                        *control_flow = ControlFlow::WaitUntil(next);
+                        return Ok(());
+                    }
+                }
+                self.redraw = false;
+                self.input.received = false;
+
+                // update time, then update state as many times
+                // as time says to
+                let steps = self.time.update();
+                self.stats.end_frame(self.time.real_delta());
+
+                let start = Instant::now();
+                for step in 0..steps
                 {
-                    // update state if time says it's time to
-                    // update
+                    // presses and releases are only seen by the
+                    // first of several fixed updates
+                    if step > 0
+                    {
+                        self.input.consume();
+                    }
+                    self.timers.advance(self.time.delta());
+
                     sketch.update(self);
 
                     // process requests
-                    self.process_requests(window_target);
+                    self.process_requests::<T>(window_target)?;
 
                     // input has been used by update
                     self.input.reset = true;
                 }
+                self.stats.add(Phase::Update, start.elapsed());
+
+                // paused: input is only seen by `pre_draw`, so it
+                // doesn't pile up until resuming
+                if self.time.is_paused()
+                {
+                    self.input.reset = true;
+                }
+                sketch.pre_draw(self);
                 
                 // request a redraw on each window
                 for window in self.windows.iter_mut()
                 {
                     window.winit.request_redraw();
                 }
+
+                // sleep until the next frame, unless spinning as fast
+                // as possible
+                *control_flow = match self.time.next_frame()
+                {
+                    Some(next) if self.looping => ControlFlow::WaitUntil(next),
+                    None if self.looping => ControlFlow::Poll,
+                    _ => ControlFlow::Wait,
+                };
             }
             ProcessedEvent::None => { }
         }
+        Ok(())
+    }
+
+    /// draw, and render a window taken out of `App::windows`
+    fn render<T: Sketch>(&mut self, sketch: &mut T, window: &mut Window) -> Result<(), Error>
+    {
+        // prepare pixel buffer and GUI frame
+        window.prepare_frame()?;
+        window.gui.prepare(&self.time);
+
+        // update buffer, with the canvas' own draw function if
+        // it has one
+        let start = Instant::now();
+        match window.draw.take()
+        {
+            Some(mut draw) =>
+            {
+                let draw_fn = draw
+                    .downcast_mut::<DrawFn<T>>()
+                    .expect("canvas draw functions are checked when their window is created");
+
+                draw_fn(sketch, &mut window.get_frame(), &mut Canvases::new(&mut self.windows));
+                window.draw = Some(draw);
+            }
+            None => sketch.draw(&mut window.get_frame()),
+        }
+        window.finish_frame();
+        self.stats.add(Phase::Draw, start.elapsed());
+
+        // draw gui
+        let start = Instant::now();
+        sketch.gui(&mut window.get_gui());
+        if self.show_stats
+        {
+            gui::overlay(&window.get_gui(), &self.stats);
+        }
+        self.stats.add(Phase::Gui, start.elapsed());
+
+        let (pixels, gui) = (&mut window.pixels, &mut window.gui);
+
+        // render
+        let start = Instant::now();
+        pixels.render_with(|encoder, target, context|
+        {
+            // render pixels
+            context.scaling_renderer.render(encoder, target);
+
+            // render GUI
+            gui.render(encoder, target, context);
+        })
+        .map_err(WindowError::Pixels)?;
+        self.stats.add(Phase::Present, start.elapsed());
+
+        Ok(())
     }
 
-    /// process window creation requests. this should be
-    /// called right after the `App` has been mutuably passed
-    /// to the `Sketch`
-    pub(super) fn process_requests(&mut self, target: &WindowTarget<()>)
+    /// process window creation and change requests. this should
+    /// be called right after the `App` has been mutuably passed
+    /// to the sketch, a `T`
+    pub(super) fn process_requests<T: Sketch>(&mut self, target: &WindowTarget<()>) -> Result<(), Error>
     {
-        while let Some((id, title, size)) = self.requests.pop()
+        for request in std::mem::take(&mut self.requests)
         {
-            self.windows.insert(id, Window::new(target, title, size, id));
+            let (id, change) = match request
+            {
+                Request::Create(id, config) =>
+                {
+                    // fail now rather than when it's first drawn
+                    if !config.draws_with::<T>()
+                    {
+                        return Err(WindowError::DrawFn(config.title).into());
+                    }
+                    self.windows.insert(id, Window::new(target, config, id)?);
+                    continue;
+                }
+                Request::Change(id, change) => (id, change),
+            };
+            // the window may have been closed since
+            let window = match self.windows.canvas_mut(&id)
+            {
+                Some(window) => window,
+                None => continue,
+            };
+            match change
+            {
+                Change::Title(title) => window.winit.set_title(&title),
+                Change::WindowSize(size) =>
+                {
+                    window.winit.set_inner_size(LogicalSize::new(size.w as f64, size.h as f64));
+                }
+                Change::BufferSize(size) => window.resize_canvas(size),
+                Change::Fullscreen(fullscreen) =>
+                {
+                    window.winit.set_fullscreen(match fullscreen
+                    {
+                        true => Some(Fullscreen::Borderless(None)),
+                        false => None,
+                    });
+                }
+                Change::CursorVisible(visible) => window.winit.set_cursor_visible(visible),
+                // unsupported on some platforms, which isn't worth
+                // stopping the sketch over
+                Change::CursorGrab(grab) =>
+                {
+                    if window.winit.set_cursor_grab(grab).is_ok()
+                    {
+                        window.cursor_locked = grab;
+                    }
+                }
+            }
         }
+        Ok(())
     }
 
     /// get the current `Time` information for this
@@ -179,6 +410,53 @@ impl App
         &mut self.time
     }
 
+    /// get the timers scheduled on this app
+    #[inline]
+    pub fn timers(&mut self) -> &mut Timers
+    {
+        &mut self.timers
+    }
+
+    /// start a timer that fires once, `delay` from now. poll it
+    /// with `App::fired` in `Sketch::update`
+    pub fn after(&mut self, delay: Duration) -> TimerId
+    {
+        self.timers.after(delay)
+    }
+
+    /// start a timer that fires every `period` until it's
+    /// cancelled. poll it with `App::fired` in `Sketch::update`
+    pub fn every(&mut self, period: Duration) -> TimerId
+    {
+        self.timers.every(period)
+    }
+
+    /// stop a timer started with `App::after` or `App::every`
+    pub fn cancel(&mut self, id: TimerId)
+    {
+        self.timers.cancel(id)
+    }
+
+    /// did the timer fire right before this update?
+    pub fn fired(&self, id: TimerId) -> bool
+    {
+        self.timers.fired(id)
+    }
+
+    /// get the time spent on each phase of recent frames
+    #[inline]
+    pub fn stats(&self) -> &FrameStats
+    {
+        &self.stats
+    }
+
+    /// show or hide an overlay with a frame time graph and the
+    /// stats of each phase, drawn with the GUI on every canvas
+    pub fn show_stats(&mut self, show: bool)
+    {
+        self.show_stats = show;
+    }
+
     /// get the current state of the keyboard for this
     /// frame
     #[inline]
@@ -200,17 +478,93 @@ impl App
     /// back to it later on. note that the window won't actually
     /// be instantly created. instead, calls to `create_canvas`
     /// are polled right after the enclosing function goes out of
-    /// scope. 
+    /// scope. see `App::canvas` for more settings
     pub fn create_canvas(&mut self, title: impl Into<String>, size: impl Into<Extent2<usize>>) -> CanvasId
+    {
+        self.canvas(title, size).build()
+    }
+
+    /// start building a new `Canvas` with a pixel buffer of
+    /// `size`, for settings `create_canvas` doesn't have, like
+    /// pixel scale or fullscreen
+    pub fn canvas(&mut self, title: impl Into<String>, size: impl Into<Extent2<usize>>) -> CanvasBuilder<'_>
+    {
+        CanvasBuilder::new(self, title.into(), size.into())
+    }
+
+    /// queue a window to be created by `process_requests`
+    pub(crate) fn request_canvas(&mut self, config: CanvasConfig) -> CanvasId
     {
         let id = self.next;
 
         self.next = self.next.next();
-        self.requests.push((id, title.into(), size.into()));
+        self.requests.push(Request::Create(id, config));
 
         id
     }
 
+    /// set a canvas' window title
+    pub fn set_title(&mut self, id: CanvasId, title: impl Into<String>)
+    {
+        self.requests.push(Request::Change(id, Change::Title(title.into())));
+    }
+
+    /// resize a canvas' window, in logical pixels. the pixel
+    /// buffer keeps its size
+    pub fn resize_window(&mut self, id: CanvasId, size: impl Into<Extent2<usize>>)
+    {
+        self.requests.push(Request::Change(id, Change::WindowSize(size.into())));
+    }
+
+    /// resize a canvas' pixel buffer, the size of the `Canvas`
+    /// drawn to. the window keeps its size
+    pub fn resize_buffer(&mut self, id: CanvasId, size: impl Into<Extent2<usize>>)
+    {
+        self.requests.push(Request::Change(id, Change::BufferSize(size.into())));
+    }
+
+    /// make a canvas' window borderless fullscreen on its current
+    /// monitor, or leave fullscreen
+    pub fn set_fullscreen(&mut self, id: CanvasId, fullscreen: bool)
+    {
+        self.requests.push(Request::Change(id, Change::Fullscreen(fullscreen)));
+    }
+
+    /// show or hide the cursor while it's over a canvas' window
+    pub fn show_cursor(&mut self, id: CanvasId, visible: bool)
+    {
+        self.requests.push(Request::Change(id, Change::CursorVisible(visible)));
+    }
+
+    /// lock the cursor inside a canvas' window, or release it.
+    /// not supported on every platform, so check
+    /// `App::is_cursor_locked` if it matters
+    pub fn lock_cursor(&mut self, id: CanvasId, lock: bool)
+    {
+        self.requests.push(Request::Change(id, Change::CursorGrab(lock)));
+    }
+
+    /// is the cursor locked inside a canvas' window? false until
+    /// a `lock_cursor` request is processed, or if the platform
+    /// refused it
+    pub fn is_cursor_locked(&self, id: CanvasId) -> bool
+    {
+        self.windows.canvas(&id).map_or(false, |window| window.cursor_locked)
+    }
+
+    /// ratio of physical to logical pixels of a canvas' window,
+    /// or `None` if it isn't open yet or anymore
+    pub fn scale_factor(&self, id: CanvasId) -> Option<f64>
+    {
+        self.windows.canvas(&id).map(|window| window.winit.scale_factor())
+    }
+
+    /// does a canvas' window have keyboard focus?
+    pub fn is_focused(&self, id: CanvasId) -> bool
+    {
+        self.windows.canvas(&id).map_or(false, |window| window.focused)
+    }
+
     /// destroys a `Canvas` given its ID if it exists, or
     /// does nothing if it doesn't
     pub fn destroy_canvas(&mut self, id: CanvasId)
@@ -218,46 +572,161 @@ impl App
         self.windows.remove(&id);
     }
 
-    /// loads an `Image` at the specified path, converting its
-    /// pixels to `Rgba<u8>` by cloning if not already that
-    /// format. This method is shorthand for:
+    /// get this app's asset store
+    #[inline]
+    pub fn assets(&mut self) -> &mut Assets
+    {
+        &mut self.assets
+    }
+
+    /// load an asset at the specified path, relative to the asset
+    /// root, or get it if it's already loaded. This method is
+    /// shorthand for:
     /// ```
-    /// Image::open(path).ok()
+    /// app.assets().load(path)
     /// ```
-    pub fn load_image(&self, path: impl AsRef<Path>) -> Option<Image>
+    pub fn load<T: Asset>(&mut self, path: impl AsRef<Path>) -> Result<Handle<T>, Error>
     {
-        Image::open(path).ok()
+        self.assets.load(path)
+    }
+
+    /// load an asset in the background, relative to the asset root.
+    /// This method is shorthand for:
+    /// ```
+    /// app.assets().load_async(path)
+    /// ```
+    pub fn load_async<T: Asset>(&mut self, path: impl AsRef<Path>) -> Pending<T>
+    {
+        self.assets.load_async(path)
+    }
+
+    /// loads an `Image` at the specified path, relative to the
+    /// asset root, converting its pixels to `Rgba<u8>` by cloning
+    /// if not already that format. the image is cached and reloaded
+    /// when it changes, like any asset. This method is shorthand
+    /// for:
+    /// ```
+    /// app.load::<Image>(path)
+    /// ```
+    pub fn load_image(&mut self, path: impl AsRef<Path>) -> Result<Handle<Image>, Error>
+    {
+        self.assets.load(path)
     }
 
     /// create a new empty image from a given size. this is a
-    /// utility method over `Image::new()`
+    /// utility method over `Image::blank()`
     pub fn create_image(&self, size: impl Into<Extent2<usize>>) -> Image
     {
-        let size = size.into();
-        let area = size.w * size.h;
-
-        Image::new((), vec![0; area * 4], size)
+        Image::blank(size)
     }
 
-    /// loads a sound [Track] at the given path, if it exists.
-    /// supports formats with extensions:
+    /// loads a sound [Track] at the given path, relative to the
+    /// asset root. the track is cached and reloaded, paused, when
+    /// it changes, like any asset. This method is shorthand for
+    /// `app.load::<Track<S>>(path)`, and supports formats with
+    /// extensions:
     /// - flac(".flac")
     /// - ogg vorbis(".ogg", ".oga")
     /// - wav(".wav", ".wave")
     /// - alac(".caf")
     ///
-    /// [Track]: crate::audio::Track
-    #[cfg(feature = "audio")]
-    pub fn load_sound<S: Sample>(&self, path: impl AsRef<Path>) -> Option<Track<S>>
+    /// [Track]: crate::sound::Track
+    #[cfg(feature = "sound")]
+    pub fn load_sound<S: Sample>(&mut self, path: impl AsRef<Path>) -> Result<Handle<Track<S>>, Error>
+    {
+        self.assets.load(path)
+    }
+
+    /// stop calling `Sketch::update` and `Sketch::draw` every
+    /// frame, so the app sleeps instead. they're then only called
+    /// after `App::redraw` or when input arrives, which suits
+    /// sketches that don't animate. note that assets loaded in the
+    /// background or changed on disk are picked up on the next
+    /// wake
+    pub fn no_loop(&mut self)
     {
-        Track::open(path, &self.audio).ok()
+        self.looping = false;
     }
 
-    /// get this app's random number generator
-    pub fn random(&mut self) -> &mut ThreadRng
+    /// go back to calling `Sketch::update` and `Sketch::draw`
+    /// every frame, after `App::no_loop`. this is the default
+    pub fn start_loop(&mut self)
+    {
+        self.looping = true;
+    }
+
+    /// are `Sketch::update` and `Sketch::draw` called every
+    /// frame?
+    #[inline]
+    pub fn is_looping(&self) -> bool
+    {
+        self.looping
+    }
+
+    /// update and draw once more after `App::no_loop`, on the
+    /// next frame. no effect while looping
+    pub fn redraw(&mut self)
+    {
+        self.redraw = true;
+    }
+
+    /// get this app's random number generator, which starts from
+    /// a random seed. `App::random().seed()` gets the seed, to
+    /// re-create output later with `App::random_seed`
+    #[inline]
+    pub fn random(&mut self) -> &mut Random
     {
         &mut self.rand
     }
+
+    /// start the random number generator over from a seed
+    pub fn random_seed(&mut self, seed: u64)
+    {
+        self.rand.reseed(seed)
+    }
+
+    /// random number in a range, ie. `random_range(0..10)`
+    pub fn random_range<T: SampleUniform, R: SampleRange<T>>(&mut self, range: R) -> T
+    {
+        self.rand.range(range)
+    }
+
+    /// normally distributed random number around `mean`
+    pub fn random_gaussian(&mut self, mean: f32, std_dev: f32) -> f32
+    {
+        self.rand.gaussian(mean, std_dev)
+    }
+
+    /// random item of a slice, or `None` if it's empty
+    pub fn random_choice<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T>
+    {
+        self.rand.choice(items)
+    }
+
+    /// random, opaque colour
+    pub fn random_colour(&mut self) -> Rgba<u8>
+    {
+        self.rand.colour()
+    }
+
+    /// random point inside a circle
+    pub fn random_in_circle(&mut self, centre: Vec2<f32>, radius: f32) -> Vec2<f32>
+    {
+        self.rand.in_circle(centre, radius)
+    }
+
+    /// random point inside a rectangle, given its top-left corner
+    /// and size
+    pub fn random_in_rect(&mut self, pos: Vec2<f32>, size: Vec2<f32>) -> Vec2<f32>
+    {
+        self.rand.in_rect(pos, size)
+    }
+
+    /// shuffle a slice in place
+    pub fn shuffle<T>(&mut self, items: &mut [T])
+    {
+        self.rand.shuffle(items)
+    }
 }
 
 impl Windows
@@ -274,6 +743,33 @@ impl Windows
         self.win.get_mut(id)
     }
 
+    /// get a window given its `CanvasId`
+    pub fn canvas(&self, id: &CanvasId) -> Option<&Window>
+    {
+        self.id.get(id).and_then(|id| self.win.get(id))
+    }
+
+    /// mutably get a window given its `CanvasId`
+    pub fn canvas_mut(&mut self, id: &CanvasId) -> Option<&mut Window>
+    {
+        let win = &mut self.win;
+
+        self.id.get(id).and_then(move |id| win.get_mut(id))
+    }
+
+    /// take a window out given its `WindowId`, until it's put
+    /// back with `restore`. its `CanvasId` isn't found meanwhile
+    pub fn take(&mut self, id: &WindowId) -> Option<Window>
+    {
+        self.win.remove(id)
+    }
+
+    /// put back a window taken out with `take`
+    pub fn restore(&mut self, window: Window)
+    {
+        self.win.insert(window.winit.id(), window);
+    }
+
     /// insert a window given its `CanvasId`
     pub fn insert(&mut self, id: CanvasId, window: Window)
     {
@@ -0,0 +1,806 @@
+use notify::{ Watcher, RecommendedWatcher, RecursiveMode, DebouncedEvent };
+
+use std::collections::{ HashMap, HashSet };
+use std::sync::mpsc::{ channel, Sender, Receiver };
+use std::sync::{ Arc, RwLock, Mutex, MutexGuard };
+use std::path::{ Path, PathBuf };
+use std::any::{ Any, TypeId };
+use std::time::Duration;
+
+use crate::draw::{ Image, AnimatedImage, SpriteSheet, TileMap, Font };
+#[cfg(feature = "sound")]
+use crate::sound::{ Track, Sample };
+#[cfg(feature = "sound")]
+use crate::error::AudioError;
+use crate::error::Error;
+
+/// a type that can be loaded from a file by `Assets`
+/// ```
+/// impl Asset for Level
+/// {
+///     fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+///     {
+///         Ok(Level::parse(&std::fs::read_to_string(path)?))
+///     }
+/// }
+/// ```
+pub trait Asset: Sized + Send + Sync + 'static
+{
+    /// load this asset from the file at `path`, which has
+    /// already been resolved against the asset root
+    fn load(path: &Path, ctx: &LoadContext) -> Result<Self, Error>;
+}
+
+/// whatever assets may need from the app while loading, such
+/// as the audio output sounds are played on
+#[derive(Clone)]
+pub struct LoadContext
+{
+    /// output that loaded `Track`s play on, if there's one
+    #[cfg(feature = "sound")]
+    pub(crate) audio: Option<rodio::OutputStreamHandle>,
+}
+
+/// a cheap, shared reference to a loaded asset. cloning a handle
+/// doesn't clone the asset, and handles can be kept in a `Sketch`
+/// to use the asset later, in `draw` for example
+/// ```
+/// // setup
+/// let trees = app.load::<Image>("trees.jpg")?;
+///
+/// // draw
+/// c.image(&*self.trees.get(), v![0, 0]);
+/// ```
+pub struct Handle<T>
+{
+    slot: Arc<Slot<T>>,
+}
+
+/// where a handle's asset actually lives
+struct Slot<T>
+{
+    /// resolved path the asset was loaded from
+    path: PathBuf,
+    /// current value of the asset
+    value: RwLock<Arc<T>>,
+}
+
+/// an asset being loaded in the background, which becomes a `Handle`
+/// once it's done. see `Assets::load_async`
+/// ```
+/// // setup
+/// let trees = app.load_async::<Image>("trees.jpg");
+///
+/// // draw
+/// match self.trees.handle()
+/// {
+///     Some(trees) => c.image(&*trees.get(), v![0, 0]),
+///     None => c.rect(v![0, 0], v![(self.progress * 100.0) as i32, 10]),
+/// }
+/// ```
+pub struct Pending<T>
+{
+    state: Arc<Mutex<State<T>>>,
+}
+
+/// how far along a background load is
+enum State<T>
+{
+    /// still loading
+    Loading,
+    /// loaded and cached
+    Loaded(Handle<T>),
+    /// failed, with the error until it's taken
+    Failed(Option<Error>),
+}
+
+/// finishes a background load on the main thread
+type Finish = Box<dyn FnOnce(&mut Assets) + Send>;
+
+/// store of every asset loaded through the `App`, caching them by
+/// path so loading the same file twice gives the same `Handle`.
+/// relative paths are resolved against a configurable root, and
+/// assets are reloaded in place when their file changes on disk
+pub struct Assets
+{
+    /// directory relative paths are resolved against
+    root: PathBuf,
+    /// passed along to `Asset::load`
+    ctx: LoadContext,
+    /// loaded assets, keyed by their type and resolved path
+    cache: HashMap<(TypeId, PathBuf), Entry>,
+    /// file watcher, if hot reloading is enabled and the platform
+    /// supports it
+    watcher: Option<FileWatcher>,
+
+    /// `Pending<T>`s still loading, keyed like `self.cache`
+    loading: HashMap<(TypeId, PathBuf), Box<dyn Any>>,
+    /// sent by background loads when they're done
+    finished: (Sender<Finish>, Receiver<Finish>),
+    /// (done, requested) background loads since the last time
+    /// every one of them was done
+    batch: (usize, usize),
+}
+
+/// a cached asset
+struct Entry
+{
+    /// the `Handle<T>`
+    handle: Box<dyn Any>,
+    /// canonical path, to match against file events
+    canonical: PathBuf,
+    /// load the file again into `handle`
+    reload: fn(&dyn Any, &LoadContext) -> Result<(), Error>,
+}
+
+/// watches the directories of loaded assets
+struct FileWatcher
+{
+    /// the platform's watcher
+    watcher: RecommendedWatcher,
+    /// debounced file events
+    events: Receiver<DebouncedEvent>,
+    /// directories already being watched
+    dirs: HashSet<PathBuf>,
+}
+
+impl<T> Handle<T>
+{
+    /// wrap a freshly loaded asset
+    fn new(path: PathBuf, value: T) -> Self
+    {
+        Self { slot: Arc::new(Slot { path, value: RwLock::new(Arc::new(value)) }) }
+    }
+
+    /// get the asset. the returned `Arc` can be held onto for
+    /// as long as needed
+    pub fn get(&self) -> Arc<T>
+    {
+        Arc::clone(&self.slot.value.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// get the resolved path this asset was loaded from
+    #[inline]
+    pub fn path(&self) -> &Path
+    {
+        &self.slot.path
+    }
+
+    /// replace the asset, for every handle
+    fn set(&self, value: T)
+    {
+        *self.slot.value.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(value);
+    }
+
+    /// do both handles refer to the same asset?
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool
+    {
+        Arc::ptr_eq(&self.slot, &other.slot)
+    }
+}
+
+impl<T> Clone for Handle<T>
+{
+    fn clone(&self) -> Self
+    {
+        Self { slot: Arc::clone(&self.slot) }
+    }
+}
+
+impl<T> Pending<T>
+{
+    /// create an already finished load
+    fn done(state: State<T>) -> Self
+    {
+        Self { state: Arc::new(Mutex::new(state)) }
+    }
+
+    /// lock the state, even if a thread panicked while holding it
+    fn state(&self) -> MutexGuard<'_, State<T>>
+    {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// has the asset finished loading, successfully or not?
+    pub fn is_done(&self) -> bool
+    {
+        !matches!(*self.state(), State::Loading)
+    }
+
+    /// get the asset's handle, if it's been loaded successfully
+    pub fn handle(&self) -> Option<Handle<T>>
+    {
+        match &*self.state()
+        {
+            State::Loaded(handle) => Some(handle.clone()),
+            _ => None,
+        }
+    }
+
+    /// take the reason loading failed, if it did. only the first
+    /// call gets the error
+    pub fn take_error(&self) -> Option<Error>
+    {
+        match &mut *self.state()
+        {
+            State::Failed(e) => e.take(),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Clone for Pending<T>
+{
+    fn clone(&self) -> Self
+    {
+        Self { state: Arc::clone(&self.state) }
+    }
+}
+
+impl Assets
+{
+    /// create an empty store, with the working directory as its
+    /// root and hot reloading enabled
+    pub(crate) fn new(ctx: LoadContext) -> Self
+    {
+        Self
+        {
+            root: PathBuf::new(),
+            ctx,
+            cache: HashMap::new(),
+            watcher: FileWatcher::new(),
+            loading: HashMap::new(),
+            finished: channel(),
+            batch: (0, 0),
+        }
+    }
+
+    /// get the directory relative paths are resolved against
+    #[inline]
+    pub fn root(&self) -> &Path
+    {
+        &self.root
+    }
+
+    /// set the directory relative paths are resolved against, ie.
+    /// `"examples/res"`. assets already loaded are left untouched
+    pub fn set_root(&mut self, root: impl Into<PathBuf>)
+    {
+        self.root = root.into();
+    }
+
+    /// resolve a path against the asset root. absolute paths are
+    /// left as they are
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf
+    {
+        self.root.join(path)
+    }
+
+    /// load the asset at the path specified, or get it if it's
+    /// already been loaded. if it's still loading in the background,
+    /// the background load ends up with this `Handle` too
+    pub fn load<T: Asset>(&mut self, path: impl AsRef<Path>) -> Result<Handle<T>, Error>
+    {
+        if let Some(handle) = self.get(&path)
+        {
+            return Ok(handle);
+        }
+        let path = self.resolve(path);
+        let value = T::load(&path, &self.ctx)?;
+
+        self.insert(path, value)
+    }
+
+    /// load the asset at the path specified on a background thread,
+    /// so big files don't freeze the windows. the returned `Pending`
+    /// turns into a `Handle` on the first update after it's loaded.
+    /// already loaded or loading assets aren't loaded again
+    pub fn load_async<T: Asset>(&mut self, path: impl AsRef<Path>) -> Pending<T>
+    {
+        if let Some(handle) = self.get(&path)
+        {
+            return Pending::done(State::Loaded(handle));
+        }
+        let path = self.resolve(path);
+        let key = (TypeId::of::<T>(), path.clone());
+
+        if let Some(pending) = self.loading.get(&key).and_then(|p| p.downcast_ref::<Pending<T>>())
+        {
+            return pending.clone();
+        }
+
+        // start a new batch
+        if self.batch.0 == self.batch.1
+        {
+            self.batch = (0, 0);
+        }
+        self.batch.1 += 1;
+
+        let pending = Pending::done(State::Loading);
+        self.loading.insert(key, Box::new(pending.clone()));
+
+        let (ctx, finish) = (self.ctx.clone(), self.finished.0.clone());
+        rayon::spawn(move ||
+        {
+            let result = T::load(&path, &ctx);
+
+            // the store may have been dropped by now
+            let _ = finish.send(Box::new(move |assets: &mut Assets| assets.finish::<T>(path, result)));
+        });
+
+        pending
+    }
+
+    /// cache an asset loaded in the background and update its
+    /// `Pending`
+    fn finish<T: Asset>(&mut self, path: PathBuf, result: Result<T, Error>)
+    {
+        let pending = self.loading.remove(&(TypeId::of::<T>(), path.clone()));
+        // loaded with `load` in the meantime: keep that handle, which
+        // is the one that's hot reloaded
+        let result = match self.cached::<T>(&path)
+        {
+            Some(handle) => Ok(handle),
+            None => result.and_then(|value| self.insert(path, value)),
+        };
+
+        self.batch.0 += 1;
+
+        if let Some(pending) = pending.and_then(|p| p.downcast::<Pending<T>>().ok())
+        {
+            *pending.state() = match result
+            {
+                Ok(handle) => State::Loaded(handle),
+                Err(e) => State::Failed(Some(e)),
+            };
+        }
+    }
+
+    /// cache and watch a freshly loaded asset
+    fn insert<T: Asset>(&mut self, path: PathBuf, value: T) -> Result<Handle<T>, Error>
+    {
+        let handle = Handle::new(path.clone(), value);
+
+        let canonical = path.canonicalize()?;
+        if let Some(watcher) = &mut self.watcher
+        {
+            watcher.watch(&canonical);
+        }
+
+        self.cache.insert((TypeId::of::<T>(), path), Entry
+        {
+            handle: Box::new(handle.clone()),
+            canonical,
+            reload: reload::<T>,
+        });
+
+        Ok(handle)
+    }
+
+    /// get the asset at the path specified, if it's been loaded
+    pub fn get<T: Asset>(&self, path: impl AsRef<Path>) -> Option<Handle<T>>
+    {
+        self.cached(&self.resolve(path))
+    }
+
+    /// get the asset at the resolved path specified, if it's cached
+    fn cached<T: Asset>(&self, path: &Path) -> Option<Handle<T>>
+    {
+        self.cache
+            .get(&(TypeId::of::<T>(), path.to_path_buf()))
+            .and_then(|entry| entry.handle.downcast_ref::<Handle<T>>())
+            .cloned()
+    }
+
+    /// stop caching the asset at the path specified. handles
+    /// still held keep it alive
+    pub fn unload<T: Asset>(&mut self, path: impl AsRef<Path>)
+    {
+        let path = self.resolve(path);
+
+        self.cache.remove(&(TypeId::of::<T>(), path));
+    }
+
+    /// are any assets still loading in the background?
+    #[inline]
+    pub fn is_loading(&self) -> bool
+    {
+        !self.loading.is_empty()
+    }
+
+    /// how far along background loading is, from 0.0 to 1.0,
+    /// counting every asset requested since the last time they
+    /// were all done. 1.0 if nothing is loading
+    pub fn progress(&self) -> f32
+    {
+        match self.batch
+        {
+            (_, 0) => 1.0,
+            (done, total) => done as f32 / total as f32,
+        }
+    }
+
+    /// cache every asset that finished loading in the background
+    /// since the last call
+    pub(crate) fn finish_loading(&mut self)
+    {
+        while let Ok(finish) = self.finished.1.try_recv()
+        {
+            finish(self);
+        }
+    }
+
+    /// number of assets currently cached
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.cache.len()
+    }
+
+    /// are no assets cached?
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.cache.is_empty()
+    }
+
+    /// get what assets are passed while loading
+    #[inline]
+    pub fn context(&self) -> &LoadContext
+    {
+        &self.ctx
+    }
+
+    /// is hot reloading enabled?
+    #[inline]
+    pub fn is_watching(&self) -> bool
+    {
+        self.watcher.is_some()
+    }
+
+    /// enable or disable hot reloading, which is enabled by default
+    pub fn watch(&mut self, enabled: bool)
+    {
+        if !enabled
+        {
+            return self.watcher = None;
+        }
+        if self.watcher.is_none()
+        {
+            self.watcher = FileWatcher::new();
+
+            if let Some(watcher) = &mut self.watcher
+            {
+                for entry in self.cache.values()
+                {
+                    watcher.watch(&entry.canonical);
+                }
+            }
+        }
+    }
+
+    /// reload every asset whose file changed since the last call,
+    /// returning their resolved paths and whether reloading them
+    /// worked. an asset that fails to reload, ie. because it's only
+    /// partly written, keeps its previous value
+    pub(crate) fn reload_changed(&mut self) -> Vec<(PathBuf, Result<(), Error>)>
+    {
+        let changed = match &mut self.watcher
+        {
+            Some(watcher) => watcher.changed(),
+            None => return Vec::new(),
+        };
+
+        self.cache
+            .iter()
+            .filter(|(_, entry)| changed.contains(&entry.canonical))
+            .map(|((_, path), entry)| (path.clone(), (entry.reload)(&*entry.handle, &self.ctx)))
+            .collect()
+    }
+}
+
+/// load the file behind a type-erased `Handle<T>` again
+fn reload<T: Asset>(handle: &dyn Any, ctx: &LoadContext) -> Result<(), Error>
+{
+    if let Some(handle) = handle.downcast_ref::<Handle<T>>()
+    {
+        handle.set(T::load(handle.path(), ctx)?);
+    }
+    Ok(())
+}
+
+impl FileWatcher
+{
+    /// start a watcher, or `None` if the platform doesn't allow it
+    fn new() -> Option<Self>
+    {
+        let (tx, events) = channel();
+
+        notify::watcher(tx, Duration::from_millis(100))
+            .ok()
+            .map(|watcher| Self { watcher, events, dirs: HashSet::new() })
+    }
+
+    /// watch the directory containing the file at the canonical
+    /// path specified. directories are watched rather than files,
+    /// because many editors save by replacing the file
+    fn watch(&mut self, file: &Path)
+    {
+        let dir = match file.parent()
+        {
+            Some(dir) => dir.to_path_buf(),
+            None => return,
+        };
+        if !self.dirs.contains(&dir) && self.watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok()
+        {
+            self.dirs.insert(dir);
+        }
+    }
+
+    /// canonical paths of the files written to or replaced since
+    /// the last call
+    fn changed(&mut self) -> HashSet<PathBuf>
+    {
+        self.events
+            .try_iter()
+            .filter_map(|event| match event
+            {
+                DebouncedEvent::Write(path)
+                | DebouncedEvent::Create(path)
+                | DebouncedEvent::Rename(_, path) => path.canonicalize().ok(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Asset for Image
+{
+    fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+    {
+        Ok(Image::open(path)?)
+    }
+}
+
+impl Asset for AnimatedImage
+{
+    fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+    {
+        Ok(AnimatedImage::open(path)?)
+    }
+}
+
+impl Asset for SpriteSheet
+{
+    fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+    {
+        Ok(SpriteSheet::open(path)?)
+    }
+}
+
+impl Asset for TileMap
+{
+    fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+    {
+        Ok(TileMap::open(path)?)
+    }
+}
+
+impl Asset for Font
+{
+    fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+    {
+        Ok(Font::open(path)?)
+    }
+}
+
+#[cfg(feature = "sound")]
+impl<S: Sample> Asset for Track<S>
+{
+    fn load(path: &Path, ctx: &LoadContext) -> Result<Self, Error>
+    {
+        let audio = ctx.audio.as_ref().ok_or(AudioError::NoOutput)?;
+
+        Track::open_on(path, audio)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use std::fs;
+    use std::thread;
+    use std::time::Instant;
+
+    /// contents of a text file
+    struct Text(String);
+
+    /// length of a file, to cache the same path as another type
+    struct Len(usize);
+
+    impl Asset for Text
+    {
+        fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+        {
+            Ok(Text(fs::read_to_string(path)?))
+        }
+    }
+
+    impl Asset for Len
+    {
+        fn load(path: &Path, _: &LoadContext) -> Result<Self, Error>
+        {
+            Ok(Len(fs::read(path)?.len()))
+        }
+    }
+
+    /// a store rooted in an empty temporary directory
+    fn assets(name: &str) -> Assets
+    {
+        let root = std::env::temp_dir().join(format!("framework-assets-{}-{}", name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let mut assets = Assets::new(LoadContext
+        {
+            #[cfg(feature = "sound")]
+            audio: None,
+        });
+        assets.set_root(root);
+        assets
+    }
+
+    /// write a file in the store's root
+    fn write(assets: &Assets, path: &str, contents: &str)
+    {
+        fs::write(assets.resolve(path), contents).unwrap();
+    }
+
+    /// finish every background load
+    fn wait(assets: &mut Assets)
+    {
+        while assets.is_loading()
+        {
+            assets.finish_loading();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn cache_hit()
+    {
+        let mut assets = assets("cache-hit");
+        write(&assets, "a.txt", "hello");
+
+        let a = assets.load::<Text>("a.txt").unwrap();
+        let b = assets.load::<Text>("a.txt").unwrap();
+
+        assert!(a.ptr_eq(&b));
+        assert!(a.ptr_eq(&assets.get::<Text>("a.txt").unwrap()));
+        assert_eq!(a.get().0, "hello");
+        assert_eq!(a.path(), assets.resolve("a.txt"));
+        assert_eq!(assets.len(), 1);
+
+        assets.unload::<Text>("a.txt");
+        assert!(assets.get::<Text>("a.txt").is_none());
+        assert_eq!(a.get().0, "hello");
+    }
+
+    #[test]
+    fn keyed_by_type()
+    {
+        let mut assets = assets("keyed-by-type");
+        write(&assets, "a.txt", "hello");
+
+        let text = assets.load::<Text>("a.txt").unwrap();
+        let len = assets.load::<Len>("a.txt").unwrap();
+
+        assert_eq!(text.get().0, "hello");
+        assert_eq!(len.get().0, 5);
+        assert_eq!(assets.len(), 2);
+        assert!(assets.get::<Len>("b.txt").is_none());
+    }
+
+    #[test]
+    fn failed_load()
+    {
+        let mut assets = assets("failed-load");
+
+        assert!(matches!(assets.load::<Text>("missing.txt"), Err(Error::Io(_))));
+        assert!(assets.is_empty());
+
+        let pending = assets.load_async::<Text>("missing.txt");
+        wait(&mut assets);
+
+        assert!(pending.is_done());
+        assert!(pending.handle().is_none());
+        assert!(matches!(pending.take_error(), Some(Error::Io(_))));
+        assert!(pending.take_error().is_none());
+        assert!(assets.is_empty());
+    }
+
+    #[test]
+    fn batch_progress()
+    {
+        let mut assets = assets("batch-progress");
+        write(&assets, "a.txt", "a");
+        write(&assets, "b.txt", "b");
+
+        assert_eq!(assets.progress(), 1.0);
+
+        let a = assets.load_async::<Text>("a.txt");
+        let b = assets.load_async::<Text>("b.txt");
+        // already loading, so it doesn't count twice
+        assert!(!assets.load_async::<Text>("a.txt").is_done());
+        assert_eq!(assets.batch, (0, 2));
+        assert!(assets.is_loading());
+        assert_eq!(assets.progress(), 0.0);
+
+        wait(&mut assets);
+        assert_eq!(assets.progress(), 1.0);
+        assert_eq!(a.handle().unwrap().get().0, "a");
+        assert_eq!(b.handle().unwrap().get().0, "b");
+        assert_eq!(assets.len(), 2);
+
+        // cached, so it doesn't count
+        assert!(assets.load_async::<Text>("a.txt").is_done());
+        assert_eq!(assets.progress(), 1.0);
+
+        // new batch
+        write(&assets, "c.txt", "c");
+        let _c = assets.load_async::<Text>("c.txt");
+        assert_eq!(assets.progress(), 0.0);
+        wait(&mut assets);
+        assert_eq!(assets.progress(), 1.0);
+    }
+
+    #[test]
+    fn load_while_loading()
+    {
+        let mut assets = assets("load-while-loading");
+        write(&assets, "a.txt", "hello");
+
+        let pending = assets.load_async::<Text>("a.txt");
+        let handle = assets.load::<Text>("a.txt").unwrap();
+        wait(&mut assets);
+
+        assert!(pending.handle().unwrap().ptr_eq(&handle));
+        assert!(assets.get::<Text>("a.txt").unwrap().ptr_eq(&handle));
+        assert_eq!(assets.len(), 1);
+    }
+
+    #[test]
+    fn reload_changed()
+    {
+        let mut assets = assets("reload-changed");
+        write(&assets, "a.txt", "before");
+
+        let handle = assets.load::<Text>("a.txt").unwrap();
+        if !assets.is_watching()
+        {
+            return;
+        }
+        write(&assets, "a.txt", "after");
+
+        // file events are debounced
+        let start = Instant::now();
+        let reloaded = loop
+        {
+            let reloaded = assets.reload_changed();
+            if !reloaded.is_empty() || start.elapsed() > Duration::from_secs(5)
+            {
+                break reloaded;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].0, assets.resolve("a.txt"));
+        assert!(reloaded[0].1.is_ok());
+        assert_eq!(handle.get().0, "after");
+    }
+}
@@ -0,0 +1,391 @@
+use image::{ AnimationDecoder, ImageFormat, ImageError };
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::error::{ EncodingError, ImageFormatHint, ParameterError, ParameterErrorKind };
+
+use std::io::{ BufRead, BufReader, Cursor, Seek };
+use std::collections::HashMap;
+use std::time::Duration;
+use std::borrow::Cow;
+use std::fs::File;
+use std::path::Path;
+
+use crate::draw::{ Bitmap, PixelBuf, Image, Palette, Dither };
+use crate::draw::palette::MAX_SAMPLES;
+use crate::math::{ Vec2, Extent2 };
+
+/// a sequence of equally sized images, each shown for its own delay,
+/// such as an animated GIF or APNG, or frames recorded from a canvas
+/// ```
+/// // playback
+/// let gif = AnimatedImage::open("res/spinner.gif")?;
+/// c.image(gif.frame_at(app.time().elapsed()), v![0, 0]);
+///
+/// // recording
+/// self.recording.push(c, Duration::from_millis(33));
+/// self.recording.save_gif("sketch.gif", Dither::FloydSteinberg).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct AnimatedImage
+{
+    /// every frame, fully composited
+    frames: Vec<Image>,
+    /// how long each frame in `self.frames` is shown
+    delays: Vec<Duration>,
+}
+
+impl AnimatedImage
+{
+    /// create an empty animated image, to record frames into
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// open the animated GIF or APNG located at the path specified.
+    /// any other image opens as a single frame
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ImageError>
+    {
+        let path = path.as_ref();
+
+        let format = ImageFormat::from_path(path)?;
+        let file = File::open(path).map_err(ImageError::IoError)?;
+
+        Self::decode(BufReader::new(file), format)
+    }
+
+    /// decode an animated GIF or APNG already in memory, such as one
+    /// embedded with `include_bytes!`. the format is guessed from the
+    /// bytes themselves and any other image decodes as a single frame
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError>
+    {
+        Self::decode(Cursor::new(bytes), image::guess_format(bytes)?)
+    }
+
+    /// decode an animated image of a known format
+    fn decode(reader: impl BufRead + Seek, format: ImageFormat) -> Result<Self, ImageError>
+    {
+        match format
+        {
+            ImageFormat::Gif => Self::from_frames(GifDecoder::new(reader)?.into_frames()),
+            ImageFormat::Png =>
+            {
+                let decoder = PngDecoder::new(reader)?;
+
+                match decoder.is_apng()
+                {
+                    true => Self::from_frames(decoder.apng().into_frames()),
+                    false => Ok(Self::still(image::DynamicImage::from_decoder(decoder)?)),
+                }
+            }
+            format => Ok(Self::still(image::load(reader, format)?)),
+        }
+    }
+
+    /// add a copy of a bitmap, such as a canvas, as the last frame,
+    /// shown for `delay`. panics if it isn't the same size as the
+    /// previous frames
+    pub fn push<I>(&mut self, frame: &Bitmap<I, impl PixelBuf>, delay: Duration)
+    {
+        if let Some(first) = self.frames.first()
+        {
+            assert_eq!(first.size(), frame.size(), "animated image frame sizes differ");
+        }
+
+        let mut copy = Image::blank(frame.size());
+        copy.image(frame, Vec2::zero());
+
+        self.frames.push(copy);
+        self.delays.push(delay);
+    }
+
+    /// get every frame
+    #[inline]
+    pub fn frames(&self) -> &[Image]
+    {
+        &self.frames
+    }
+
+    /// get how long each frame is shown
+    #[inline]
+    pub fn delays(&self) -> &[Duration]
+    {
+        &self.delays
+    }
+
+    /// number of frames
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.frames.len()
+    }
+
+    /// are there no frames?
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.frames.is_empty()
+    }
+
+    /// get the width and height, in pixels, of the frames
+    pub fn size(&self) -> Extent2<usize>
+    {
+        self.frames.first().map_or(Extent2::zero(), |frame| frame.size())
+    }
+
+    /// total time taken to show every frame once
+    pub fn duration(&self) -> Duration
+    {
+        self.delays.iter().sum()
+    }
+
+    /// get the index of the frame shown `t` after the start, looping
+    /// forever, typically with `t` being `Time::elapsed()`. panics if
+    /// there are no frames
+    pub fn index_at(&self, t: Duration) -> usize
+    {
+        assert!(!self.is_empty(), "animated image has no frames");
+
+        let total = self.duration().as_nanos();
+        if total == 0
+        {
+            return 0;
+        }
+
+        let mut t = t.as_nanos() % total;
+        for (i, delay) in self.delays.iter().enumerate()
+        {
+            if t < delay.as_nanos()
+            {
+                return i;
+            }
+            t -= delay.as_nanos();
+        }
+        self.len() - 1
+    }
+
+    /// get the frame shown `t` after the start, looping forever. see
+    /// `AnimatedImage::index_at`
+    #[inline]
+    pub fn frame_at(&self, t: Duration) -> &Image
+    {
+        &self.frames[self.index_at(t)]
+    }
+
+    /// save as an animated GIF that loops forever, reducing the frames to
+    /// a shared palette of 256 colours picked with `Palette::median_cut`
+    /// and dithering with `dither`. frames are saved as fully opaque
+    pub fn save_gif(&self, path: impl AsRef<Path>, dither: Dither) -> Result<(), ImageError>
+    {
+        // colours sampled evenly across every frame
+        let area = self.size().w * self.size().h * self.len();
+        let step = (area / MAX_SAMPLES).max(1);
+
+        let samples = self.frames
+            .iter()
+            .flat_map(|frame| frame.pixels())
+            .step_by(step)
+            .map(|px| [px.r, px.g, px.b])
+            .collect();
+
+        self.save_gif_with(path, &Palette::median_cut_samples(samples, 256), dither)
+    }
+
+    /// same as `AnimatedImage::save_gif`, with a palette of 1 to 256
+    /// colours of your choice, ie. `Palette::pico8()`. fails without
+    /// writing anything if there are no frames
+    pub fn save_gif_with(&self, path: impl AsRef<Path>, palette: &Palette, dither: Dither) -> Result<(), ImageError>
+    {
+        let err = |e: gif::EncodingError| ImageError::Encoding(EncodingError::new(ImageFormatHint::Exact(ImageFormat::Gif), e));
+        let param = |kind| ImageError::Parameter(ParameterError::from_kind(kind));
+
+        let size = self.size();
+        if self.is_empty() || size.w > u16::MAX as usize || size.h > u16::MAX as usize
+        {
+            return Err(param(ParameterErrorKind::DimensionMismatch));
+        }
+        if palette.is_empty() || palette.len() > 256
+        {
+            return Err(param(ParameterErrorKind::Generic(format!("gif palettes have 1 to 256 colours, not {}", palette.len()))));
+        }
+
+        let rgb = palette
+            .colours()
+            .iter()
+            .flat_map(|col| vec![col.r, col.g, col.b])
+            .collect::<Vec<_>>();
+
+        let file = File::create(path).map_err(ImageError::IoError)?;
+        let mut encoder = gif::Encoder::new(file, size.w as u16, size.h as u16, &rgb).map_err(err)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(err)?;
+
+        // palette index of each colour seen so far
+        let mut indices = HashMap::new();
+
+        for (frame, delay) in self.frames.iter().zip(&self.delays)
+        {
+            let mut frame = frame.clone();
+            frame.quantize(palette, dither);
+
+            let buffer = frame
+                .pixels()
+                .iter()
+                .map(|px| *indices
+                    .entry([px.r, px.g, px.b])
+                    .or_insert_with(|| palette.nearest_index(px.rgb().as_()).unwrap_or(0) as u8))
+                .collect::<Vec<_>>();
+
+            encoder.write_frame(&gif::Frame
+            {
+                width: size.w as u16,
+                height: size.h as u16,
+                // in hundredths of a second
+                delay: ((delay.as_millis() + 5) / 10).min(u16::MAX as u128) as u16,
+                buffer: Cow::Owned(buffer),
+                ..gif::Frame::default()
+            })
+            .map_err(err)?;
+        }
+        Ok(())
+    }
+
+    /// create an animated image from decoded frames, compositing any
+    /// partial frames onto the previous ones
+    fn from_frames(frames: image::Frames) -> Result<Self, ImageError>
+    {
+        let mut anim = Self::new();
+
+        for frame in frames
+        {
+            let frame: image::Frame = frame?;
+
+            let (n, d) = frame.delay().numer_denom_ms();
+            let delay = Duration::from_secs_f64(n as f64 / d.max(1) as f64 / 1000.0);
+
+            let pos = Vec2::new(frame.left(), frame.top()).as_::<i32>();
+            let buf = frame.into_buffer();
+            let part: Image = Bitmap::new((), buf.as_raw().clone(), Extent2::new(buf.width(), buf.height()).as_());
+
+            let full = match anim.frames.last()
+            {
+                // partial frame over the previous one
+                Some(prev) if pos != Vec2::zero() || part.size() != prev.size() =>
+                {
+                    let mut full = prev.clone();
+                    full.image(&part, pos);
+                    full
+                }
+                _ => part,
+            };
+
+            anim.frames.push(full);
+            anim.delays.push(delay);
+        }
+        Ok(anim)
+    }
+
+    /// create an animated image of a single, still frame
+    fn still(img: image::DynamicImage) -> Self
+    {
+        let img = img.into_rgba8();
+        let size = Extent2::new(img.width(), img.height()).as_();
+
+        Self
+        {
+            frames: vec![Bitmap::new((), img.into_raw(), size)],
+            delays: vec![Duration::default()],
+        }
+    }
+}
+
+impl From<Image> for AnimatedImage
+{
+    /// an animated image of a single, still frame
+    fn from(img: Image) -> Self
+    {
+        Self { frames: vec![img], delays: vec![Duration::default()] }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::math::Rgba;
+
+    /// 5x3 frames shown for `delays` milliseconds, in turns of colour
+    fn frames(delays: &[u64]) -> AnimatedImage
+    {
+        let colours = [Rgba::new(0xff, 0x00, 0x4d, 0xff), Rgba::new(0x29, 0xad, 0xff, 0xff), Rgba::new(0xff, 0xec, 0x27, 0xff)];
+        let mut anim = AnimatedImage::new();
+
+        for (i, &delay) in delays.iter().enumerate()
+        {
+            let mut frame = Image::blank((5, 3));
+
+            frame.background(colours[i % colours.len()]);
+            anim.push(&frame, Duration::from_millis(delay));
+        }
+        anim
+    }
+
+    /// path of a file in the temporary directory, unique to this test
+    fn temp(name: &str) -> std::path::PathBuf
+    {
+        std::env::temp_dir().join(format!("framework-{}-{}.gif", name, std::process::id()))
+    }
+
+    #[test]
+    fn gif_round_trip()
+    {
+        let anim = frames(&[100, 50, 30]);
+        let path = temp("round-trip");
+
+        anim.save_gif_with(&path, &Palette::pico8(), Dither::None).unwrap();
+        let saved = AnimatedImage::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved.size(), Extent2::new(5, 3));
+        // in hundredths of a second
+        assert_eq!(saved.delays(), &[Duration::from_millis(100), Duration::from_millis(50), Duration::from_millis(30)]);
+        for (saved, frame) in saved.frames().iter().zip(anim.frames())
+        {
+            assert_eq!(saved.pixels(), frame.pixels());
+        }
+    }
+
+    #[test]
+    fn gif_invalid()
+    {
+        let path = temp("invalid");
+        let param = |result| matches!(result, Err(ImageError::Parameter(_)));
+
+        assert!(param(AnimatedImage::new().save_gif_with(&path, &Palette::pico8(), Dither::None)));
+        assert!(param(AnimatedImage::new().save_gif(&path, Dither::None)));
+        assert!(param(frames(&[100]).save_gif_with(&path, &Palette::new(Vec::new()), Dither::None)));
+        assert!(param(frames(&[100]).save_gif_with(&path, &Palette::new(vec![Rgba::black(); 257]), Dither::None)));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn index_at()
+    {
+        let ms = Duration::from_millis;
+        let anim = frames(&[100, 50]);
+
+        assert_eq!(anim.index_at(ms(0)), 0);
+        assert_eq!(anim.index_at(ms(99)), 0);
+        assert_eq!(anim.index_at(ms(100)), 1);
+        assert_eq!(anim.index_at(ms(149)), 1);
+        // looped
+        assert_eq!(anim.index_at(ms(150)), 0);
+        assert_eq!(anim.index_at(ms(1000)), 1);
+
+        let still = frames(&[0, 0]);
+        assert_eq!(still.duration(), Duration::default());
+        assert_eq!(still.index_at(ms(0)), 0);
+        assert_eq!(still.index_at(ms(1234)), 0);
+    }
+}
@@ -0,0 +1,192 @@
+use image::{ ColorType, ImageError };
+
+use std::path::Path;
+use std::fmt;
+
+use crate::draw::{ Bitmap, PixelBuf, Image };
+use crate::math::Rgba;
+
+/// per-pixel difference between two bitmaps, see `Bitmap::diff`
+#[derive(Clone)]
+pub struct Diff
+{
+    /// largest difference in any one channel, alpha included
+    pub max_error: u8,
+    /// peak signal-to-noise ratio in decibels, over all channels.
+    /// infinite if both bitmaps are identical
+    pub psnr: f64,
+    /// number of pixels that differ at all
+    pub differing: usize,
+    /// the first bitmap faded out, with differing pixels in red. the
+    /// larger the difference, the brighter the red
+    pub image: Image,
+}
+
+impl Diff
+{
+    /// are both bitmaps identical?
+    #[inline]
+    pub fn is_identical(&self) -> bool
+    {
+        self.differing == 0
+    }
+}
+
+impl fmt::Debug for Diff
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("Diff")
+            .field("max_error", &self.max_error)
+            .field("psnr", &self.psnr)
+            .field("differing", &self.differing)
+            .field("image", &self.image.size())
+            .finish()
+    }
+}
+
+impl<I, B: PixelBuf> Bitmap<I, B>
+{
+    /// compare this bitmap with another one of the same size, pixel
+    /// by pixel. panics if their sizes differ
+    /// ```
+    /// let diff = c.diff(&reference);
+    /// println!("{} pixels differ, PSNR: {:.2}dB", diff.differing, diff.psnr);
+    /// ```
+    pub fn diff<I2, B2: PixelBuf>(&self, other: &Bitmap<I2, B2>) -> Diff
+    {
+        assert_eq!(self.size(), other.size(), "bitmap sizes differ");
+
+        let w = self.width();
+
+        let mut max_error = 0;
+        let mut differing = 0;
+        // sum of squared errors
+        let mut sse = 0u64;
+        let mut image = Vec::with_capacity(self.area() * 4);
+
+        for y in 0..self.height()
+        {
+            for (a, b) in self.buf.row(y, w).iter().zip(other.buf.row(y, w))
+            {
+                let errors = [a.r, a.g, a.b, a.a]
+                    .iter()
+                    .zip(&[b.r, b.g, b.b, b.a])
+                    .map(|(&a, &b)| if a > b { a - b } else { b - a })
+                    .collect::<Vec<_>>();
+                let error = errors.iter().copied().max().unwrap();
+
+                sse += errors.iter().map(|&e| e as u64 * e as u64).sum::<u64>();
+                max_error = max_error.max(error);
+
+                let px = match error
+                {
+                    0 =>
+                    {
+                        // faded out luma
+                        let luma = (a.r as u32 * 299 + a.g as u32 * 587 + a.b as u32 * 114) / 1000;
+                        let g = 191 + (luma / 4) as u8;
+
+                        Rgba::new(g, g, g, 0xff)
+                    }
+                    _ =>
+                    {
+                        differing += 1;
+
+                        Rgba::new(128 + error / 2, 0, 0, 0xff)
+                    }
+                };
+                image.extend_from_slice(&[px.r, px.g, px.b, px.a]);
+            }
+        }
+
+        let mse = sse as f64 / (self.area() * 4).max(1) as f64;
+        let psnr = 10.0 * (255.0 * 255.0 / mse).log10();
+
+        Diff { max_error, psnr, differing, image: Bitmap::new((), image, self.size()) }
+    }
+
+    /// save this bitmap to a file, its format guessed from the path's
+    /// extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageError>
+    {
+        let w = self.width();
+        let buf = (0..self.height())
+            .flat_map(|y| self.buf.raw_row(y, w).iter().copied())
+            .collect::<Vec<_>>();
+
+        image::save_buffer(path, &buf, w as u32, self.height() as u32, ColorType::Rgba8)
+    }
+}
+
+/// compare a bitmap with the reference image at `path`, allowing at
+/// most `tolerance` difference in any channel. panics on a mismatch,
+/// writing "<path>.actual.png" and "<path>.diff.png" next to the
+/// reference so they can be inspected(or the former renamed into the
+/// new reference). see `assert_image_eq!`
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_image_eq<I, B: PixelBuf>(bitmap: &Bitmap<I, B>, path: impl AsRef<Path>, tolerance: u8)
+{
+    let path = path.as_ref();
+    let actual = path.with_extension("actual.png");
+    let diff_path = path.with_extension("diff.png");
+
+    let expected = match Image::open(path)
+    {
+        Ok(img) => img,
+        Err(e) =>
+        {
+            let _ = bitmap.save(&actual);
+            panic!("can't open reference image {}: {}, actual image written to {}", path.display(), e, actual.display());
+        }
+    };
+
+    if expected.size() != bitmap.size()
+    {
+        let _ = bitmap.save(&actual);
+        panic!
+        (
+            "image is {}x{} but reference {} is {}x{}, actual image written to {}",
+            bitmap.width(), bitmap.height(), path.display(), expected.width(), expected.height(), actual.display()
+        );
+    }
+
+    let diff = bitmap.diff(&expected);
+    if diff.max_error > tolerance
+    {
+        let _ = bitmap.save(&actual);
+        let _ = diff.image.save(&diff_path);
+        panic!
+        (
+            "image differs from reference {}: {} pixels differ, max error {} > {}, PSNR {:.2}dB. see {} and {}",
+            path.display(), diff.differing, diff.max_error, tolerance, diff.psnr, actual.display(), diff_path.display()
+        );
+    }
+
+    // clean up after previous failures
+    let _ = std::fs::remove_file(&actual);
+    let _ = std::fs::remove_file(&diff_path);
+}
+
+/// assert that a bitmap matches the reference PNG on disk, optionally
+/// allowing a small difference in any one channel. on a mismatch, the
+/// actual image and a visual diff are written next to the reference
+/// before panicking
+/// ```
+/// assert_image_eq!(img, "tests/golden/triangle.png");
+/// // allow off-by-one rounding
+/// assert_image_eq!(img, "tests/golden/gradient.png", 1);
+/// ```
+#[macro_export]
+macro_rules! assert_image_eq
+{
+    ($bitmap:expr, $path:expr) =>
+    {
+        $crate::draw::assert_image_eq(&$bitmap, $path, 0)
+    };
+    ($bitmap:expr, $path:expr, $tolerance:expr) =>
+    {
+        $crate::draw::assert_image_eq(&$bitmap, $path, $tolerance)
+    };
+}
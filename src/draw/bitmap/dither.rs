@@ -0,0 +1,412 @@
+use std::sync::OnceLock;
+
+use crate::draw::{ Bitmap, PixelBufMut, Palette };
+use crate::math::{ Rgb, Rgba };
+
+/// size of the tiled blue noise threshold map
+const BLUE_NOISE_SIZE: usize = 32;
+
+/// how the error left by reducing a bitmap's colours to a palette
+/// is spread out, see `Bitmap::quantize`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dither
+{
+    /// no dithering, each pixel becomes its nearest palette colour
+    None,
+    /// error diffusion to 4 neighbours, smooth but grainy
+    FloydSteinberg,
+    /// error diffusion to 6 neighbours, only 3/4 of the error is kept
+    /// giving more contrast(as on the original Macintosh)
+    Atkinson,
+    /// ordered dithering with an n x n Bayer matrix, n being rounded up
+    /// to a power of two between 2 and 16. gives a regular crosshatch
+    Bayer(usize),
+    /// ordered dithering with a tiled blue noise threshold map. gives an
+    /// even texture without a visible pattern
+    BlueNoise,
+}
+
+impl<I, B: PixelBufMut> Bitmap<I, B>
+{
+    /// reduce this bitmap's colours to those in `palette`, dithering
+    /// the difference away. only red, green and blue are changed, alpha
+    /// is left as is
+    /// ```
+    /// c.quantize(&Palette::pico8(), Dither::Atkinson);
+    /// ```
+    pub fn quantize(&mut self, palette: &Palette, dither: Dither)
+    {
+        if palette.is_empty()
+        {
+            return;
+        }
+
+        match dither
+        {
+            Dither::None => self.ordered(palette, &[0.5], 1),
+            Dither::FloydSteinberg =>
+            {
+                self.diffuse(palette, &[(1, 0, 7.0 / 16.0), (-1, 1, 3.0 / 16.0), (0, 1, 5.0 / 16.0), (1, 1, 1.0 / 16.0)]);
+            }
+            Dither::Atkinson =>
+            {
+                self.diffuse(palette, &[(1, 0, 0.125), (2, 0, 0.125), (-1, 1, 0.125), (0, 1, 0.125), (1, 1, 0.125), (0, 2, 0.125)]);
+            }
+            Dither::Bayer(n) =>
+            {
+                let n = n.clamp(2, 16).next_power_of_two();
+
+                self.ordered(palette, &bayer(n), n);
+            }
+            Dither::BlueNoise => self.ordered(palette, blue_noise_map(), BLUE_NOISE_SIZE),
+        }
+    }
+
+    /// ordered dithering with a `n` x `n` map of thresholds in [0, 1),
+    /// tiled over this bitmap
+    fn ordered(&mut self, palette: &Palette, thresholds: &[f32], n: usize)
+    {
+        // how far a threshold can push a colour: the step between levels
+        // if the palette's colours were evenly spread over each channel
+        let levels = (palette.len() as f32).cbrt().round().max(2.0);
+        let spread = 255.0 / (levels - 1.0);
+
+        let w = self.width();
+        for y in 0..self.height()
+        {
+            let row = self.buf.row_mut(y, w);
+            let thresholds = &thresholds[(y % n) * n..(y % n + 1) * n];
+
+            for (x, px) in row.iter_mut().enumerate()
+            {
+                let offset = (thresholds[x % n] - 0.5) * spread;
+
+                *px = nearest(palette, rgb(*px) + offset, px.a);
+            }
+        }
+    }
+
+    /// error diffusion, spreading each pixel's error to the neighbours
+    /// in `kernel` as (x offset, y offset, weight)
+    fn diffuse(&mut self, palette: &Palette, kernel: &[(isize, usize, f32)])
+    {
+        let w = self.width();
+
+        // errors carried to the current row and the ones below it, as
+        // a ring of rows
+        let rows = kernel.iter().map(|&(_, dy, _)| dy).max().unwrap_or(0) + 1;
+        let mut errors = vec![Rgb::<f32>::zero(); w * rows];
+
+        for y in 0..self.height()
+        {
+            let row = self.buf.row_mut(y, w);
+
+            for (x, px) in row.iter_mut().enumerate()
+            {
+                let i = (y % rows) * w + x;
+                let col = rgb(*px) + errors[i];
+                errors[i] = Rgb::zero();
+
+                *px = nearest(palette, col, px.a);
+
+                // spread error
+                let err = col - rgb(*px);
+                for &(dx, dy, weight) in kernel
+                {
+                    let nx = x as isize + dx;
+                    if nx >= 0 && (nx as usize) < w
+                    {
+                        errors[((y + dy) % rows) * w + nx as usize] += err * weight;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// get the red, green and blue of `col` as floats
+#[inline]
+fn rgb(col: Rgba<u8>) -> Rgb<f32>
+{
+    Rgb::new(col.r, col.g, col.b).as_()
+}
+
+/// get the colour in `palette` nearest to `col`, with alpha `a`
+#[inline]
+fn nearest(palette: &Palette, col: Rgb<f32>, a: u8) -> Rgba<u8>
+{
+    let i = palette.nearest_index(col).unwrap();
+
+    Rgba { a, ..palette.colours()[i] }
+}
+
+/// generate the `n` x `n` Bayer matrix, `n` being a power of two, as
+/// thresholds in [0, 1)
+fn bayer(n: usize) -> Vec<f32>
+{
+    // each step quadruples the matrix:
+    // | 4m + 0  4m + 2 |
+    // | 4m + 3  4m + 1 |
+    let mut m = vec![0u32];
+    let mut size = 1;
+
+    while size < n
+    {
+        let next = size * 2;
+        let mut grown = vec![0; next * next];
+
+        for y in 0..next
+        {
+            for x in 0..next
+            {
+                let quad = match (x / size, y / size)
+                {
+                    (0, 0) => 0,
+                    (1, 1) => 1,
+                    (1, 0) => 2,
+                    _ => 3,
+                };
+                grown[y * next + x] = 4 * m[(y % size) * size + x % size] + quad;
+            }
+        }
+        m = grown;
+        size = next;
+    }
+
+    let len = (n * n) as f32;
+    m
+        .into_iter()
+        .map(|v| (v as f32 + 0.5) / len)
+        .collect()
+}
+
+/// the shared blue noise threshold map, generated once on first use
+/// since void-and-cluster is slow
+fn blue_noise_map() -> &'static [f32]
+{
+    static MAP: OnceLock<Vec<f32>> = OnceLock::new();
+
+    MAP.get_or_init(|| blue_noise(BLUE_NOISE_SIZE))
+}
+
+/// generate a tileable `n` x `n` blue noise threshold map, values in
+/// [0, 1), with the void-and-cluster method
+fn blue_noise(n: usize) -> Vec<f32>
+{
+    let len = n * n;
+
+    // gaussian energy each point adds to its surroundings, by toroidal
+    // offset
+    let sigma = 1.5f32;
+    let gauss = (0..len)
+        .map(|i|
+        {
+            let wrap = |d: usize| d.min(n - d) as f32;
+            let (dx, dy) = (wrap(i % n), wrap(i / n));
+
+            (-(dx * dx + dy * dy) / (2.0 * sigma * sigma)).exp()
+        })
+        .collect::<Vec<_>>();
+
+    // each pixel's energy, from every set point
+    let mut energy = vec![0.0f32; len];
+    let mut points = vec![false; len];
+
+    let toggle = |points: &mut Vec<bool>, energy: &mut Vec<f32>, i: usize|
+    {
+        points[i] = !points[i];
+        let sign = if points[i] { 1.0 } else { -1.0 };
+
+        let (px, py) = (i % n, i / n);
+        for j in 0..len
+        {
+            let dx = (j % n + n - px) % n;
+            let dy = (j / n + n - py) % n;
+
+            energy[j] += sign * gauss[dy * n + dx];
+        }
+    };
+    // set point with the most energy, or unset one with the least
+    let tightest = |points: &[bool], energy: &[f32]| (0..len)
+        .filter(|&i| points[i])
+        .max_by(|&a, &b| energy[a].partial_cmp(&energy[b]).unwrap())
+        .unwrap();
+    let largest_void = |points: &[bool], energy: &[f32]| (0..len)
+        .filter(|&i| !points[i])
+        .min_by(|&a, &b| energy[a].partial_cmp(&energy[b]).unwrap())
+        .unwrap();
+
+    // initial pattern, a tenth of the points picked with a fixed
+    // xorshift so every call gives the same map
+    let mut state = 0x9e37_79b9u32;
+    let ones = (len / 10).max(1);
+    let mut set = 0;
+    while set < ones
+    {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+
+        let i = state as usize % len;
+        if !points[i]
+        {
+            toggle(&mut points, &mut energy, i);
+            set += 1;
+        }
+    }
+
+    // move points from clusters to voids until evenly spread
+    loop
+    {
+        let cluster = tightest(&points, &energy);
+        toggle(&mut points, &mut energy, cluster);
+
+        let void = largest_void(&points, &energy);
+        toggle(&mut points, &mut energy, void);
+
+        if void == cluster
+        {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0usize; len];
+
+    // rank initial points by removing the tightest clusters first
+    let (mut pts, mut en) = (points.clone(), energy.clone());
+    for rank in (0..ones).rev()
+    {
+        let cluster = tightest(&pts, &en);
+        toggle(&mut pts, &mut en, cluster);
+        ranks[cluster] = rank;
+    }
+    // then rank the rest by filling the largest voids
+    for rank in ones..len
+    {
+        let void = largest_void(&points, &energy);
+        toggle(&mut points, &mut energy, void);
+        ranks[void] = rank;
+    }
+
+    ranks
+        .into_iter()
+        .map(|r| (r as f32 + 0.5) / len as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::draw::Image;
+
+    /// 32x16 image covering a spread of colours, half transparent
+    fn gradient() -> Image
+    {
+        let mut img = Image::blank((32, 16));
+        for (i, px) in img.pixels_mut().iter_mut().enumerate()
+        {
+            let (x, y) = (i % 32, i / 32);
+
+            *px = Rgba::new((x * 8) as u8, (y * 16) as u8, ((x + y) * 5) as u8, 0x80);
+        }
+        img
+    }
+
+    /// get the fraction of `img`'s pixels that are white
+    fn white(img: &Image) -> f32
+    {
+        let count = img
+            .pixels()
+            .iter()
+            .filter(|px| px.r == 0xff)
+            .count();
+
+        count as f32 / img.pixels().len() as f32
+    }
+
+    #[test]
+    fn palette_colours_only()
+    {
+        let palette = Palette::pico8();
+
+        for dither in [Dither::None, Dither::FloydSteinberg, Dither::Atkinson, Dither::Bayer(4), Dither::BlueNoise]
+        {
+            let mut img = gradient();
+            img.quantize(&palette, dither);
+
+            for px in img.pixels()
+            {
+                assert!(palette.colours().contains(&Rgba { a: 0xff, ..*px }), "{:?} gave {:?}", dither, px);
+                assert_eq!(px.a, 0x80);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_palette()
+    {
+        let mut img = gradient();
+        img.quantize(&Palette::new(vec![]), Dither::FloydSteinberg);
+
+        assert_eq!(img.pixels(), gradient().pixels());
+    }
+
+    #[test]
+    fn half_grey()
+    {
+        for dither in [Dither::FloydSteinberg, Dither::Atkinson, Dither::Bayer(2), Dither::Bayer(8), Dither::BlueNoise]
+        {
+            let mut img = Image::blank((32, 32));
+            img.background(Rgba::new(0x80, 0x80, 0x80, 0xff));
+            img.quantize(&Palette::one_bit(), dither);
+
+            let white = white(&img);
+            assert!((0.45..=0.55).contains(&white), "{:?} gave {} white", dither, white);
+        }
+
+        // without dithering it's all one colour
+        let mut img = Image::blank((32, 32));
+        img.background(Rgba::new(0x80, 0x80, 0x80, 0xff));
+        img.quantize(&Palette::one_bit(), Dither::None);
+
+        assert_eq!(white(&img), 1.0);
+    }
+
+    #[test]
+    fn bayer_thresholds()
+    {
+        // every threshold appears once
+        for n in [2, 4, 8, 16]
+        {
+            let mut ranks = bayer(n)
+                .into_iter()
+                .map(|t| (t * (n * n) as f32) as usize)
+                .collect::<Vec<_>>();
+            ranks.sort_unstable();
+
+            assert_eq!(ranks, (0..n * n).collect::<Vec<_>>());
+        }
+
+        assert_eq!(bayer(2), [0.125, 0.625, 0.875, 0.375]);
+    }
+
+    #[test]
+    fn blue_noise_deterministic()
+    {
+        let map = blue_noise(8);
+
+        assert_eq!(map, blue_noise(8));
+        assert_eq!(blue_noise_map(), blue_noise(BLUE_NOISE_SIZE));
+
+        // every threshold appears once
+        let mut ranks = map
+            .into_iter()
+            .map(|t| (t * 64.0) as usize)
+            .collect::<Vec<_>>();
+        ranks.sort_unstable();
+
+        assert_eq!(ranks, (0..64).collect::<Vec<_>>());
+    }
+}
@@ -1,13 +1,29 @@
-use crate::draw::{ Bitmap, PixelBuf, PixelBufMut };
+use crate::draw::{ Bitmap, PixelBuf, PixelBufMut, Paint, Font };
 use crate::math::{ Vec2, Rgba };
 
 impl<I, B: PixelBufMut> Bitmap<I, B>
 {
-    /// fills this entire bitmap with a color. this is much more efficient
-    /// than iterating through the pixels and individually setting their
-    /// colors.
-    pub fn background(&mut self, col: Rgba<u8>)
+    /// fills this entire bitmap with a color, or any other `Paint`. for
+    /// a solid color, this is much more efficient than iterating through
+    /// the pixels and individually setting their colors.
+    pub fn background(&mut self, paint: impl Into<Paint>)
     {
+        let col = match paint.into()
+        {
+            Paint::Solid(col) => col,
+            // paint isn't uniform, fill row-by-row
+            paint =>
+            {
+                for y in 0..self.height()
+                {
+                    let row = self.buf.row_mut(y, self.width());
+
+                    fill_span(row, Vec2::new(0, y as i32), &paint);
+                }
+                return;
+            }
+        };
+
         // most optimized, incremental fill method:
         if let Some(buf) = self.buf.try_pixels_mut()
         {
@@ -63,13 +79,14 @@ impl<I, B: PixelBufMut> Bitmap<I, B>
     {
         use crate::util::Triangle;
 
-        // fill
-        if let Some(fill) = self.fill
+        // fill, taking the paint out while the pixels are borrowed
+        if let Some(fill) = self.fill.take()
         {
             for (pos, _) in Triangle::new_bounded([a, b, c], self.size().as_())
             {
-                self[pos] = fill;
+                self[pos] = fill.sample(pos);
             }
+            self.fill = Some(fill);
         }
 
         // stroke
@@ -101,40 +118,58 @@ impl<I, B: PixelBufMut> Bitmap<I, B>
             return;
         }
 
-        // fill
-        if let Some(fill) = self.fill
+        // fill, taking the paint out while the pixels are borrowed
+        if let Some(fill) = self.fill.take()
         {
             // conversions are now safe
             let pos: Vec2<usize> = pos.as_();
             let siz: Vec2<usize> = siz.as_();
 
-            // get the first row
-            let row0 = 
+            match &fill
             {
-                // SAFETY: borrow checker isn't smart enough to know
-                // we're only borrowing the first row once
-                let this = &*self as *const Self as *mut Self;
-
-                unsafe { &mut *this }.buf.row_mut(pos.y, self.width())
-            };
-            // get only the columns we care about in the first row
-            let row0 = &mut row0[pos.x..pos.x + siz.x];
-
-            // fill the first row
-            incremental_fill(row0, fill);
-
-            // fill every other row
-            for y in pos.y + 1..pos.y + siz.y
-            {
-                // get the row
-                let row = self.buf.row_mut(y, self.width());
-
-                // get only the columns we care about
-                let row = &mut row[pos.x..pos.x + siz.x];
-
-                // copy the first row into this one
-                row.copy_from_slice(row0);
+                &Paint::Solid(fill) =>
+                {
+                    // get the first row
+                    let row0 = 
+                    {
+                        // SAFETY: borrow checker isn't smart enough to know
+                        // we're only borrowing the first row once
+                        let this = &*self as *const Self as *mut Self;
+
+                        unsafe { &mut *this }.buf.row_mut(pos.y, self.width())
+                    };
+                    // get only the columns we care about in the first row
+                    let row0 = &mut row0[pos.x..pos.x + siz.x];
+
+                    // fill the first row
+                    incremental_fill(row0, fill);
+
+                    // fill every other row
+                    for y in pos.y + 1..pos.y + siz.y
+                    {
+                        // get the row
+                        let row = self.buf.row_mut(y, self.width());
+
+                        // get only the columns we care about
+                        let row = &mut row[pos.x..pos.x + siz.x];
+
+                        // copy the first row into this one
+                        row.copy_from_slice(row0);
+                    }
+                }
+                // paint isn't uniform, fill row-by-row
+                paint =>
+                {
+                    for y in pos.y..pos.y + siz.y
+                    {
+                        // get only the columns we care about
+                        let row = &mut self.buf.row_mut(y, self.width())[pos.x..pos.x + siz.x];
+
+                        fill_span(row, Vec2::new(pos.x, y).as_(), paint);
+                    }
+                }
             }
+            self.fill = Some(fill);
         }
         // stroke
         if self.stroke.is_some()
@@ -165,16 +200,16 @@ impl<I, B: PixelBufMut> Bitmap<I, B>
         let src_min = pos.map2(src_size, |p, s| (if p < 0 { -p } else { 0 }).min(s));
         let src_max = pos.map3(src_size, dst_size, |p, ss, ds| if p + ss > ds { ds - p } else { ss });
   
-        // as you copy to dst's pixels; [0, dst_width] and [0, dst_height]
-        let dst_min_x = if pos.x < 0 { 0 } else { pos.x } as usize;
-        let dst_max_x = dst_min_x + (src_max.x - src_min.x) as usize;
-
         // nothing to copy
-        if dst_max_x < dst_min_x
+        if src_min.x >= src_max.x || src_min.y >= src_max.y
         {
             return;
         }
 
+        // as you copy to dst's pixels; [0, dst_width] and [0, dst_height]
+        let dst_min_x = pos.x.max(0) as usize;
+        let dst_max_x = dst_min_x + (src_max.x - src_min.x) as usize;
+
         // now safe to convert
         let src_min: Vec2<usize> = src_min.as_();
         let src_max: Vec2<usize> = src_max.as_();
@@ -188,7 +223,7 @@ impl<I, B: PixelBufMut> Bitmap<I, B>
             let src_buf = &src_buf[src_min.x..src_max.x];
 
             // get the destination image's row
-            let dst_buf = self.buf.row_mut(y + pos.y as usize, self.width());
+            let dst_buf = self.buf.row_mut((y as i32 + pos.y) as usize, self.width());
             // take only the columns we care about
             let dst_buf = &mut dst_buf[dst_min_x..dst_max_x];
 
@@ -196,6 +231,70 @@ impl<I, B: PixelBufMut> Bitmap<I, B>
             dst_buf.copy_from_slice(src_buf);
         }
     }
+
+    /// draw `text` with a bitmap `font`, the top-left corner of its
+    /// first line at `pos`. glyphs are tinted with the fill paint and
+    /// blended over this bitmap using their alpha. characters the font
+    /// doesn't have are skipped, see also `Font::measure`
+    pub fn text(&mut self, font: &Font, pos: Vec2<i32>, text: &str)
+    {
+        // take the paint out while the pixels are borrowed
+        let fill = match self.fill.take()
+        {
+            Some(fill) => fill,
+            None => return,
+        };
+        let bounds: Vec2<i32> = self.size().as_::<i32>().into();
+
+        font.layout(text, |glyph, at|
+        {
+            let page = font.page(glyph.page);
+            let src: Vec2<i32> = Vec2::new(glyph.rect.x, glyph.rect.y).as_();
+
+            for y in 0..glyph.rect.h as i32
+            {
+                for x in 0..glyph.rect.w as i32
+                {
+                    let dst = pos + at + Vec2::new(x, y);
+                    if dst.x < 0 || dst.y < 0 || dst.x >= bounds.x || dst.y >= bounds.y
+                    {
+                        continue;
+                    }
+
+                    let coverage = page[src + Vec2::new(x, y)].a;
+                    if coverage > 0
+                    {
+                        self[dst] = blend(self[dst], fill.sample(dst), coverage);
+                    }
+                }
+            }
+        });
+        self.fill = Some(fill);
+    }
+}
+
+/// blend `src` over `dst`, with `src`'s alpha scaled by `coverage`
+fn blend(dst: Rgba<u8>, src: Rgba<u8>, coverage: u8) -> Rgba<u8>
+{
+    // opacity of `src`, in [0, 255 * 255]
+    let t = src.a as u32 * coverage as u32;
+    let mix = |d: u8, s: u8| ((d as u32 * (65025 - t) + s as u32 * t + 32512) / 65025) as u8;
+
+    Rgba::new(mix(dst.r, src.r), mix(dst.g, src.g), mix(dst.b, src.b), mix(dst.a, 0xff))
+}
+
+/// fill a row of pixels `buf`, whose first pixel is at
+/// `pos`, with a `Paint`
+fn fill_span(buf: &mut [Rgba<u8>], pos: Vec2<i32>, paint: &Paint)
+{
+    match paint
+    {
+        Paint::Solid(col) => incremental_fill(buf, *col),
+        paint => for (x, px) in (pos.x..).zip(buf.iter_mut())
+        {
+            *px = paint.sample(Vec2::new(x, pos.y));
+        }
+    }
 }
 
 /// incrementally fill a slice `buf` with `ele`
@@ -0,0 +1,282 @@
+use crate::draw::{ Bitmap, PixelBuf, PixelBufMut, Paint, Mask };
+use crate::math::{ Vec2, Rgba };
+
+/// which neighbours of a pixel are connected to it when flood
+/// filling
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Connectivity
+{
+    /// up, down, left and right
+    Four,
+    /// up, down, left, right and diagonals
+    Eight,
+}
+
+impl<I, B: PixelBuf> Bitmap<I, B>
+{
+    /// select the region connected to `seed` whose colours are within
+    /// `tolerance` of the colour at `seed`, without painting anything.
+    /// `tolerance` is the largest difference allowed in any one channel,
+    /// alpha included, so `0` only selects the exact same colour
+    ///
+    /// returns an empty `Mask` if `seed` is out of bounds
+    pub fn flood_select(&self, seed: Vec2<i32>, tolerance: u8, connectivity: Connectivity) -> Mask
+    {
+        let mut mask = Mask::new(self.size());
+
+        let (w, h) = (self.width(), self.height());
+
+        // out of bounds
+        if seed.x < 0 || seed.y < 0 || seed.x as usize >= w || seed.y as usize >= h
+        {
+            return mask;
+        }
+
+        // colour to match
+        let target = self.buf.row(seed.y as usize, w)[seed.x as usize];
+        let matches = |col: Rgba<u8>|
+        {
+            let d = |a: u8, b: u8| if a > b { a - b } else { b - a };
+
+            d(col.r, target.r) <= tolerance
+                && d(col.g, target.g) <= tolerance
+                && d(col.b, target.b) <= tolerance
+                && d(col.a, target.a) <= tolerance
+        };
+        // how far past a span's ends the rows above and below it are
+        // scanned, to reach diagonals
+        let reach = match connectivity
+        {
+            Connectivity::Four => 0,
+            Connectivity::Eight => 1,
+        };
+
+        // scanline span algorithm: each seed is extended into the widest
+        // horizontal span it's part of, then the rows above and below that
+        // span are scanned for new seeds, one per span found
+        let mut stack = vec![(seed.x as usize, seed.y as usize)];
+
+        while let Some((x, y)) = stack.pop()
+        {
+            let row = self.buf.row(y, w);
+            let sel = mask.row(y);
+
+            // already visited or not part of region
+            if sel[x] || !matches(row[x])
+            {
+                continue;
+            }
+
+            // extend to the left and right
+            let mut l = x;
+            while l > 0 && !sel[l - 1] && matches(row[l - 1])
+            {
+                l -= 1;
+            }
+            let mut r = x;
+            while r + 1 < w && !sel[r + 1] && matches(row[r + 1])
+            {
+                r += 1;
+            }
+
+            // select the span
+            for b in &mut mask.row_mut(y)[l..=r]
+            {
+                *b = true;
+            }
+
+            // scan above and below
+            let lo = l.saturating_sub(reach);
+            let hi = (r + reach).min(w - 1);
+
+            let above = y.checked_sub(1);
+            let below = if y + 1 < h { Some(y + 1) } else { None };
+
+            for ny in above.into_iter().chain(below)
+            {
+                let row = self.buf.row(ny, w);
+                let sel = mask.row(ny);
+
+                // push the start of every span found
+                let mut in_span = false;
+                for nx in lo..=hi
+                {
+                    let open = !sel[nx] && matches(row[nx]);
+
+                    if open && !in_span
+                    {
+                        stack.push((nx, ny));
+                    }
+                    in_span = open;
+                }
+            }
+        }
+        mask
+    }
+}
+
+impl<I, B: PixelBufMut> Bitmap<I, B>
+{
+    /// bucket fill the region connected to `seed`, up, down, left and
+    /// right, whose colours are within `tolerance` of the colour at `seed`.
+    /// see `Bitmap::flood_select` for more on `tolerance`
+    ///
+    /// this is a shorthand for:
+    /// ```
+    /// let mask = c.flood_select(seed, tolerance, Connectivity::Four);
+    /// c.fill_mask(&mask, paint);
+    /// ```
+    pub fn flood_fill(&mut self, seed: Vec2<i32>, paint: impl Into<Paint>, tolerance: u8)
+    {
+        self.flood_fill_with(seed, paint, tolerance, Connectivity::Four);
+    }
+
+    /// same as `Bitmap::flood_fill`, with a choice of which neighbours
+    /// are connected
+    pub fn flood_fill_with(&mut self, seed: Vec2<i32>, paint: impl Into<Paint>, tolerance: u8, connectivity: Connectivity)
+    {
+        let mask = self.flood_select(seed, tolerance, connectivity);
+
+        self.fill_mask(&mask, paint);
+    }
+
+    /// paint every pixel selected in `mask`, which must be the same
+    /// size as this bitmap
+    pub fn fill_mask(&mut self, mask: &Mask, paint: impl Into<Paint>)
+    {
+        assert_eq!(mask.size(), self.size(), "mask and bitmap sizes differ");
+
+        let paint = paint.into();
+
+        for y in 0..self.height()
+        {
+            let row = self.buf.row_mut(y, self.width());
+
+            for (x, (px, &sel)) in row.iter_mut().zip(mask.row(y)).enumerate()
+            {
+                if sel
+                {
+                    *px = paint.sample(Vec2::new(x as i32, y as i32));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::draw::Image;
+    use crate::math::Extent2;
+
+    const WHITE: Rgba<u8> = Rgba::new(0xff, 0xff, 0xff, 0xff);
+    const BLACK: Rgba<u8> = Rgba::new(0, 0, 0, 0xff);
+    const RED: Rgba<u8> = Rgba::new(0xff, 0, 0, 0xff);
+
+    /// 5x5 white image split by a black wall from its top-left
+    /// corner to its bottom-right one
+    fn diagonal() -> Image
+    {
+        let mut img = Image::blank((5, 5));
+
+        img.background(WHITE);
+        for i in 0..5
+        {
+            img.pixels_mut()[i * 5 + i] = BLACK;
+        }
+        img
+    }
+
+    #[test]
+    fn diagonal_wall()
+    {
+        let img = diagonal();
+
+        // the wall's corners only touch diagonally
+        let four = img.flood_select(Vec2::new(4, 0), 0, Connectivity::Four);
+        assert_eq!(four.count(), 10);
+        assert!(four.iter().all(|pos| pos.x > pos.y));
+
+        let eight = img.flood_select(Vec2::new(4, 0), 0, Connectivity::Eight);
+        assert_eq!(eight.count(), 20);
+        assert!(!eight.contains(Vec2::new(2, 2)));
+
+        // and the wall itself only holds together diagonally
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 0, Connectivity::Four).count(), 1);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 0, Connectivity::Eight).count(), 5);
+    }
+
+    #[test]
+    fn tolerance()
+    {
+        let mut img = Image::blank((5, 1));
+        let greys = [100, 110, 120, 90, 79];
+        for (px, &n) in img.pixels_mut().iter_mut().zip(&greys)
+        {
+            *px = Rgba::new(n, n, n, 0xff);
+        }
+
+        // differences of exactly `tolerance` are selected, compared
+        // to the seed rather than to neighbours
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 0, Connectivity::Four).count(), 1);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 9, Connectivity::Four).count(), 1);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 10, Connectivity::Four).count(), 2);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 20, Connectivity::Four).count(), 4);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 21, Connectivity::Four).count(), 5);
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 255, Connectivity::Four).count(), 5);
+
+        // alpha counts too
+        img.pixels_mut()[1].a = 0xff - 11;
+        assert_eq!(img.flood_select(Vec2::new(0, 0), 10, Connectivity::Four).count(), 1);
+    }
+
+    #[test]
+    fn seed_out_of_bounds()
+    {
+        let mut img = diagonal();
+
+        for &(x, y) in &[(-1, 0), (0, -1), (5, 0), (0, 5), (i32::MIN, i32::MAX), (i32::MAX, 2)]
+        {
+            assert!(img.flood_select(Vec2::new(x, y), 255, Connectivity::Eight).is_empty());
+
+            img.flood_fill(Vec2::new(x, y), RED, 255);
+            assert_eq!(img.pixels(), diagonal().pixels());
+        }
+    }
+
+    #[test]
+    fn fill_matches_select()
+    {
+        for &connectivity in &[Connectivity::Four, Connectivity::Eight]
+        {
+            let mut filled = diagonal();
+            filled.flood_fill_with(Vec2::new(4, 0), RED, 0, connectivity);
+
+            let mut masked = diagonal();
+            let mask = masked.flood_select(Vec2::new(4, 0), 0, connectivity);
+            masked.fill_mask(&mask, RED);
+
+            assert_eq!(filled.pixels(), masked.pixels());
+            for (i, px) in filled.pixels().iter().enumerate()
+            {
+                let pos = Vec2::new((i % 5) as i32, (i / 5) as i32);
+
+                assert_eq!(*px == RED, mask.contains(pos), "{:?}", pos);
+            }
+        }
+
+        // four-connected by default
+        let mut img = diagonal();
+        img.flood_fill(Vec2::new(4, 0), RED, 0);
+        assert_eq!(img.pixels().iter().filter(|&&px| px == RED).count(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "mask and bitmap sizes differ")]
+    fn fill_mask_size()
+    {
+        diagonal().fill_mask(&Mask::new(Extent2::new(4, 5)), RED);
+    }
+}
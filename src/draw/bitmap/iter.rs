@@ -119,6 +119,29 @@ impl<I, B: FlatPixelBufMut> Bitmap<I, B>
 
 impl<I, B: PixelBuf> Bitmap<I, B>
 {
+    /// get a "sub-bitmap" of this bitmap, of size `size` and whose top-left
+    /// corner is at `pos`, without copying any pixels. panics if it doesn't
+    /// fit entirely inside this bitmap
+    ///
+    /// the sub-bitmap's `id()` is `pos`, in pixel-space
+    pub fn view(&self, pos: Vec2<usize>, size: Extent2<usize>) -> Bitmap<Vec2<i32>, Chunk<'_>>
+    {
+        assert!
+        (
+            pos.x + size.w <= self.width() && pos.y + size.h <= self.height(),
+            "view out of bitmap bounds"
+        );
+
+        // create sparse 2D buffer(see `Chunks::buf` doc)
+        let buf = (pos.y..pos.y + size.h)
+            // go through each row in chunk
+            .map(|y| &self.buf.row(y, self.width())[pos.x..pos.x + size.w])
+            // collect to box(no other choice, chunk isn't contiguous)
+            .collect::<Box<_>>();
+
+        Bitmap::new(pos.as_(), Chunk(buf), size)
+    }
+
     /// iterate non-overlapping "sub-bitmaps" or "chunks" in this bitmap,
     /// of size `size`. the remaining pixels, if any, are discarded from
     /// the iterator.
@@ -140,20 +163,7 @@ impl<I, B: PixelBuf> Bitmap<I, B>
         {
             // (x, y) is chunk index; remap to top-left corner in pixel
             // space
-            let pos = Vec2::new(x * size.w, y * size.h);
-
-            // create sparse 2D buffer(see `Chunks::buf` doc)
-            let buf = (pos.y..pos.y + size.h)
-                // go through each row in chunk
-                .map(|y| &self.buf.row(y, self.width())[pos.x..pos.x + size.w])
-                // collect to box(no other choice, chunk isn't contiguous)
-                .collect::<Box<_>>();
-            
-            // convert pos
-            let pos = pos.as_();
-            
-            // return chunks
-            Bitmap::new(pos, Chunk(buf), size)
+            self.view(Vec2::new(x * size.w, y * size.h), size)
         })
     }
 
@@ -166,18 +176,7 @@ impl<I, B: PixelBuf> Bitmap<I, B>
         // once we have zig-zag indices, begin dividing chunks:
         .map(move |(x, y)|
         {
-            // create sparse 2D buffer(see `Chunks::buf` doc)
-            let buf = (y..y + size.h)
-                // go through each row in chunk
-                .map(|y| &self.buf.row(y, self.width())[x..x + size.w])
-                // collect to box(no other choice, chunk isn't contiguous)
-                .collect::<Box<_>>();
-            
-            // convert pos
-            let pos = Vec2::new(x as i32, y as i32);
-            
-            // return chunks
-            Bitmap::new(pos, Chunk(buf), size)
+            self.view(Vec2::new(x, y), size)
         })
     }
 
@@ -192,26 +191,13 @@ impl<I, B: PixelBuf> Bitmap<I, B>
         {
             // (x, y) is chunk index; remap to top-left corner in pixel
             // space
-            let pos = Vec2::new(x * inc.x, y * inc.y);
-
-            // create sparse 2D buffer(see `Chunks::buf` doc)
-            let buf = (pos.y..pos.y + size.h)
-                // go through each row in chunk
-                .map(|y| &self.buf.row(y, self.width())[pos.x..pos.x + size.w])
-                // collect to box(no other choice, chunk isn't contiguous)
-                .collect::<Box<_>>();
-            
-            // convert pos
-            let pos = pos.as_();
-            
-            // return chunks
-            Bitmap::new(pos, Chunk(buf), size)
+            self.view(Vec2::new(x * inc.x, y * inc.y), size)
         })
     }
 }
 
-/// a single chunk in [Bitmap::iter_pixel_chunks] and
-/// [Bitmap::iter_pixel_windows]
+/// a single chunk in [Bitmap::view], [Bitmap::iter_pixel_chunks]
+/// and [Bitmap::iter_pixel_windows]
 ///
 /// `self.0`:
 /// outer-most array is columns, where
@@ -226,6 +212,7 @@ impl<I, B: PixelBuf> Bitmap<I, B>
 /// in non-contiguous memory
 /// ```
 ///
+/// [Bitmap::view]: super::Bitmap::view
 /// [Bitmap::iter_pixel_chunks]: super::Bitmap::iter_pixel_chunks
 /// [Bitmap::iter_pixel_windows]: super::Bitmap::iter_pixel_windows
 pub struct Chunk<'a>(Box<[&'a [Rgba<u8>]]>);
@@ -1,11 +1,18 @@
 mod draw;
 mod iter;
 mod buf;
+mod flood;
+mod dither;
+mod diff;
 
 pub use self::buf::{ PixelBuf, PixelBufMut, FlatPixelBuf, FlatPixelBufMut };
 pub use self::iter::Chunk;
+pub use self::flood::Connectivity;
+pub use self::dither::Dither;
+pub use self::diff::{ Diff, assert_image_eq };
 
-use crate::math::{ Vec2, Rgba, Extent2 };
+use crate::draw::Paint;
+use crate::math::{ Vec2, Vec3, Rgba, Extent2, ColourMode };
 
 // represents a bitmap, which can be iterated and
 /// drawn to
@@ -26,8 +33,10 @@ pub struct Bitmap<I, B>
 
     /// current stroke colour, if any
     pub stroke: Option<Rgba<u8>>,
-    /// current fill colour, if any
-    pub fill: Option<Rgba<u8>>,
+    /// current fill paint, if any
+    pub fill: Option<Paint>,
+    /// how components passed to `Bitmap::colour` are interpreted
+    pub colour_mode: ColourMode,
 }
 
 impl<I, B> Bitmap<I, B>
@@ -37,9 +46,10 @@ impl<I, B> Bitmap<I, B>
     {
         // pen
         let stroke = Some(Rgba::white());
-        let fill = Some(Rgba::grey(0x80));
+        let fill = Some(Rgba::grey(0x80).into());
+        let colour_mode = ColourMode::Rgb;
         
-        Self { id, buf, size, stroke, fill }
+        Self { id, buf, size, stroke, fill, colour_mode }
     }
 
     /// get this bitmap' ID if it's used(otherwise
@@ -78,12 +88,15 @@ impl<I, B> Bitmap<I, B>
         self.width() * self.height()
     }
 
-    /// set the fill colour to be used for any future drawing calls.
-    /// this is a shorthand for `canvas.fill = Some(col)`
+    /// set the fill colour, or any other [Paint], to be used for any
+    /// future drawing calls. this is a shorthand for
+    /// `canvas.fill = Some(paint.into())`
+    ///
+    /// [Paint]: crate::draw::Paint
     #[inline]
-    pub fn fill(&mut self, col: Rgba<u8>)
+    pub fn fill(&mut self, paint: impl Into<Paint>)
     {
-        self.fill = Some(col);
+        self.fill = Some(paint.into());
     }
 
     /// set the stroke colour to be used for any future drawing calls
@@ -109,6 +122,33 @@ impl<I, B> Bitmap<I, B>
     {
         self.stroke = None;
     }
+
+    /// set how components passed to `Bitmap::colour` are interpreted.
+    /// this is a shorthand for `canvas.colour_mode = mode`
+    #[inline]
+    pub fn colour_mode(&mut self, mode: ColourMode)
+    {
+        self.colour_mode = mode;
+    }
+
+    /// create a fully opaque colour from its components, interpreted
+    /// with the current `ColourMode`
+    /// ```
+    /// c.colour_mode(ColourMode::Hsb);
+    /// c.fill(c.colour((200.0, 80.0, 90.0)));
+    /// ```
+    #[inline]
+    pub fn colour(&self, comps: impl Into<Vec3<f32>>) -> Rgba<u8>
+    {
+        self.colour_mode.colour(comps.into(), 255.0)
+    }
+
+    /// same as `Bitmap::colour`, with an alpha in [0, 255]
+    #[inline]
+    pub fn colour_alpha(&self, comps: impl Into<Vec3<f32>>, alpha: f32) -> Rgba<u8>
+    {
+        self.colour_mode.colour(comps.into(), alpha)
+    }
 }
 
 impl<I, B: FlatPixelBuf> Bitmap<I, B>
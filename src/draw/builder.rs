@@ -0,0 +1,314 @@
+use std::any::Any;
+
+use crate::draw::{ Canvas, CanvasId, Canvases, Image, ResizePolicy };
+use crate::math::{ Extent2, Vec2 };
+use crate::{ App, Sketch };
+
+/// builder for a new `Canvas` and its window, from
+/// `App::canvas`. the window is created once `build` is
+/// called and the enclosing function returns
+/// ```
+/// // 160x120 pixels, shown 4 times as big in a 640x480 window
+/// app.canvas("retro", (160, 120))
+///     .scale(4)
+///     .resizable(false)
+///     .build();
+/// ```
+pub struct CanvasBuilder<'a>
+{
+    /// app to create the canvas in
+    app: &'a mut App,
+    /// the canvas' settings so far
+    config: CanvasConfig,
+}
+
+/// a canvas' own draw function, for sketches of type `S`
+pub(crate) type DrawFn<S> = Box<dyn FnMut(&mut S, &mut Canvas, &mut Canvases)>;
+
+/// settings of a canvas' window, independent of winit
+pub(crate) struct CanvasConfig
+{
+    /// window title
+    pub title: String,
+    /// pixel buffer size, in pixels
+    pub size: Extent2<usize>,
+    /// size of a buffer pixel in the window, in logical pixels
+    pub scale: usize,
+    /// can the window be resized?
+    pub resizable: bool,
+    /// how the canvas fills its window once resized
+    pub policy: ResizePolicy,
+    /// borderless fullscreen on the current monitor?
+    pub fullscreen: bool,
+    /// does the window have a title bar and borders?
+    pub decorations: bool,
+    /// does the window stay above the others?
+    pub always_on_top: bool,
+    /// window position on the desktop, in logical pixels
+    pub position: Option<Vec2<i32>>,
+    /// is presenting synced to the display's refresh rate?
+    pub vsync: bool,
+    /// smallest window size, in logical pixels
+    pub min_size: Option<Extent2<usize>>,
+    /// biggest window size, in logical pixels
+    pub max_size: Option<Extent2<usize>>,
+    /// window icon
+    pub icon: Option<Image>,
+    /// a `DrawFn` called instead of `Sketch::draw`
+    pub draw: Option<Box<dyn Any>>,
+}
+
+impl<'a> CanvasBuilder<'a>
+{
+    /// start building a canvas with a pixel buffer of `size`
+    pub(crate) fn new(app: &'a mut App, title: String, size: Extent2<usize>) -> Self
+    {
+        Self { app, config: CanvasConfig::new(title, size) }
+    }
+
+    /// show each pixel of the buffer as a `scale`x`scale` square,
+    /// ie. a 160x120 canvas at scale 4 opens a 640x480 window.
+    /// defaults to 1
+    pub fn scale(mut self, scale: usize) -> Self
+    {
+        self.config.scale = scale.max(1);
+        self
+    }
+
+    /// allow resizing the window, or keep it at a fixed size.
+    /// defaults to `true`
+    pub fn resizable(mut self, resizable: bool) -> Self
+    {
+        self.config.resizable = resizable;
+        self
+    }
+
+    /// how the canvas fills its window once the window is
+    /// resized. defaults to `ResizePolicy::Integer`
+    pub fn resize_policy(mut self, policy: ResizePolicy) -> Self
+    {
+        self.config.policy = policy;
+        self
+    }
+
+    /// open the window borderless fullscreen, on the current
+    /// monitor. the window still gets its windowed size, which it
+    /// goes back to when leaving fullscreen. defaults to `false`
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self
+    {
+        self.config.fullscreen = fullscreen;
+        self
+    }
+
+    /// remove the window's title bar and borders. defaults to
+    /// `false`
+    pub fn borderless(mut self, borderless: bool) -> Self
+    {
+        self.config.decorations = !borderless;
+        self
+    }
+
+    /// keep the window above the others. defaults to `false`
+    pub fn always_on_top(mut self, always_on_top: bool) -> Self
+    {
+        self.config.always_on_top = always_on_top;
+        self
+    }
+
+    /// position of the window's top-left corner on the desktop,
+    /// in logical pixels. defaults to wherever the platform puts it
+    pub fn position(mut self, pos: impl Into<Vec2<i32>>) -> Self
+    {
+        self.config.position = Some(pos.into());
+        self
+    }
+
+    /// sync presenting to the display's refresh rate, or present
+    /// as soon as possible. defaults to `true`
+    pub fn vsync(mut self, vsync: bool) -> Self
+    {
+        self.config.vsync = vsync;
+        self
+    }
+
+    /// smallest size the window can be resized to, in logical
+    /// pixels
+    pub fn min_size(mut self, size: impl Into<Extent2<usize>>) -> Self
+    {
+        self.config.min_size = Some(size.into());
+        self
+    }
+
+    /// biggest size the window can be resized to, in logical
+    /// pixels
+    pub fn max_size(mut self, size: impl Into<Extent2<usize>>) -> Self
+    {
+        self.config.max_size = Some(size.into());
+        self
+    }
+
+    /// set the window's icon
+    pub fn icon(mut self, icon: Image) -> Self
+    {
+        self.config.icon = Some(icon);
+        self
+    }
+
+    /// draw this canvas with its own function instead of
+    /// `Sketch::draw`, which can also read the other canvases.
+    /// `S` has to be the sketch being run, or `run` stops with an
+    /// error once the canvas is created
+    /// ```
+    /// app.canvas("preview", (200, 200))
+    ///     .draw(Foo::draw_preview)
+    ///     .build();
+    /// ```
+    pub fn draw<S: Sketch>(mut self, draw: impl FnMut(&mut S, &mut Canvas, &mut Canvases) + 'static) -> Self
+    {
+        self.config.draw = Some(Box::new(Box::new(draw) as DrawFn<S>));
+        self
+    }
+
+    /// request the canvas, returning its ID. see
+    /// `App::create_canvas`
+    pub fn build(self) -> CanvasId
+    {
+        self.app.request_canvas(self.config)
+    }
+}
+
+impl CanvasConfig
+{
+    /// default settings for a canvas
+    pub(crate) fn new(title: String, size: Extent2<usize>) -> Self
+    {
+        Self
+        {
+            title,
+            size,
+            scale: 1,
+            resizable: true,
+            policy: ResizePolicy::default(),
+            fullscreen: false,
+            decorations: true,
+            always_on_top: false,
+            position: None,
+            vsync: true,
+            min_size: None,
+            max_size: None,
+            icon: None,
+            draw: None,
+        }
+    }
+
+    /// initial window size, in logical pixels: the buffer size
+    /// times the scale, kept within the min and max sizes. this is
+    /// the windowed size even when `fullscreen` is set, since the
+    /// monitor decides the fullscreen size and the window goes back
+    /// to this one when leaving fullscreen
+    pub(crate) fn window_size(&self) -> Extent2<usize>
+    {
+        let mut size = self.size * self.scale;
+
+        if let Some(max) = self.max_size
+        {
+            size = size.map2(max, usize::min);
+        }
+        if let Some(min) = self.min_size
+        {
+            size = size.map2(min, usize::max);
+        }
+        size
+    }
+
+    /// can the running sketch `S` call this canvas' draw function?
+    /// true if it doesn't have one
+    pub(crate) fn draws_with<S: Sketch>(&self) -> bool
+    {
+        self.draw.as_ref().map_or(true, |draw| draw.is::<DrawFn<S>>())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// settings for a 160x120 canvas
+    fn canvas() -> CanvasConfig
+    {
+        CanvasConfig::new("test".into(), Extent2::new(160, 120))
+    }
+
+    #[test]
+    fn window_size()
+    {
+        assert_eq!(canvas().window_size(), Extent2::new(160, 120));
+    }
+
+    #[test]
+    fn window_size_scale()
+    {
+        let config = CanvasConfig { scale: 4, ..canvas() };
+
+        assert_eq!(config.window_size(), Extent2::new(640, 480));
+    }
+
+    #[test]
+    fn window_size_limits()
+    {
+        // each dimension is clamped on its own
+        let config = CanvasConfig { scale: 4, max_size: Some(Extent2::new(500, 1000)), ..canvas() };
+        assert_eq!(config.window_size(), Extent2::new(500, 480));
+
+        let config = CanvasConfig { min_size: Some(Extent2::new(100, 200)), ..canvas() };
+        assert_eq!(config.window_size(), Extent2::new(160, 200));
+
+        // min wins over max if they overlap
+        let config = CanvasConfig
+        {
+            min_size: Some(Extent2::new(300, 300)),
+            max_size: Some(Extent2::new(200, 200)),
+            ..canvas()
+        };
+        assert_eq!(config.window_size(), Extent2::new(300, 300));
+    }
+
+    #[test]
+    fn window_size_fullscreen()
+    {
+        let config = CanvasConfig { scale: 2, fullscreen: true, ..canvas() };
+
+        assert_eq!(config.window_size(), Extent2::new(320, 240));
+    }
+
+    #[test]
+    fn draws_with()
+    {
+        struct Foo;
+        struct Bar;
+
+        impl Sketch for Foo
+        {
+            fn setup(_: &mut App) -> crate::Result<Self>
+            {
+                Ok(Foo)
+            }
+        }
+        impl Sketch for Bar
+        {
+            fn setup(_: &mut App) -> crate::Result<Self>
+            {
+                Ok(Bar)
+            }
+        }
+
+        let draw = |_: &mut Foo, _: &mut Canvas, _: &mut Canvases| { };
+        let config = CanvasConfig { draw: Some(Box::new(Box::new(draw) as DrawFn<Foo>)), ..canvas() };
+
+        assert!(config.draws_with::<Foo>());
+        assert!(!config.draws_with::<Bar>());
+        // without its own draw function any sketch draws it
+        assert!(canvas().draws_with::<Bar>());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::draw::Bitmap;
+use crate::app::Windows;
 
 /// represents a framebuffer, which does everything a [Bitmap]
 /// can while drawing to the screen and holding a reference to
@@ -7,6 +8,25 @@ use crate::draw::Bitmap;
 /// [Bitmap]: crate::Bitmap
 pub type Canvas<'a> = Bitmap<CanvasId, &'a mut [u8]>;
 
+/// the other canvases' pixels, as of the last time they
+/// were drawn, for a draw function registered with
+/// `CanvasBuilder::draw`. the canvas being drawn isn't in
+/// here
+/// ```
+/// fn draw_preview(&mut self, c: &mut Canvas, canvases: &mut Canvases)
+/// {
+///     if let Some(main) = canvases.get(self.main)
+///     {
+///         c.image(&main, v![0, 0]);
+///     }
+/// }
+/// ```
+pub struct Canvases<'a>
+{
+    /// every window, except the one being drawn to
+    windows: &'a mut Windows,
+}
+
 /// unique identifier for each [Canvas], and,
 /// thereby each window
 /// 
@@ -27,4 +47,22 @@ impl CanvasId
     {
         Self(self.0 + 1)
     }
-}
\ No newline at end of file
+}
+
+impl<'a> Canvases<'a>
+{
+    /// wrap every open window
+    pub(crate) fn new(windows: &'a mut Windows) -> Self
+    {
+        Self { windows }
+    }
+
+    /// borrow a canvas' pixels, or get `None` if it isn't open
+    /// or is the one being drawn
+    pub fn get(&mut self, id: CanvasId) -> Option<Bitmap<CanvasId, &[u8]>>
+    {
+        self.windows
+            .canvas_mut(&id)
+            .map(|window| window.canvas())
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::fmt;
+
+use crate::draw::Image;
+use crate::math::{ Vec2, Extent2, Rect };
+
+/// a bitmap font in the AngelCode BMFont text format(".fnt"), as
+/// exported by BMFont, Hiero or Littera. its pages are PNGs next to
+/// the ".fnt" file
+/// ```
+/// // setup
+/// let font = app.load::<Font>("pixel.fnt")?;
+///
+/// // draw
+/// c.fill(c!("white"));
+/// c.text(&*self.font.get(), v![10, 10], "hello!");
+/// ```
+#[derive(Clone)]
+pub struct Font
+{
+    /// distance between two lines, in pixels
+    line_height: usize,
+    /// distance from the top of a line to its baseline, in pixels
+    base: usize,
+    /// images containing the glyphs, by page ID
+    pages: Vec<Image>,
+    /// every glyph in this font
+    glyphs: HashMap<char, Glyph>,
+    /// extra horizontal advance between pairs of characters
+    kerning: HashMap<(char, char), i32>,
+}
+
+/// where a single character is in a [Font]'s pages, and how it's
+/// laid out
+///
+/// [Font]: self::Font
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph
+{
+    /// ID of the page this glyph is in
+    pub page: usize,
+    /// where this glyph is in its page, in pixels
+    pub rect: Rect<usize, usize>,
+    /// offset of this glyph from the pen position when drawn
+    pub offset: Vec2<i32>,
+    /// how far the pen moves right after this glyph
+    pub advance: i32,
+}
+
+/// a font's description, before its pages are opened
+#[derive(Debug, Default)]
+struct FontDesc
+{
+    line_height: usize,
+    base: usize,
+    /// page file names, by ID, relative to the ".fnt" file
+    pages: Vec<String>,
+    glyphs: HashMap<char, Glyph>,
+    kerning: HashMap<(char, char), i32>,
+}
+
+impl Font
+{
+    /// open a ".fnt" file and the pages it refers to, which are
+    /// located relative to it
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FontError>
+    {
+        let path = path.as_ref();
+        let fnt = std::fs::read_to_string(path).map_err(FontError::Io)?;
+
+        Self::from_fnt(&fnt, path.parent().unwrap_or_else(|| Path::new("")))
+    }
+
+    /// parse a font in the BMFont text format, opening its pages
+    /// relative to `dir`
+    pub fn from_fnt(fnt: &str, dir: impl AsRef<Path>) -> Result<Self, FontError>
+    {
+        let desc = parse(fnt)?;
+        let pages = desc.pages
+            .iter()
+            .map(|file| Image::open(dir.as_ref().join(file)).map_err(FontError::Image))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_parts(desc, pages)
+    }
+
+    /// distance between two lines, in pixels
+    #[inline]
+    pub fn line_height(&self) -> usize
+    {
+        self.line_height
+    }
+
+    /// distance from the top of a line to its baseline, in pixels
+    #[inline]
+    pub fn base(&self) -> usize
+    {
+        self.base
+    }
+
+    /// get the glyph of a character, if this font has one
+    #[inline]
+    pub fn glyph(&self, c: char) -> Option<&Glyph>
+    {
+        self.glyphs.get(&c)
+    }
+
+    /// get the image of a page
+    #[inline]
+    pub fn page(&self, id: usize) -> &Image
+    {
+        &self.pages[id]
+    }
+
+    /// extra horizontal advance, usually negative, when `second`
+    /// follows `first`
+    #[inline]
+    pub fn kerning(&self, first: char, second: char) -> i32
+    {
+        self.kerning.get(&(first, second)).copied().unwrap_or(0)
+    }
+
+    /// size `text` takes once drawn with `Bitmap::text`, in pixels:
+    /// its widest line and its number of lines times the line height
+    pub fn measure(&self, text: &str) -> Extent2<usize>
+    {
+        let w = text
+            .split('\n')
+            .map(|line| self.advance(line).max(0) as usize)
+            .max()
+            .unwrap_or(0);
+
+        Extent2::new(w, text.split('\n').count() * self.line_height)
+    }
+
+    /// call `glyph` with each glyph of `text` and the top-left corner
+    /// it's drawn at, relative to the start of the text. characters
+    /// without a glyph are skipped
+    pub(crate) fn layout(&self, text: &str, mut glyph: impl FnMut(&Glyph, Vec2<i32>))
+    {
+        for (i, line) in text.split('\n').enumerate()
+        {
+            let mut pen = Vec2::new(0, (i * self.line_height) as i32);
+            let mut prev = None;
+
+            for c in line.chars()
+            {
+                let g = match self.glyphs.get(&c)
+                {
+                    Some(g) => g,
+                    None => continue,
+                };
+                if let Some(prev) = prev
+                {
+                    pen.x += self.kerning(prev, c);
+                }
+                glyph(g, pen + g.offset);
+
+                pen.x += g.advance;
+                prev = Some(c);
+            }
+        }
+    }
+
+    /// width of a single line of text, kerning included
+    fn advance(&self, line: &str) -> i32
+    {
+        let mut width = 0;
+
+        self.layout(line, |g, pos| width = pos.x - g.offset.x + g.advance);
+        width
+    }
+
+    /// create a font from its description and opened pages
+    fn from_parts(desc: FontDesc, pages: Vec<Image>) -> Result<Self, FontError>
+    {
+        for (c, glyph) in &desc.glyphs
+        {
+            let fits = pages
+                .get(glyph.page)
+                .is_some_and(|page| glyph.rect.x + glyph.rect.w <= page.width() && glyph.rect.y + glyph.rect.h <= page.height());
+
+            if !fits
+            {
+                return Err(FontError::Format(format!("glyph {:?} out of its page's bounds", c)));
+            }
+        }
+
+        Ok(Self
+        {
+            line_height: desc.line_height,
+            base: desc.base,
+            pages,
+            glyphs: desc.glyphs,
+            kerning: desc.kerning,
+        })
+    }
+}
+
+/// reasons why loading a font can fail
+#[derive(Debug)]
+pub enum FontError
+{
+    /// couldn't read the ".fnt" file
+    Io(std::io::Error),
+    /// couldn't open a page's image
+    Image(image::ImageError),
+    /// the file isn't a BMFont text font
+    Format(String),
+}
+
+impl fmt::Display for FontError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            FontError::Io(e) => write!(f, "can't read font: {}", e),
+            FontError::Image(e) => write!(f, "can't open font page: {}", e),
+            FontError::Format(e) => write!(f, "invalid font: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FontError { }
+
+/// parse the lines of a BMFont text file, ignoring the tags it
+/// doesn't need
+fn parse(fnt: &str) -> Result<FontDesc, FontError>
+{
+    let mut desc = FontDesc::default();
+    let mut common = false;
+
+    for line in fnt.lines()
+    {
+        let (tag, pairs) = match split(line)
+        {
+            Some(split) => split,
+            None => continue,
+        };
+
+        match tag
+        {
+            "common" =>
+            {
+                desc.line_height = num(&pairs, tag, "lineHeight")?;
+                desc.base = num(&pairs, tag, "base")?;
+                common = true;
+            }
+            "page" =>
+            {
+                let id: usize = num(&pairs, tag, "id")?;
+                let file = get(&pairs, tag, "file")?;
+
+                if desc.pages.len() <= id
+                {
+                    desc.pages.resize(id + 1, String::new());
+                }
+                desc.pages[id] = file.to_owned();
+            }
+            "char" =>
+            {
+                let c = character(num(&pairs, tag, "id")?)?;
+
+                desc.glyphs.insert(c, Glyph
+                {
+                    page: num(&pairs, tag, "page")?,
+                    rect: Rect::new
+                    (
+                        num(&pairs, tag, "x")?,
+                        num(&pairs, tag, "y")?,
+                        num(&pairs, tag, "width")?,
+                        num(&pairs, tag, "height")?,
+                    ),
+                    offset: Vec2::new(num(&pairs, tag, "xoffset")?, num(&pairs, tag, "yoffset")?),
+                    advance: num(&pairs, tag, "xadvance")?,
+                });
+            }
+            "kerning" =>
+            {
+                let first = character(num(&pairs, tag, "first")?)?;
+                let second = character(num(&pairs, tag, "second")?)?;
+
+                desc.kerning.insert((first, second), num(&pairs, tag, "amount")?);
+            }
+            _ => {}
+        }
+    }
+
+    if !common
+    {
+        return Err(FontError::Format("missing \"common\" line".into()));
+    }
+    if let Some(id) = desc.pages.iter().position(String::is_empty)
+    {
+        return Err(FontError::Format(format!("missing page {}", id)));
+    }
+    Ok(desc)
+}
+
+/// split a line into its tag and `key=value` pairs, unquoting values.
+/// `None` for blank lines
+fn split(line: &str) -> Option<(&str, Vec<(&str, &str)>)>
+{
+    let line = line.trim();
+    let (tag, mut rest) = match line.find(char::is_whitespace)
+    {
+        Some(i) => (&line[..i], line[i..].trim_start()),
+        None if line.is_empty() => return None,
+        None => (line, ""),
+    };
+
+    let mut pairs = Vec::new();
+    while let Some(eq) = rest.find('=')
+    {
+        let key = rest[..eq].trim();
+        let after = &rest[eq + 1..];
+
+        // quoted values can contain spaces
+        let (value, next) = match after.strip_prefix('"')
+        {
+            Some(quoted) => match quoted.find('"')
+            {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => match after.find(char::is_whitespace)
+            {
+                Some(end) => (&after[..end], &after[end..]),
+                None => (after, ""),
+            },
+        };
+        pairs.push((key, value));
+        rest = next.trim_start();
+    }
+    Some((tag, pairs))
+}
+
+/// get the value of `key` in a line tagged `tag`
+fn get<'a>(pairs: &[(&str, &'a str)], tag: &str, key: &str) -> Result<&'a str, FontError>
+{
+    pairs
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|&(_, v)| v)
+        .ok_or_else(|| FontError::Format(format!("\"{}\" line missing \"{}\"", tag, key)))
+}
+
+/// get the value of `key` in a line tagged `tag`, as a number
+fn num<T: FromStr>(pairs: &[(&str, &str)], tag: &str, key: &str) -> Result<T, FontError>
+{
+    get(pairs, tag, key)?
+        .parse()
+        .map_err(|_| FontError::Format(format!("\"{}\" line has an invalid \"{}\"", tag, key)))
+}
+
+/// get the character of a unicode code point
+fn character(id: u32) -> Result<char, FontError>
+{
+    std::char::from_u32(id).ok_or_else(|| FontError::Format(format!("invalid character {}", id)))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::math::Rgba;
+
+    const FNT: &str = r#"info face="Tiny Sans" size=8 bold=0 italic=0 charset="" unicode=1 padding=0,0,0,0 spacing=1,1
+common lineHeight=6 base=5 scaleW=8 scaleH=4 pages=1 packed=0
+page id=0 file="tiny_0.png"
+chars count=3
+char id=32   x=0     y=0     width=0     height=0     xoffset=0     yoffset=5     xadvance=2     page=0  chnl=15
+char id=65   x=0     y=0     width=2     height=3     xoffset=0     yoffset=1     xadvance=3     page=0  chnl=15
+char id=86   x=2     y=0     width=3     height=2     xoffset=-1    yoffset=2     xadvance=3     page=0  chnl=15
+kernings count=1
+kerning first=65  second=86  amount=-1
+"#;
+
+    #[test]
+    fn parse_fnt()
+    {
+        let desc = parse(FNT).unwrap();
+
+        assert_eq!(desc.line_height, 6);
+        assert_eq!(desc.base, 5);
+        assert_eq!(desc.pages, vec!["tiny_0.png".to_owned()]);
+        assert_eq!(desc.glyphs.len(), 3);
+        assert_eq!(desc.glyphs[&'V'], Glyph
+        {
+            page: 0,
+            rect: Rect::new(2, 0, 3, 2),
+            offset: Vec2::new(-1, 2),
+            advance: 3,
+        });
+        assert_eq!(desc.kerning[&('A', 'V')], -1);
+    }
+
+    #[test]
+    fn parse_errors()
+    {
+        assert!(matches!(parse("info face=\"x\""), Err(FontError::Format(_))));
+        assert!(matches!(parse("common lineHeight=6"), Err(FontError::Format(_))));
+        assert!(matches!(parse("common lineHeight=6 base=5\npage id=1 file=\"a.png\""), Err(FontError::Format(_))));
+        assert!(matches!(parse("common lineHeight=six base=5"), Err(FontError::Format(_))));
+    }
+
+    /// the test font, with opaque glyphs on a transparent page
+    fn font() -> Font
+    {
+        let mut page = Image::blank((8, 4));
+        for (x, y) in (0..2).flat_map(|x| (0..3).map(move |y| (x, y))).chain((2..5).flat_map(|x| (0..2).map(move |y| (x, y))))
+        {
+            page[Vec2::new(x, y)] = Rgba::white();
+        }
+
+        Font::from_parts(parse(FNT).unwrap(), vec![page]).unwrap()
+    }
+
+    #[test]
+    fn glyph_out_of_page()
+    {
+        let page = Image::blank((4, 4));
+
+        assert!(matches!(Font::from_parts(parse(FNT).unwrap(), vec![page]), Err(FontError::Format(_))));
+    }
+
+    #[test]
+    fn measure()
+    {
+        let font = font();
+
+        // 3 + 3 - 1 kerning
+        assert_eq!(font.measure("AV"), Extent2::new(5, 6));
+        assert_eq!(font.measure("A A\nV"), Extent2::new(8, 12));
+        // unknown characters are skipped
+        assert_eq!(font.measure("A?"), Extent2::new(3, 6));
+    }
+
+    #[test]
+    fn draw_text()
+    {
+        let font = font();
+        let mut dst = Image::blank((8, 6));
+
+        dst.background(Rgba::black());
+        dst.fill(Rgba::red());
+        dst.text(&font, Vec2::new(1, 0), "AV");
+
+        let red = (0..6)
+            .flat_map(|y| (0..8).map(move |x| Vec2::new(x, y)))
+            .filter(|&pos| dst[pos] == Rgba::red())
+            .collect::<Vec<_>>();
+        // 'A' at (1, 1), 'V' kerned to (1 + 3 - 1 - 1, 2)
+        let mut expected = Vec::new();
+        for y in 0..6
+        {
+            for x in 0..8
+            {
+                if (1..3).contains(&x) && (1..4).contains(&y) || (2..5).contains(&x) && (2..4).contains(&y)
+                {
+                    expected.push(Vec2::new(x, y));
+                }
+            }
+        }
+        assert_eq!(red, expected);
+        assert_eq!(dst[Vec2::new(0, 0)], Rgba::black());
+    }
+}
@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use crate::math::{ Vec2, Rgba, ColourSpace };
+
+/// number of pre-computed colours in a gradient's lookup table
+const LUT_SIZE: usize = 1024;
+
+/// a linear, radial or conic gradient with any number of colour
+/// stops, which can be used to fill shapes. this is a builder:
+/// ```
+/// c.fill(Gradient::linear(v![0.0, 0.0], v![200.0, 0.0])
+///     .stop(0.0, c!("royalblue"))
+///     .stop(0.5, c!("white"))
+///     .stop(1.0, c!("crimson"))
+///     .space(ColourSpace::Oklab)
+///     .spread(Spread::Reflect));
+/// ```
+/// positions are in pixels, in the coordinates of the bitmap
+/// being drawn to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient
+{
+    /// shape of this gradient
+    kind: GradientKind,
+    /// colour stops as (offset, colour), sorted by offset
+    stops: Vec<(f32, Rgba<u8>)>,
+    /// what happens outside of [0, 1]
+    spread: Spread,
+    /// colour space in which stops are interpolated
+    space: ColourSpace,
+
+    /// pre-computed colours from offset 0 to 1, rebuilt every
+    /// time the stops or colour space change
+    lut: Arc<[Rgba<u8>]>,
+}
+
+/// shape of a [Gradient], which maps a position to an offset
+///
+/// [Gradient]: self::Gradient
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GradientKind
+{
+    /// offset goes from 0 at `start` to 1 at `end`, and is
+    /// constant perpendicular to that line
+    Linear { start: Vec2<f32>, end: Vec2<f32> },
+    /// offset goes from 0 at `centre` to 1 at `radius` away
+    /// from it
+    Radial { centre: Vec2<f32>, radius: f32 },
+    /// offset goes from 0 to 1 clockwise around `centre`,
+    /// starting at `angle`, in radians
+    Conic { centre: Vec2<f32>, angle: f32 },
+}
+
+/// what a [Gradient] does for offsets outside of [0, 1]
+///
+/// [Gradient]: self::Gradient
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Spread
+{
+    /// extend the first and last colours
+    Pad,
+    /// start over from the first colour
+    Repeat,
+    /// go back and forth between the first and last colours
+    Reflect,
+}
+
+impl Gradient
+{
+    /// create a new linear gradient from `start` to `end`, in
+    /// pixels
+    pub fn linear(start: Vec2<f32>, end: Vec2<f32>) -> Self
+    {
+        Self::new(GradientKind::Linear { start, end })
+    }
+
+    /// create a new radial gradient around `centre` of the given
+    /// `radius`, in pixels
+    pub fn radial(centre: Vec2<f32>, radius: f32) -> Self
+    {
+        Self::new(GradientKind::Radial { centre, radius })
+    }
+
+    /// create a new conic gradient around `centre`, in pixels, which
+    /// starts at `angle`, in radians
+    pub fn conic(centre: Vec2<f32>, angle: f32) -> Self
+    {
+        Self::new(GradientKind::Conic { centre, angle })
+    }
+
+    /// create a new gradient without stops
+    pub fn new(kind: GradientKind) -> Self
+    {
+        Self
+        {
+            kind,
+            stops: Vec::new(),
+            spread: Spread::Pad,
+            space: ColourSpace::default(),
+            lut: Arc::new([]),
+        }
+    }
+
+    /// add a colour stop at `offset`, usually in [0, 1]
+    pub fn stop(mut self, offset: f32, col: Rgba<u8>) -> Self
+    {
+        // keep sorted, after any stop at the same offset
+        let i = self.stops
+            .iter()
+            .position(|&(o, _)| o > offset)
+            .unwrap_or(self.stops.len());
+
+        self.stops.insert(i, (offset, col));
+        self.bake();
+        self
+    }
+
+    /// set what happens outside of the [0, 1] offset range. the
+    /// default is `Spread::Pad`
+    pub fn spread(mut self, spread: Spread) -> Self
+    {
+        self.spread = spread;
+        self
+    }
+
+    /// set the colour space in which stops are interpolated. the
+    /// default is `ColourSpace::Srgb`
+    pub fn space(mut self, space: ColourSpace) -> Self
+    {
+        self.space = space;
+        self.bake();
+        self
+    }
+
+    /// get the shape of this gradient
+    #[inline]
+    pub fn kind(&self) -> GradientKind
+    {
+        self.kind
+    }
+
+    /// get the colour stops of this gradient as (offset, colour),
+    /// sorted by offset
+    #[inline]
+    pub fn stops(&self) -> &[(f32, Rgba<u8>)]
+    {
+        &self.stops
+    }
+
+    /// get the colour of this gradient at a position, in pixels.
+    /// this samples the centre of the pixel at `pos`
+    #[inline]
+    pub fn sample(&self, pos: Vec2<i32>) -> Rgba<u8>
+    {
+        self.sample_f32(pos.as_::<f32>() + 0.5)
+    }
+
+    /// get the colour of this gradient at a position, in pixels
+    pub fn sample_f32(&self, pos: Vec2<f32>) -> Rgba<u8>
+    {
+        use std::f32::consts::PI;
+
+        let t = match self.kind
+        {
+            GradientKind::Linear { start, end } =>
+            {
+                let d = end - start;
+                let len = d.dot(d);
+
+                if len == 0.0 { 0.0 } else { (pos - start).dot(d) / len }
+            }
+            GradientKind::Radial { centre, radius } =>
+            {
+                if radius == 0.0 { 1.0 } else { pos.distance(centre) / radius }
+            }
+            GradientKind::Conic { centre, angle } =>
+            {
+                let d = pos - centre;
+
+                ((d.y.atan2(d.x) - angle) / (2.0 * PI)).rem_euclid(1.0)
+            }
+        };
+        self.colour_at(t)
+    }
+
+    /// get the colour of this gradient at an offset, after applying
+    /// its `Spread`
+    pub fn colour_at(&self, t: f32) -> Rgba<u8>
+    {
+        // no stops
+        if self.lut.is_empty()
+        {
+            return Rgba::zero();
+        }
+
+        let t = match self.spread
+        {
+            Spread::Pad => t.max(0.0).min(1.0),
+            Spread::Repeat => t.rem_euclid(1.0),
+            Spread::Reflect =>
+            {
+                let t = t.rem_euclid(2.0);
+
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        };
+        // NaN offsets end up at 0
+        let i = (t * (LUT_SIZE - 1) as f32).round() as usize;
+
+        self.lut[i.min(LUT_SIZE - 1)]
+    }
+
+    /// pre-compute the colours of this gradient between offsets 0
+    /// and 1
+    fn bake(&mut self)
+    {
+        let stops = &self.stops;
+        let space = self.space;
+
+        // colour at offset `t`, without spread
+        let colour = |t: f32|
+        {
+            // first stop past `t`
+            match stops.iter().position(|&(o, _)| o > t)
+            {
+                Some(0) => stops[0].1,
+                None => stops[stops.len() - 1].1,
+                Some(i) =>
+                {
+                    let (o0, c0) = stops[i - 1];
+                    let (o1, c1) = stops[i];
+
+                    space.lerp(c0, c1, (t - o0) / (o1 - o0))
+                }
+            }
+        };
+
+        self.lut = match stops.is_empty()
+        {
+            true => Arc::new([]),
+            false => (0..LUT_SIZE)
+                .map(|i| colour(i as f32 / (LUT_SIZE - 1) as f32))
+                .collect(),
+        };
+    }
+}
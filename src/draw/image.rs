@@ -1,4 +1,5 @@
 use image::{ GenericImageView, ImageError };
+use image::error::{ ParameterError, ParameterErrorKind };
 
 use std::path::Path;
 
@@ -17,11 +18,122 @@ impl Image
     /// if the format isn't Rgba<u8>
     pub fn open(path: impl AsRef<Path>) -> Result<Self, ImageError>
     {
-        image::open(path).map(|img|
+        image::open(path).map(Self::from_dynamic)
+    }
+
+    /// decode an image already in memory, such as one embedded in the
+    /// binary with `include_bytes!`. the format is guessed from the
+    /// bytes themselves
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError>
+    {
+        image::load_from_memory(bytes).map(Self::from_dynamic)
+    }
+
+    /// create an image of `size`, with every pixel transparent black
+    pub fn blank(size: impl Into<Extent2<usize>>) -> Self
+    {
+        let size = size.into();
+
+        Bitmap::new((), vec![0; size.w * size.h * 4], size)
+    }
+
+    /// create an image from raw RGBA bytes, row-by-row. fails if there
+    /// aren't exactly `size.w * size.h * 4` bytes
+    pub fn from_raw(rgba: impl Into<Vec<u8>>, size: impl Into<Extent2<usize>>) -> Result<Self, ImageError>
+    {
+        let rgba = rgba.into();
+        let size = size.into();
+
+        if rgba.len() != size.w * size.h * 4
+        {
+            return Err(ImageError::Parameter(ParameterError::from_kind(ParameterErrorKind::DimensionMismatch)));
+        }
+
+        Ok(Bitmap::new((), rgba, size))
+    }
+
+    /// convert a decoded image, copying it if the format
+    /// isn't Rgba<u8>
+    fn from_dynamic(img: image::DynamicImage) -> Self
+    {
+        let size = Extent2::new(img.width(), img.height()).as_();
+
+        Bitmap::new((), img.into_rgba8().into_raw(), size)
+    }
+}
+
+/// embed an image file in the binary and decode it, so sketches
+/// can ship without their resources directory. the path is relative
+/// to the current file, as in `include_bytes!`. panics if the image
+/// can't be decoded
+/// ```
+/// let trees = include_image!("res/trees.jpg");
+/// ```
+#[macro_export]
+macro_rules! include_image
+{
+    ($path:literal) =>
+    {
+        $crate::draw::Image::from_bytes(include_bytes!($path))
+            .expect(concat!("couldn't decode embedded image: ", $path))
+    };
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::math::Rgba;
+
+    #[test]
+    fn from_raw()
+    {
+        let rgba = (0..2 * 3 * 4).map(|i| i as u8).collect::<Vec<_>>();
+        let img = Image::from_raw(rgba, (2, 3)).unwrap();
+
+        assert_eq!(img.size(), Extent2::new(2, 3));
+        assert_eq!(img.pixels()[1], Rgba::new(4, 5, 6, 7));
+        assert_eq!(img.pixels()[5], Rgba::new(20, 21, 22, 23));
+    }
+
+    #[test]
+    fn from_raw_size_mismatch()
+    {
+        for len in [0, 2 * 3 * 4 - 1, 2 * 3 * 4 + 4]
         {
-            let size = Extent2::new(img.width(), img.height()).as_();
+            match Image::from_raw(vec![0; len], (2, 3))
+            {
+                Err(ImageError::Parameter(e)) => assert_eq!(e.kind(), ParameterErrorKind::DimensionMismatch),
+                other => panic!("{} bytes gave {:?}", len, other.map(|img| img.size())),
+            }
+        }
+    }
+
+    #[test]
+    fn from_bytes()
+    {
+        let img = Image::from_bytes(include_bytes!("../../tests/golden/line.png")).unwrap();
+
+        assert_eq!(img.size(), Extent2::new(32, 24));
+        // black background, with a white line down the middle
+        assert_eq!(img.pixels()[0], Rgba::new(0, 0, 0, 0xff));
+        assert_eq!(img.pixels()[16], Rgba::new(0xff, 0xff, 0xff, 0xff));
+    }
+
+    #[test]
+    fn from_bytes_invalid()
+    {
+        assert!(Image::from_bytes(&[]).is_err());
+        assert!(Image::from_bytes(b"not an image").is_err());
+    }
+
+    #[test]
+    fn blank()
+    {
+        let img = Image::blank((3, 2));
 
-            Bitmap::new((), img.into_rgba8().into_raw(), size)
-        })
+        assert_eq!(img.size(), Extent2::new(3, 2));
+        assert!(img.pixels().iter().all(|&px| px == Rgba::new(0, 0, 0, 0)));
     }
-}
\ No newline at end of file
+}
@@ -15,11 +15,11 @@ macro_rules! background
 {
     ($canvas:expr, [$x:expr, $y:expr, $z:expr, $w:expr]) =>
     {
-        $canvas.background(framework::math::Rgba::new($x, $y, $z, $w))
+        $canvas.background($crate::math::Rgba::new($x, $y, $z, $w))
     };
     ($canvas:expr, [$x:expr, $y:expr, $z:expr]) =>
     {
-        $canvas.background(framework::math::Rgba::new($x, $y, $z, 0xff))
+        $canvas.background($crate::math::Rgba::new($x, $y, $z, 0xff))
     };
     ($canvas:expr, $col:expr) =>
     {
@@ -40,11 +40,11 @@ macro_rules! fill
 {
     ($canvas:expr, [$x:expr, $y:expr, $z:expr, $w:expr]) =>
     {
-        $canvas.set_fill(Some(framework::math::Rgba::new($x, $y, $z, $w)))
+        $canvas.set_fill(Some($crate::math::Rgba::new($x, $y, $z, $w)))
     };
     ($canvas:expr, [$x:expr, $y:expr, $z:expr]) =>
     {
-        $canvas.set_fill(Some(framework::math::Rgba::new($x, $y, $z, 0xff)))
+        $canvas.set_fill(Some($crate::math::Rgba::new($x, $y, $z, 0xff)))
     };
     ($canvas:expr, $col:expr) =>
     {
@@ -65,11 +65,11 @@ macro_rules! stroke
 {
     ($canvas:expr, [$x:expr, $y:expr, $z:expr, $w:expr]) =>
     {
-        $canvas.set_stroke(Some(framework::math::Rgba::new($x, $y, $z, $w)))
+        $canvas.set_stroke(Some($crate::math::Rgba::new($x, $y, $z, $w)))
     };
     ($canvas:expr, [$x:expr, $y:expr, $z:expr]) =>
     {
-        $canvas.set_stroke(Some(framework::math::Rgba::new($x, $y, $z, 0xff)))
+        $canvas.set_stroke(Some($crate::math::Rgba::new($x, $y, $z, 0xff)))
     };
     ($canvas:expr, $col:expr) =>
     {
@@ -91,11 +91,11 @@ macro_rules! lines
 {
     ($canvas:expr, [$ax:expr, $ay:expr], [$bx:expr, $by:expr]) =>
     {
-        $canvas.line(framework::math::Vec2::new($ax, $ay), framework::math::Vec2::new($bx, $by))
+        $canvas.line($crate::math::Vec2::new($ax, $ay), $crate::math::Vec2::new($bx, $by))
     };
     ($canvas:expr, $(([$ax:expr, $ay:expr], [$bx:expr, $by:expr])),*) =>
     {
-        $($canvas.line(framework::math::Vec2::new($ax, $ay), framework::math::Vec2::new($bx, $by)));*
+        $($canvas.line($crate::math::Vec2::new($ax, $ay), $crate::math::Vec2::new($bx, $by)));*
     };
 }
 
@@ -107,9 +107,9 @@ macro_rules! triangle
     ($canvas:expr, [$ax:expr, $ay:expr], [$bx:expr, $by:expr], [$cx:expr, $cy:expr]) =>
     {
         $canvas.triangle(
-            framework::math::Vec2::new($ax, $ay),
-            framework::math::Vec2::new($bx, $by),
-            framework::math::Vec2::new($cx, $cy),
+            $crate::math::Vec2::new($ax, $ay),
+            $crate::math::Vec2::new($bx, $by),
+            $crate::math::Vec2::new($cx, $cy),
         )
     };
 }
\ No newline at end of file
@@ -0,0 +1,141 @@
+use crate::math::{ Vec2, Extent2 };
+
+/// a selection of pixels the size of a bitmap, such as the region
+/// returned by `Bitmap::flood_select`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask
+{
+    /// width and height, in pixels, of this mask
+    size: Extent2<usize>,
+    /// whether each pixel is selected, row-by-row
+    bits: Vec<bool>,
+}
+
+impl Mask
+{
+    /// create a new mask with no pixels selected
+    pub fn new(size: Extent2<usize>) -> Self
+    {
+        Self { size, bits: vec![false; size.w * size.h] }
+    }
+
+    /// get this mask's width and height, in pixels
+    #[inline]
+    pub fn size(&self) -> Extent2<usize>
+    {
+        self.size
+    }
+
+    /// is the pixel at `pos` selected? pixels out of bounds
+    /// never are
+    #[inline]
+    pub fn contains(&self, pos: Vec2<i32>) -> bool
+    {
+        self.index(pos).map_or(false, |i| self.bits[i])
+    }
+
+    /// select or deselect the pixel at `pos`. panics if out of
+    /// bounds
+    #[inline]
+    pub fn set(&mut self, pos: Vec2<i32>, selected: bool)
+    {
+        let i = self.index(pos).expect("position out of mask bounds");
+
+        self.bits[i] = selected;
+    }
+
+    /// number of selected pixels
+    pub fn count(&self) -> usize
+    {
+        self.bits.iter().filter(|&&b| b).count()
+    }
+
+    /// are no pixels selected?
+    pub fn is_empty(&self) -> bool
+    {
+        !self.bits.iter().any(|&b| b)
+    }
+
+    /// iterate the positions of the selected pixels, row-by-row
+    pub fn iter(&self) -> impl Iterator<Item = Vec2<i32>> + '_
+    {
+        let w = self.size.w;
+
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b)
+            .map(move |(i, _)| Vec2::new((i % w) as i32, (i / w) as i32))
+    }
+
+    /// get whether each pixel is selected, row-by-row
+    #[inline]
+    pub fn bits(&self) -> &[bool]
+    {
+        &self.bits
+    }
+
+    /// get the selected pixels in row `y`
+    #[inline]
+    pub(crate) fn row(&self, y: usize) -> &[bool]
+    {
+        &self.bits[y * self.size.w..(y + 1) * self.size.w]
+    }
+
+    /// get the selected pixels in row `y`, mutably
+    #[inline]
+    pub(crate) fn row_mut(&mut self, y: usize) -> &mut [bool]
+    {
+        &mut self.bits[y * self.size.w..(y + 1) * self.size.w]
+    }
+
+    /// index of `pos` in `self.bits`, if in bounds
+    #[inline]
+    fn index(&self, pos: Vec2<i32>) -> Option<usize>
+    {
+        let in_bounds = pos.x >= 0
+            && pos.y >= 0
+            && (pos.x as usize) < self.size.w
+            && (pos.y as usize) < self.size.h;
+
+        match in_bounds
+        {
+            true => Some(pos.y as usize * self.size.w + pos.x as usize),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn select()
+    {
+        let mut mask = Mask::new(Extent2::new(3, 2));
+        assert!(mask.is_empty());
+
+        mask.set(Vec2::new(2, 0), true);
+        mask.set(Vec2::new(0, 1), true);
+        mask.set(Vec2::new(1, 1), true);
+        mask.set(Vec2::new(1, 1), false);
+
+        assert_eq!(mask.count(), 2);
+        assert!(mask.contains(Vec2::new(2, 0)));
+        assert!(!mask.contains(Vec2::new(1, 1)));
+        assert_eq!(mask.iter().collect::<Vec<_>>(), vec![Vec2::new(2, 0), Vec2::new(0, 1)]);
+        assert_eq!(mask.bits(), &[false, false, true, true, false, false]);
+        // never selected
+        assert!(!mask.contains(Vec2::new(-1, 0)));
+        assert!(!mask.contains(Vec2::new(3, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of mask bounds")]
+    fn set_out_of_bounds()
+    {
+        Mask::new(Extent2::new(3, 2)).set(Vec2::new(0, 2), true);
+    }
+}
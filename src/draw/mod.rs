@@ -3,12 +3,36 @@
 mod canvas;
 mod bitmap;
 mod window;
+mod builder;
+mod resize;
 mod image;
+mod paint;
+mod gradient;
+mod pattern;
+mod mask;
+mod palette;
+mod sprite;
+mod tilemap;
+mod animated;
+mod font;
 
-pub use self::bitmap::{ Bitmap, /*Chunk,*/ PixelBuf, PixelBufMut, FlatPixelBuf, FlatPixelBufMut };
-pub use self::canvas::{ Canvas, CanvasId };
+pub use self::bitmap::{ Bitmap, Chunk, PixelBuf, PixelBufMut, FlatPixelBuf, FlatPixelBufMut, Connectivity, Dither, Diff, assert_image_eq };
+pub use self::canvas::{ Canvas, CanvasId, Canvases };
+pub use self::builder::CanvasBuilder;
+pub use self::resize::ResizePolicy;
 pub use self::image::Image;
+pub use self::paint::Paint;
+pub use self::gradient::{ Gradient, GradientKind, Spread };
+pub use self::pattern::{ Pattern, Sampling };
+pub use self::mask::Mask;
+pub use self::palette::Palette;
+pub use self::sprite::{ SpriteSheet, SpriteFrame, SpriteTag, Animation, Playback, AtlasError };
+pub use self::tilemap::{ TileMap, Tileset, Layer, TileLayer, ObjectLayer, MapObject, Properties, Property, MapError };
+pub use self::animated::AnimatedImage;
+pub use self::font::{ Font, Glyph, FontError };
 
 pub(crate) use self::window::Window;
+pub(crate) use self::builder::{ CanvasConfig, DrawFn };
+pub(crate) use self::resize::Viewport;
 
 pub use rayon::prelude::{ ParallelIterator, IndexedParallelIterator };
\ No newline at end of file
@@ -0,0 +1,68 @@
+use crate::draw::{ Gradient, Pattern };
+use crate::math::{ Vec2, Rgba };
+
+/// what a [Bitmap] fills shapes with
+///
+/// anything that converts to a `Paint` can be passed to
+/// `Bitmap::fill` or `Bitmap::background`:
+/// ```
+/// c.fill(c!("royalblue"));
+/// c.fill(Gradient::radial(v![50.0, 50.0], 50.0)
+///     .stop(0.0, c!("white"))
+///     .stop(1.0, c!("black")));
+/// c.fill(Pattern::new(img).scale(v![4.0, 4.0]));
+/// ```
+///
+/// [Bitmap]: crate::draw::Bitmap
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint
+{
+    /// a single colour
+    Solid(Rgba<u8>),
+    /// a linear, radial or conic gradient
+    Gradient(Gradient),
+    /// a repeating image
+    Pattern(Pattern),
+}
+
+impl Paint
+{
+    /// get the colour of this paint at a position in pixels
+    #[inline]
+    pub fn sample(&self, pos: Vec2<i32>) -> Rgba<u8>
+    {
+        match self
+        {
+            Paint::Solid(col) => *col,
+            Paint::Gradient(grad) => grad.sample(pos),
+            Paint::Pattern(pat) => pat.sample(pos),
+        }
+    }
+}
+
+impl From<Rgba<u8>> for Paint
+{
+    #[inline]
+    fn from(col: Rgba<u8>) -> Self
+    {
+        Paint::Solid(col)
+    }
+}
+
+impl From<Gradient> for Paint
+{
+    #[inline]
+    fn from(grad: Gradient) -> Self
+    {
+        Paint::Gradient(grad)
+    }
+}
+
+impl From<Pattern> for Paint
+{
+    #[inline]
+    fn from(pat: Pattern) -> Self
+    {
+        Paint::Pattern(pat)
+    }
+}
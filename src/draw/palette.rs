@@ -0,0 +1,381 @@
+use crate::draw::{ Bitmap, PixelBuf };
+use crate::math::{ Rgb, Rgba };
+
+/// maximum number of pixels sampled when building a palette from
+/// a bitmap
+pub(crate) const MAX_SAMPLES: usize = 1 << 16;
+
+/// a limited set of colours that a bitmap can be reduced to with
+/// `Bitmap::quantize`
+/// ```
+/// // classic palette
+/// c.quantize(&Palette::game_boy(), Dither::Bayer(4));
+/// // palette built from an image
+/// let palette = Palette::median_cut(&img, 8);
+/// img.quantize(&palette, Dither::FloydSteinberg);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette
+{
+    /// colours of this palette. only red, green and blue are used
+    colours: Vec<Rgba<u8>>,
+}
+
+impl Palette
+{
+    /// create a new palette from its colours. only their red, green
+    /// and blue are used
+    pub fn new(colours: impl Into<Vec<Rgba<u8>>>) -> Self
+    {
+        Self { colours: colours.into() }
+    }
+
+    /// get the colours of this palette
+    #[inline]
+    pub fn colours(&self) -> &[Rgba<u8>]
+    {
+        &self.colours
+    }
+
+    /// get the number of colours in this palette
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.colours.len()
+    }
+
+    /// does this palette have no colours?
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.colours.is_empty()
+    }
+
+    /// get the index of the colour in this palette closest to `col`,
+    /// or `None` if it's empty
+    pub fn nearest_index(&self, col: Rgb<f32>) -> Option<usize>
+    {
+        let dist = |c: &Rgba<u8>|
+        {
+            let d = Rgb::new(c.r as f32, c.g as f32, c.b as f32) - col;
+
+            d.r * d.r + d.g * d.g + d.b * d.b
+        };
+
+        self.colours
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| dist(a).partial_cmp(&dist(b)).unwrap())
+            .map(|(i, _)| i)
+    }
+
+    /// get the colour in this palette closest to `col`, keeping
+    /// `col`'s alpha. panics if this palette is empty
+    pub fn nearest(&self, col: Rgba<u8>) -> Rgba<u8>
+    {
+        let i = self
+            .nearest_index(Rgb::new(col.r, col.g, col.b).as_())
+            .expect("empty palette");
+
+        Rgba { a: col.a, ..self.colours[i] }
+    }
+
+    /// black and white
+    pub fn one_bit() -> Self
+    {
+        Self::from_rgb(&[[0x00, 0x00, 0x00], [0xff, 0xff, 0xff]])
+    }
+
+    /// the original Game Boy's four shades of green
+    pub fn game_boy() -> Self
+    {
+        Self::from_rgb(&
+        [
+            [0x0f, 0x38, 0x0f], [0x30, 0x62, 0x30], [0x8b, 0xac, 0x0f], [0x9b, 0xbc, 0x0f],
+        ])
+    }
+
+    /// the PICO-8 fantasy console's 16 colours
+    pub fn pico8() -> Self
+    {
+        Self::from_rgb(&
+        [
+            [0x00, 0x00, 0x00], [0x1d, 0x2b, 0x53], [0x7e, 0x25, 0x53], [0x00, 0x87, 0x51],
+            [0xab, 0x52, 0x36], [0x5f, 0x57, 0x4f], [0xc2, 0xc3, 0xc7], [0xff, 0xf1, 0xe8],
+            [0xff, 0x00, 0x4d], [0xff, 0xa3, 0x00], [0xff, 0xec, 0x27], [0x00, 0xe4, 0x36],
+            [0x29, 0xad, 0xff], [0x83, 0x76, 0x9c], [0xff, 0x77, 0xa8], [0xff, 0xcc, 0xaa],
+        ])
+    }
+
+    /// the classic 4 colour CGA palette: black, cyan, magenta and
+    /// white(mode 4, palette 1, high intensity)
+    pub fn cga() -> Self
+    {
+        Self::from_rgb(&
+        [
+            [0x00, 0x00, 0x00], [0x55, 0xff, 0xff], [0xff, 0x55, 0xff], [0xff, 0xff, 0xff],
+        ])
+    }
+
+    /// the full 16 colour CGA palette
+    pub fn cga16() -> Self
+    {
+        Self::from_rgb(&
+        [
+            [0x00, 0x00, 0x00], [0x00, 0x00, 0xaa], [0x00, 0xaa, 0x00], [0x00, 0xaa, 0xaa],
+            [0xaa, 0x00, 0x00], [0xaa, 0x00, 0xaa], [0xaa, 0x55, 0x00], [0xaa, 0xaa, 0xaa],
+            [0x55, 0x55, 0x55], [0x55, 0x55, 0xff], [0x55, 0xff, 0x55], [0x55, 0xff, 0xff],
+            [0xff, 0x55, 0x55], [0xff, 0x55, 0xff], [0xff, 0xff, 0x55], [0xff, 0xff, 0xff],
+        ])
+    }
+
+    /// build a palette of at most `n` colours that best represents the
+    /// colours in `bitmap`, using the median cut algorithm. large bitmaps
+    /// are sampled rather than read in full. the palette is empty if
+    /// `n` is 0
+    pub fn median_cut<I>(bitmap: &Bitmap<I, impl PixelBuf>, n: usize) -> Self
+    {
+        Self::median_cut_samples(samples(bitmap), n)
+    }
+
+    /// same as `Palette::median_cut`, from colours given as [r, g, b]
+    pub(crate) fn median_cut_samples(mut samples: Vec<[u8; 3]>, n: usize) -> Self
+    {
+        if n == 0
+        {
+            return Self::new(Vec::new());
+        }
+        // boxes to split, as ranges in `samples`
+        let mut boxes = vec![(0, samples.len())];
+
+        while boxes.len() < n
+        {
+            // find the box with the widest channel
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .map(|(i, &(lo, hi))| (i, widest_channel(&samples[lo..hi])))
+                .max_by_key(|&(_, (_, range))| range);
+
+            match widest
+            {
+                // can't split any further
+                None | Some((_, (_, 0))) => break,
+                Some((i, (channel, _))) =>
+                {
+                    let (lo, hi) = boxes[i];
+
+                    // split at the median of that channel
+                    samples[lo..hi].sort_unstable_by_key(|col| col[channel]);
+
+                    let mid = lo + (hi - lo) / 2;
+                    boxes[i] = (lo, mid);
+                    boxes.push((mid, hi));
+                }
+            }
+        }
+
+        Self::new(boxes
+            .into_iter()
+            .filter(|(lo, hi)| hi > lo)
+            .map(|(lo, hi)| mean(&samples[lo..hi]))
+            .collect::<Vec<_>>())
+    }
+
+    /// build a palette of at most `n` colours that best represents the
+    /// colours in `bitmap`, using k-means clustering for at most `iters`
+    /// iterations. the clusters start from `Palette::median_cut`, so
+    /// results are deterministic. large bitmaps are sampled rather than
+    /// read in full
+    pub fn k_means<I>(bitmap: &Bitmap<I, impl PixelBuf>, n: usize, iters: usize) -> Self
+    {
+        let samples = samples(bitmap);
+        let mut palette = Self::median_cut(bitmap, n);
+
+        for _ in 0..iters
+        {
+            // (sum of red, green, blue, count) of each cluster
+            let mut sums = vec![[0u64; 4]; palette.len()];
+
+            for col in &samples
+            {
+                let i = match palette.nearest_index(Rgb::new(col[0], col[1], col[2]).as_())
+                {
+                    Some(i) => i,
+                    None => return palette,
+                };
+                for c in 0..3
+                {
+                    sums[i][c] += col[c] as u64;
+                }
+                sums[i][3] += 1;
+            }
+
+            // move each cluster to its mean
+            let next = sums
+                .iter()
+                .zip(palette.colours())
+                .map(|(sum, &old)| match sum[3]
+                {
+                    // empty cluster stays put
+                    0 => old,
+                    n => Rgba::new((sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8, 0xff),
+                })
+                .collect::<Vec<_>>();
+
+            // converged
+            if next == palette.colours
+            {
+                break;
+            }
+            palette.colours = next;
+        }
+        palette
+    }
+
+    /// create a palette from opaque colours given as [r, g, b]
+    fn from_rgb(colours: &[[u8; 3]]) -> Self
+    {
+        Self::new(colours
+            .iter()
+            .map(|&[r, g, b]| Rgba::new(r, g, b, 0xff))
+            .collect::<Vec<_>>())
+    }
+}
+
+/// get the [r, g, b] of up to `MAX_SAMPLES` pixels evenly spread
+/// across `bitmap`
+fn samples<I>(bitmap: &Bitmap<I, impl PixelBuf>) -> Vec<[u8; 3]>
+{
+    let step = (bitmap.area() / MAX_SAMPLES).max(1);
+
+    bitmap
+        .iter_pixels()
+        .step_by(step)
+        .map(|(_, px)| [px.r, px.g, px.b])
+        .collect()
+}
+
+/// get the (index, range) of the channel with the largest range in
+/// `cols`
+fn widest_channel(cols: &[[u8; 3]]) -> (usize, u8)
+{
+    (0..3)
+        .map(|c|
+        {
+            let min = cols.iter().map(|col| col[c]).min().unwrap_or(0);
+            let max = cols.iter().map(|col| col[c]).max().unwrap_or(0);
+
+            (c, max - min)
+        })
+        .max_by_key(|&(_, range)| range)
+        .unwrap()
+}
+
+/// get the mean of non-empty `cols`, as an opaque colour
+fn mean(cols: &[[u8; 3]]) -> Rgba<u8>
+{
+    let mut sum = [0u64; 3];
+    for col in cols
+    {
+        for c in 0..3
+        {
+            sum[c] += col[c] as u64;
+        }
+    }
+    let n = cols.len() as u64;
+
+    Rgba::new((sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8, 0xff)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::draw::Image;
+
+    #[test]
+    fn median_cut_zero_colours()
+    {
+        let palette = Palette::median_cut_samples(vec![[0, 0, 0], [255, 255, 255]], 0);
+
+        assert!(palette.colours().is_empty());
+    }
+
+    #[test]
+    fn median_cut_splits()
+    {
+        let palette = Palette::median_cut_samples(vec![[0, 0, 0], [255, 255, 255]], 2);
+
+        assert_eq!(palette.len(), 2);
+    }
+
+    #[test]
+    fn presets()
+    {
+        let rgb = |palette: Palette| palette
+            .colours()
+            .iter()
+            .map(|c| (c.r, c.g, c.b, c.a))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rgb(Palette::one_bit()), [(0, 0, 0, 0xff), (0xff, 0xff, 0xff, 0xff)]);
+        assert_eq!(rgb(Palette::cga()), [(0, 0, 0, 0xff), (0x55, 0xff, 0xff, 0xff), (0xff, 0x55, 0xff, 0xff), (0xff, 0xff, 0xff, 0xff)]);
+
+        // shades of green, darkest first
+        let game_boy = rgb(Palette::game_boy());
+        assert_eq!(game_boy.len(), 4);
+        assert!(game_boy.iter().all(|&(r, g, b, _)| g > r && g > b));
+        assert!(game_boy.windows(2).all(|w| w[0].1 < w[1].1));
+
+        // indices as in PICO-8 itself
+        let pico8 = rgb(Palette::pico8());
+        assert_eq!(pico8.len(), 16);
+        assert_eq!(pico8[0], (0x00, 0x00, 0x00, 0xff));
+        assert_eq!(pico8[7], (0xff, 0xf1, 0xe8, 0xff));
+        assert_eq!(pico8[8], (0xff, 0x00, 0x4d, 0xff));
+        assert_eq!(pico8[12], (0x29, 0xad, 0xff, 0xff));
+        assert_eq!(pico8[15], (0xff, 0xcc, 0xaa, 0xff));
+
+        // in CGA order, brown included
+        let cga16 = rgb(Palette::cga16());
+        assert_eq!(cga16.len(), 16);
+        assert_eq!(cga16[6], (0xaa, 0x55, 0x00, 0xff));
+        assert_eq!(cga16[8], (0x55, 0x55, 0x55, 0xff));
+        assert_eq!(cga16[15], (0xff, 0xff, 0xff, 0xff));
+
+        for palette in [pico8, cga16]
+        {
+            let mut unique = palette.clone();
+            unique.sort_unstable();
+            unique.dedup();
+
+            assert_eq!(unique.len(), palette.len());
+        }
+    }
+
+    #[test]
+    fn k_means_two_clusters()
+    {
+        // three times as many dark pixels as light ones, so the median
+        // cut it starts from splits the dark cluster rather than
+        // between the two
+        let mut img = Image::blank((8, 8));
+        for (i, px) in img.pixels_mut().iter_mut().enumerate()
+        {
+            let d = (i % 2) as u8 * 2;
+
+            *px = match i < 48
+            {
+                true => Rgba::new(10 + d, 20 + d, 30 + d, 0xff),
+                false => Rgba::new(200 + d, 210 + d, 220 + d, 0xff),
+            };
+        }
+
+        let mut colours = Palette::k_means(&img, 2, 16).colours().to_vec();
+        colours.sort_by_key(|c| c.r);
+
+        assert_eq!(colours, [Rgba::new(11, 21, 31, 0xff), Rgba::new(201, 211, 221, 0xff)]);
+    }
+}
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+use std::fmt;
+
+use crate::draw::Image;
+use crate::math::{ Vec2, Vec3, Mat3, Rgba };
+
+/// an `Image` repeated infinitely in both directions, which can be used
+/// to fill shapes. this is a builder:
+/// ```
+/// c.fill(Pattern::new(img)
+///     .offset(v![10.0, 10.0])
+///     .scale(v![2.0, 2.0])
+///     .sampling(Sampling::Bilinear));
+/// ```
+/// the image's top-left corner is placed at `offset`, in the pixel
+/// coordinates of the bitmap being drawn to, scaled by `scale` and then
+/// transformed by the optional `transform`
+#[derive(Clone)]
+pub struct Pattern
+{
+    /// tiled image
+    image: Arc<Image>,
+    /// position of the image's top-left corner
+    offset: Vec2<f32>,
+    /// size of one image pixel, in bitmap pixels
+    scale: Vec2<f32>,
+    /// inverse of the transform applied after offset and scale, mapping
+    /// from bitmap to pattern space
+    inverse: Option<Mat3<f32>>,
+    /// how the image is sampled between pixels
+    sampling: Sampling,
+}
+
+/// how a [Pattern] samples its image
+///
+/// [Pattern]: self::Pattern
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sampling
+{
+    /// nearest pixel, which keeps pixel art crisp
+    Nearest,
+    /// weighted average of the 4 nearest pixels, which smooths
+    /// scaled and rotated images
+    Bilinear,
+}
+
+impl Pattern
+{
+    /// create a new pattern from an image, or a shared image,
+    /// repeated from the top-left corner at its original size
+    pub fn new(image: impl Into<Arc<Image>>) -> Self
+    {
+        Self
+        {
+            image: image.into(),
+            offset: Vec2::zero(),
+            scale: Vec2::one(),
+            inverse: None,
+            sampling: Sampling::Nearest,
+        }
+    }
+
+    /// set the position of the image's top-left corner, in pixels
+    pub fn offset(mut self, offset: Vec2<f32>) -> Self
+    {
+        self.offset = offset;
+        self
+    }
+
+    /// set the size of one image pixel, in bitmap pixels. the default
+    /// is `[1.0, 1.0]`
+    pub fn scale(mut self, scale: Vec2<f32>) -> Self
+    {
+        self.scale = scale;
+        self
+    }
+
+    /// set the 2D transform, as a matrix over homogeneous coordinates,
+    /// applied to the pattern after its offset and scale. a matrix that
+    /// isn't invertible, ie. one that scales by 0, removes the transform
+    /// ```
+    /// // rotate the pattern by 45° around the origin
+    /// Pattern::new(img).transform(Mat3::rotation_z(std::f32::consts::FRAC_PI_4))
+    /// ```
+    pub fn transform(mut self, transform: Mat3<f32>) -> Self
+    {
+        self.inverse = invert(transform);
+        self
+    }
+
+    /// set how the image is sampled between pixels. the default is
+    /// `Sampling::Nearest`
+    pub fn sampling(mut self, sampling: Sampling) -> Self
+    {
+        self.sampling = sampling;
+        self
+    }
+
+    /// get the tiled image
+    #[inline]
+    pub fn image(&self) -> &Image
+    {
+        &self.image
+    }
+
+    /// get the colour of this pattern at a position, in pixels.
+    /// this samples the centre of the pixel at `pos`
+    pub fn sample(&self, pos: Vec2<i32>) -> Rgba<u8>
+    {
+        // empty image
+        if self.image.area() == 0
+        {
+            return Rgba::zero();
+        }
+
+        // bitmap -> pattern space
+        let pos = pos.as_::<f32>() + 0.5;
+        let pos = match self.inverse
+        {
+            Some(inv) => (inv * Vec3::new(pos.x, pos.y, 1.0)).xy(),
+            None => pos,
+        };
+        // pattern -> image space
+        let pos = (pos - self.offset) / self.scale;
+
+        match self.sampling
+        {
+            Sampling::Nearest => self.texel(pos.map(|n| n.floor() as i64)),
+            Sampling::Bilinear =>
+            {
+                // relative to the centres of the 4 nearest pixels
+                let pos = pos - 0.5;
+                let min = pos.map(|n| n.floor());
+                let t = pos - min;
+                let min = min.map(|n| n as i64);
+
+                let tl = self.texel(min).as_::<f32>();
+                let tr = self.texel(min + Vec2::new(1, 0)).as_::<f32>();
+                let bl = self.texel(min + Vec2::new(0, 1)).as_::<f32>();
+                let br = self.texel(min + Vec2::new(1, 1)).as_::<f32>();
+
+                let top = tl + (tr - tl) * t.x;
+                let btm = bl + (br - bl) * t.x;
+
+                (top + (btm - top) * t.y).map(|n| n.round() as u8)
+            }
+        }
+    }
+
+    /// get the image's pixel at `pos`, wrapping around its edges
+    #[inline]
+    fn texel(&self, pos: Vec2<i64>) -> Rgba<u8>
+    {
+        let x = pos.x.rem_euclid(self.image.width() as i64) as usize;
+        let y = pos.y.rem_euclid(self.image.height() as i64) as usize;
+
+        self.image.pixels()[y * self.image.width() + x]
+    }
+}
+
+impl fmt::Debug for Pattern
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("Pattern")
+            .field("image", &self.image.size())
+            .field("offset", &self.offset)
+            .field("scale", &self.scale)
+            .field("inverse", &self.inverse)
+            .field("sampling", &self.sampling)
+            .finish()
+    }
+}
+
+impl PartialEq for Pattern
+{
+    /// patterns are equal if they share the same image, not just
+    /// identical pixels
+    fn eq(&self, other: &Self) -> bool
+    {
+        Arc::ptr_eq(&self.image, &other.image)
+            && self.offset == other.offset
+            && self.scale == other.scale
+            && self.inverse == other.inverse
+            && self.sampling == other.sampling
+    }
+}
+
+/// invert a 3x3 matrix using its adjugate, or `None` if it's singular
+fn invert(m: Mat3<f32>) -> Option<Mat3<f32>>
+{
+    let [[a, b, c], [d, e, f], [g, h, i]] = m.into_row_arrays();
+
+    // cofactors
+    let (ca, cb, cc) = (e * i - f * h, f * g - d * i, d * h - e * g);
+    let det = a * ca + b * cb + c * cc;
+
+    if det.is_nan() || det.abs() < f32::EPSILON
+    {
+        return None;
+    }
+    let inv = 1.0 / det;
+
+    Some(Mat3::from_row_arrays(
+    [
+        [ca * inv, (c * h - b * i) * inv, (b * f - c * e) * inv],
+        [cb * inv, (a * i - c * g) * inv, (c * d - a * f) * inv],
+        [cc * inv, (b * g - a * h) * inv, (a * e - b * d) * inv],
+    ]))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const RED: Rgba<u8> = Rgba::new(0xff, 0, 0, 0xff);
+    const GREEN: Rgba<u8> = Rgba::new(0, 0xff, 0, 0xff);
+    const BLUE: Rgba<u8> = Rgba::new(0, 0, 0xff, 0xff);
+    const WHITE: Rgba<u8> = Rgba::new(0xff, 0xff, 0xff, 0xff);
+
+    /// 2x2 image of red, green, blue and white
+    fn image() -> Image
+    {
+        let px = [RED, GREEN, BLUE, WHITE];
+
+        Image::from_raw(px.iter().flat_map(|c| vec![c.r, c.g, c.b, c.a]).collect::<Vec<_>>(), (2, 2)).unwrap()
+    }
+
+    #[test]
+    fn nearest()
+    {
+        let pattern = Pattern::new(image());
+
+        assert_eq!(pattern.sample(Vec2::new(0, 0)), RED);
+        assert_eq!(pattern.sample(Vec2::new(1, 0)), GREEN);
+        assert_eq!(pattern.sample(Vec2::new(0, 1)), BLUE);
+        assert_eq!(pattern.sample(Vec2::new(1, 1)), WHITE);
+
+        let scaled = Pattern::new(image()).scale(Vec2::new(2.0, 3.0)).offset(Vec2::new(1.0, 0.0));
+        assert_eq!(scaled.sample(Vec2::new(0, 0)), GREEN);
+        assert_eq!(scaled.sample(Vec2::new(2, 2)), RED);
+        assert_eq!(scaled.sample(Vec2::new(3, 3)), WHITE);
+    }
+
+    #[test]
+    fn repeat()
+    {
+        let pattern = Pattern::new(image());
+
+        for &(x, y) in &[(0, 0), (1, 1), (3, -2), (-5, 7)]
+        {
+            let pos = Vec2::new(x, y);
+
+            assert_eq!(pattern.sample(pos), pattern.sample(pos + Vec2::new(2, 0)), "{:?}", pos);
+            assert_eq!(pattern.sample(pos), pattern.sample(pos - Vec2::new(0, 4)), "{:?}", pos);
+        }
+        assert_eq!(pattern.sample(Vec2::new(-1, -1)), WHITE);
+    }
+
+    #[test]
+    fn bilinear()
+    {
+        let pattern = Pattern::new(image()).sampling(Sampling::Bilinear);
+
+        // pixel centres are exact
+        assert_eq!(pattern.sample(Vec2::new(0, 0)), RED);
+        assert_eq!(pattern.sample(Vec2::new(1, 1)), WHITE);
+
+        // between red and green, wrapping around
+        let scaled = Pattern::new(image()).sampling(Sampling::Bilinear).scale(Vec2::new(2.0, 1.0));
+        assert_eq!(scaled.sample(Vec2::new(1, 0)), Rgba::new(0xbf, 0x40, 0, 0xff));
+        assert_eq!(scaled.sample(Vec2::new(2, 0)), Rgba::new(0x40, 0xbf, 0, 0xff));
+        assert_eq!(scaled.sample(Vec2::new(0, 0)), Rgba::new(0xbf, 0x40, 0, 0xff));
+    }
+
+    #[test]
+    fn transform()
+    {
+        let pattern = Pattern::new(image());
+        let moved = Pattern::new(image()).transform(Mat3::translation_2d(Vec2::new(1.0, 0.0)));
+
+        assert_eq!(moved.sample(Vec2::new(1, 0)), pattern.sample(Vec2::new(0, 0)));
+        assert_eq!(moved.sample(Vec2::new(0, 1)), pattern.sample(Vec2::new(1, 1)));
+    }
+
+    #[test]
+    fn singular_transform()
+    {
+        let pattern = Pattern::new(image());
+
+        for &m in &[Mat3::zero(), Mat3::scaling_3d(Vec3::new(0.0, 1.0, 1.0)), Mat3::identity() * f32::NAN]
+        {
+            let singular = Pattern::new(image()).transform(m);
+
+            assert!(invert(m).is_none());
+            for &(x, y) in &[(0, 0), (1, 0), (0, 1), (7, -3)]
+            {
+                assert_eq!(singular.sample(Vec2::new(x, y)), pattern.sample(Vec2::new(x, y)));
+            }
+        }
+    }
+}
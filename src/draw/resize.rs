@@ -0,0 +1,279 @@
+use rayon::prelude::*;
+
+use crate::math::{ Extent2, Vec2 };
+
+/// how a `Canvas` fills its window once the window is resized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizePolicy
+{
+    /// scale the canvas by the largest whole number that fits,
+    /// centred with black bars around it. keeps pixels square
+    /// and sharp
+    Integer,
+    /// scale the canvas as much as fits while keeping its aspect
+    /// ratio, centred with black bars on two sides
+    Letterbox,
+    /// scale the canvas to fill the whole window, ignoring its
+    /// aspect ratio
+    Stretch,
+    /// resize the canvas to fit the window, at the canvas' pixel
+    /// scale. `Sketch::resized` says what the new size is
+    FollowWindow,
+}
+
+/// where a canvas is shown in its window, in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Viewport
+{
+    /// top-left corner, which is negative if the window is too
+    /// small for the canvas
+    pub pos: Vec2<i32>,
+    /// size of the scaled canvas
+    pub size: Extent2<usize>,
+}
+
+impl ResizePolicy
+{
+    /// is the canvas drawn to its own buffer, then scaled to its
+    /// window's size on the CPU? the other policies let `pixels`
+    /// scale the canvas
+    pub(crate) fn is_resampled(self) -> bool
+    {
+        matches!(self, ResizePolicy::Letterbox | ResizePolicy::Stretch)
+    }
+
+    /// canvas size in a window of logical size `window`, given the
+    /// canvas' current size and pixel scale. a minimised window
+    /// leaves the canvas as it is
+    pub(crate) fn canvas_size(self, canvas: Extent2<usize>, window: Extent2<usize>, scale: usize) -> Extent2<usize>
+    {
+        match self
+        {
+            _ if window.w == 0 || window.h == 0 => canvas,
+            ResizePolicy::FollowWindow => (window / scale.max(1)).map(|n| n.max(1)),
+            _ => canvas,
+        }
+    }
+
+    /// where a canvas of size `canvas` is shown in a window of
+    /// physical size `window`
+    pub(crate) fn viewport(self, canvas: Extent2<usize>, window: Extent2<usize>) -> Viewport
+    {
+        let ratio = window.as_::<f32>() / canvas.map(|n| n.max(1)).as_::<f32>();
+        let size = match self
+        {
+            ResizePolicy::Integer | ResizePolicy::FollowWindow =>
+            {
+                canvas * (ratio.w.min(ratio.h).floor() as usize).max(1)
+            }
+            ResizePolicy::Letterbox =>
+            {
+                (canvas.as_::<f32>() * ratio.w.min(ratio.h)).round().as_()
+            }
+            ResizePolicy::Stretch => window,
+        };
+        let pos = (window.as_::<i32>() - size.as_::<i32>()) / 2;
+
+        Viewport { pos: Vec2::new(pos.w, pos.h), size }
+    }
+}
+
+impl Default for ResizePolicy
+{
+    fn default() -> Self
+    {
+        ResizePolicy::Integer
+    }
+}
+
+impl Viewport
+{
+    /// canvas pixel under a physical window position, clamped to
+    /// the canvas' bounds
+    pub(crate) fn to_canvas(&self, pos: Vec2<f32>, canvas: Extent2<usize>) -> Vec2<i32>
+    {
+        let scale = canvas.as_::<f32>() / self.size.map(|n| n.max(1)).as_::<f32>();
+        let pos = (pos - self.pos.as_::<f32>()) * Vec2::new(scale.w, scale.h);
+        let max = Vec2::new(canvas.w, canvas.h).as_::<i32>() - 1;
+
+        Vec2::new(pos.x.floor() as i32, pos.y.floor() as i32)
+            .map2(max, |p, max| p.min(max).max(0))
+    }
+
+    /// copy the canvas' `src` pixels into the window's `dst`
+    /// pixels, scaling them with nearest-neighbour sampling and
+    /// clearing the rest of the window to black
+    pub(crate) fn resample(&self, src: &[u8], canvas: Extent2<usize>, dst: &mut [u8], window: Extent2<usize>)
+    {
+        if canvas.w == 0 || canvas.h == 0 || window.w == 0
+        {
+            return;
+        }
+        // source column of each window column, if inside the canvas
+        let cols = (0..window.w as i32)
+            .map(|x| source(x - self.pos.x, self.size.w, canvas.w))
+            .collect::<Vec<_>>();
+
+        dst
+            .par_chunks_exact_mut(window.w * 4)
+            .enumerate()
+            .for_each(|(y, row)|
+            {
+                let src_y = source(y as i32 - self.pos.y, self.size.h, canvas.h);
+
+                for (px, &src_x) in row.chunks_exact_mut(4).zip(&cols)
+                {
+                    match src_x.zip(src_y)
+                    {
+                        Some((x, y)) =>
+                        {
+                            let i = (y * canvas.w + x) * 4;
+                            px.copy_from_slice(&src[i..i + 4]);
+                        }
+                        None => px.copy_from_slice(&[0, 0, 0, 0xff]),
+                    }
+                }
+            });
+    }
+}
+
+/// canvas coordinate at `offset` into a viewport of `len` pixels,
+/// for a canvas `canvas` pixels wide, if it's inside the viewport
+fn source(offset: i32, len: usize, canvas: usize) -> Option<usize>
+{
+    match offset >= 0 && (offset as usize) < len
+    {
+        true => Some(offset as usize * canvas / len),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const CANVAS: Extent2<usize> = Extent2::new(160, 120);
+
+    fn viewport(policy: ResizePolicy, window: (usize, usize)) -> Viewport
+    {
+        policy.viewport(CANVAS, window.into())
+    }
+
+    #[test]
+    fn viewport_non_integer_ratio()
+    {
+        // 3.125x wide, 3.33x tall
+        assert_eq!(viewport(ResizePolicy::Integer, (500, 400)), Viewport { pos: Vec2::new(10, 20), size: Extent2::new(480, 360) });
+        assert_eq!(viewport(ResizePolicy::FollowWindow, (500, 400)), Viewport { pos: Vec2::new(10, 20), size: Extent2::new(480, 360) });
+        assert_eq!(viewport(ResizePolicy::Letterbox, (500, 400)), Viewport { pos: Vec2::new(0, 12), size: Extent2::new(500, 375) });
+        assert_eq!(viewport(ResizePolicy::Stretch, (500, 400)), Viewport { pos: Vec2::new(0, 0), size: Extent2::new(500, 400) });
+    }
+
+    #[test]
+    fn viewport_small_window()
+    {
+        // never scaled down, centred and cropped instead
+        assert_eq!(viewport(ResizePolicy::Integer, (100, 100)), Viewport { pos: Vec2::new(-30, -10), size: CANVAS });
+        assert_eq!(viewport(ResizePolicy::Letterbox, (100, 100)), Viewport { pos: Vec2::new(0, 12), size: Extent2::new(100, 75) });
+        assert_eq!(viewport(ResizePolicy::Stretch, (100, 100)), Viewport { pos: Vec2::new(0, 0), size: Extent2::new(100, 100) });
+    }
+
+    #[test]
+    fn viewport_zero_window()
+    {
+        // minimised
+        assert_eq!(viewport(ResizePolicy::Integer, (0, 0)), Viewport { pos: Vec2::new(-80, -60), size: CANVAS });
+        assert_eq!(viewport(ResizePolicy::Letterbox, (0, 0)), Viewport { pos: Vec2::new(0, 0), size: Extent2::new(0, 0) });
+        assert_eq!(viewport(ResizePolicy::Stretch, (0, 0)), Viewport { pos: Vec2::new(0, 0), size: Extent2::new(0, 0) });
+        assert_eq!(viewport(ResizePolicy::Letterbox, (300, 0)), Viewport { pos: Vec2::new(150, 0), size: Extent2::new(0, 0) });
+    }
+
+    #[test]
+    fn canvas_size()
+    {
+        let window = Extent2::new(501, 377);
+
+        assert_eq!(ResizePolicy::FollowWindow.canvas_size(CANVAS, window, 4), Extent2::new(125, 94));
+        assert_eq!(ResizePolicy::FollowWindow.canvas_size(CANVAS, window, 0), window);
+        assert_eq!(ResizePolicy::Integer.canvas_size(CANVAS, window, 4), CANVAS);
+        assert_eq!(ResizePolicy::Letterbox.canvas_size(CANVAS, window, 4), CANVAS);
+        // never empty
+        assert_eq!(ResizePolicy::FollowWindow.canvas_size(CANVAS, Extent2::new(1, 3), 2), Extent2::new(1, 1));
+    }
+
+    #[test]
+    fn canvas_size_zero_window()
+    {
+        // minimised, so the canvas keeps its size and pixels
+        for &window in &[Extent2::new(0, 0), Extent2::new(3, 0), Extent2::new(0, 3)]
+        {
+            assert_eq!(ResizePolicy::FollowWindow.canvas_size(CANVAS, window, 2), CANVAS);
+            assert_eq!(ResizePolicy::Letterbox.canvas_size(CANVAS, window, 2), CANVAS);
+        }
+    }
+
+    #[test]
+    fn to_canvas_non_integer_ratio()
+    {
+        let letterbox = viewport(ResizePolicy::Letterbox, (500, 400));
+
+        assert_eq!(letterbox.to_canvas(Vec2::new(250.0, 199.5), CANVAS), Vec2::new(80, 60));
+        assert_eq!(letterbox.to_canvas(Vec2::new(499.9, 386.9), CANVAS), Vec2::new(159, 119));
+        // on the bars
+        assert_eq!(letterbox.to_canvas(Vec2::new(-5.0, 5.0), CANVAS), Vec2::new(0, 0));
+        assert_eq!(letterbox.to_canvas(Vec2::new(600.0, 399.0), CANVAS), Vec2::new(159, 119));
+
+        let stretch = viewport(ResizePolicy::Stretch, (500, 400));
+        assert_eq!(stretch.to_canvas(Vec2::new(3.2, 3.4), CANVAS), Vec2::new(1, 1));
+    }
+
+    #[test]
+    fn to_canvas_small_window()
+    {
+        let integer = viewport(ResizePolicy::Integer, (100, 100));
+
+        assert_eq!(integer.to_canvas(Vec2::new(0.0, 0.0), CANVAS), Vec2::new(30, 10));
+        assert_eq!(integer.to_canvas(Vec2::new(99.5, 99.5), CANVAS), Vec2::new(129, 109));
+    }
+
+    #[test]
+    fn to_canvas_zero_window()
+    {
+        for &policy in &[ResizePolicy::Integer, ResizePolicy::Letterbox, ResizePolicy::Stretch, ResizePolicy::FollowWindow]
+        {
+            let pos = viewport(policy, (0, 0)).to_canvas(Vec2::new(0.0, 0.0), CANVAS);
+
+            assert!(pos.x >= 0 && pos.x < 160 && pos.y >= 0 && pos.y < 120, "{:?}: {:?}", policy, pos);
+        }
+    }
+
+    #[test]
+    fn resample_non_integer_ratio()
+    {
+        // red and blue, stretched 2.5x wide and 3x tall
+        let src = [0xff, 0, 0, 0xff, 0, 0, 0xff, 0xff];
+        let canvas = Extent2::new(2, 1);
+        let window = Extent2::new(5, 3);
+        let mut dst = vec![0; 5 * 3 * 4];
+
+        ResizePolicy::Stretch.viewport(canvas, window).resample(&src, canvas, &mut dst, window);
+
+        for row in dst.chunks_exact(5 * 4)
+        {
+            assert_eq!(row, &[&src[..4], &src[..4], &src[..4], &src[4..], &src[4..]].concat()[..]);
+        }
+    }
+
+    #[test]
+    fn resample_zero_window()
+    {
+        let src = vec![0xff; 160 * 120 * 4];
+        let mut dst = Vec::new();
+
+        for &window in &[Extent2::new(0, 0), Extent2::new(10, 0), Extent2::new(0, 10)]
+        {
+            ResizePolicy::Letterbox.viewport(CANVAS, window).resample(&src, CANVAS, &mut dst, window);
+        }
+    }
+}
@@ -0,0 +1,633 @@
+use serde_json::Value;
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::path::Path;
+use std::fmt;
+
+use crate::draw::{ Bitmap, Chunk, Image, PixelBufMut };
+use crate::math::{ Vec2, Extent2, Rect };
+
+/// an image made up of many smaller frames, such as the steps of a
+/// walk cycle or the tiles of a level
+/// ```
+/// // 16x16 frames, row-by-row
+/// let sheet = SpriteSheet::grid(Image::open("res/hero.png")?, (16, 16));
+/// c.image(&sheet.frame(3), v![10, 10]);
+/// ```
+#[derive(Clone)]
+pub struct SpriteSheet
+{
+    /// image containing every frame
+    image: Image,
+    /// where each frame is in `self.image`
+    frames: Vec<SpriteFrame>,
+    /// named ranges of frames, as exported by Aseprite
+    tags: Vec<SpriteTag>,
+}
+
+/// a single frame in a [SpriteSheet]
+///
+/// [SpriteSheet]: self::SpriteSheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteFrame
+{
+    /// name of this frame in the atlas it was loaded from, if any
+    pub name: String,
+    /// where this frame is in its sheet's image, in pixels
+    pub rect: Rect<usize, usize>,
+    /// offset of this frame when drawn, for atlases that trim the
+    /// transparent edges of frames
+    pub offset: Vec2<i32>,
+    /// how long this frame lasts when animated, if the atlas
+    /// specifies it
+    pub duration: Option<Duration>,
+}
+
+/// a named range of frames in a [SpriteSheet], and how they
+/// should be played back
+///
+/// [SpriteSheet]: self::SpriteSheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteTag
+{
+    /// name of this tag, ie. "walk" or "idle"
+    pub name: String,
+    /// index of the first frame
+    pub from: usize,
+    /// index of the last frame, inclusive
+    pub to: usize,
+    /// how the frames are played back
+    pub playback: Playback,
+    /// are the frames played back last to first?
+    pub reverse: bool,
+}
+
+impl SpriteSheet
+{
+    /// create a sprite sheet from an image and the rects of
+    /// its frames. panics if any rect doesn't fit in `image`
+    pub fn new(image: Image, rects: impl IntoIterator<Item = Rect<usize, usize>>) -> Self
+    {
+        let frames = rects
+            .into_iter()
+            .map(|rect| SpriteFrame { name: String::new(), rect, offset: Vec2::zero(), duration: None })
+            .collect::<Vec<_>>();
+
+        for frame in &frames
+        {
+            assert!
+            (
+                frame.rect.x + frame.rect.w <= image.width() && frame.rect.y + frame.rect.h <= image.height(),
+                "sprite frame out of image bounds"
+            );
+        }
+
+        Self { image, frames, tags: Vec::new() }
+    }
+
+    /// create a sprite sheet by dividing an image in frames of
+    /// size `size`, row-by-row. the remaining pixels, if any, are
+    /// discarded(like in `Bitmap::iter_pixel_chunks`). the sheet
+    /// has no frames if `size` is zero in either dimension
+    pub fn grid(image: Image, size: impl Into<Extent2<usize>>) -> Self
+    {
+        let size = size.into();
+
+        if size.w == 0 || size.h == 0
+        {
+            return Self::new(image, Vec::new());
+        }
+
+        let cols = image.width() / size.w;
+        let rows = image.height() / size.h;
+
+        let rects = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (x, y)))
+            .map(|(x, y)| Rect::new(x * size.w, y * size.h, size.w, size.h))
+            .collect::<Vec<_>>();
+
+        Self::new(image, rects)
+    }
+
+    /// open an atlas's JSON and the image it refers to, which is
+    /// located relative to the JSON file. see `SpriteSheet::from_json`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AtlasError>
+    {
+        let path = path.as_ref();
+
+        let json = std::fs::read_to_string(path).map_err(AtlasError::Io)?;
+        let meta: Value = serde_json::from_str(&json).map_err(AtlasError::Json)?;
+
+        let image = meta["meta"]["image"]
+            .as_str()
+            .ok_or_else(|| AtlasError::Format("missing \"meta.image\"".into()))?;
+        let image = Image::open(path.with_file_name(image)).map_err(AtlasError::Image)?;
+
+        Self::from_json(image, &json)
+    }
+
+    /// create a sprite sheet from an image and the JSON describing its
+    /// frames, as exported by Aseprite or TexturePacker(either the "hash"
+    /// or "array" layout). Aseprite's frame durations and tags are kept
+    /// too
+    pub fn from_json(image: Image, json: &str) -> Result<Self, AtlasError>
+    {
+        let root: Value = serde_json::from_str(json).map_err(AtlasError::Json)?;
+
+        let frames = match &root["frames"]
+        {
+            Value::Array(frames) => frames
+                .iter()
+                .map(|f| parse_frame(f["filename"].as_str().unwrap_or_default(), f))
+                .collect::<Result<Vec<_>, _>>()?,
+            Value::Object(frames) => frames
+                .iter()
+                .map(|(name, f)| parse_frame(name, f))
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(AtlasError::Format("missing \"frames\"".into())),
+        };
+
+        let tags = match &root["meta"]["frameTags"]
+        {
+            Value::Array(tags) => tags
+                .iter()
+                .map(|t| parse_tag(t, frames.len()))
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => Vec::new(),
+        };
+
+        for frame in &frames
+        {
+            if frame.rect.x + frame.rect.w > image.width() || frame.rect.y + frame.rect.h > image.height()
+            {
+                return Err(AtlasError::Format(format!("frame \"{}\" out of image bounds", frame.name)));
+            }
+        }
+
+        Ok(Self { image, frames, tags })
+    }
+
+    /// get the image containing every frame
+    #[inline]
+    pub fn image(&self) -> &Image
+    {
+        &self.image
+    }
+
+    /// get every frame in this sheet
+    #[inline]
+    pub fn frames(&self) -> &[SpriteFrame]
+    {
+        &self.frames
+    }
+
+    /// get every tag in this sheet
+    #[inline]
+    pub fn tags(&self) -> &[SpriteTag]
+    {
+        &self.tags
+    }
+
+    /// get the tag named `name`, if any
+    pub fn tag(&self, name: &str) -> Option<&SpriteTag>
+    {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    /// get the index of the frame named `name`, if any
+    pub fn index_of(&self, name: &str) -> Option<usize>
+    {
+        self.frames.iter().position(|frame| frame.name == name)
+    }
+
+    /// number of frames in this sheet
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.frames.len()
+    }
+
+    /// does this sheet have no frames?
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.frames.is_empty()
+    }
+
+    /// get the `i`th frame as a sub-bitmap of this sheet's image,
+    /// without copying any pixels. panics if out of bounds
+    pub fn frame(&self, i: usize) -> Bitmap<Vec2<i32>, Chunk<'_>>
+    {
+        let rect = self.frames[i].rect;
+
+        self.image.view(Vec2::new(rect.x, rect.y), Extent2::new(rect.w, rect.h))
+    }
+
+    /// draw the `i`th frame onto a bitmap, with its top-left corner
+    /// at `pos`(before any trimming offset)
+    pub fn draw<I>(&self, i: usize, dst: &mut Bitmap<I, impl PixelBufMut>, pos: Vec2<i32>)
+    {
+        dst.image(&self.frame(i), pos + self.frames[i].offset);
+    }
+}
+
+/// how an [Animation] plays its frames back
+///
+/// [Animation]: self::Animation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Playback
+{
+    /// first to last, then starting over
+    Loop,
+    /// first to last, then last to first, and so on
+    PingPong,
+    /// first to last, then stopping on the last frame
+    Once,
+}
+
+/// a sequence of frames in a [SpriteSheet], advanced over time
+/// ```
+/// // in setup
+/// let walk = Animation::new(sheet, 0..4, Duration::from_millis(100));
+///
+/// // in update
+/// self.walk.update(app.time().delta());
+///
+/// // in draw
+/// self.walk.draw(c, v![20, 20]);
+/// ```
+///
+/// [SpriteSheet]: self::SpriteSheet
+#[derive(Clone)]
+pub struct Animation
+{
+    /// sheet containing the frames
+    sheet: Arc<SpriteSheet>,
+    /// indices of the frames in `self.sheet`, in order
+    frames: Vec<usize>,
+    /// how long each frame in `self.frames` lasts
+    durations: Vec<Duration>,
+    /// how frames are played back
+    playback: Playback,
+
+    /// current index in `self.frames`
+    current: usize,
+    /// time spent on the current frame
+    elapsed: Duration,
+    /// is a ping-pong animation going forward?
+    forward: bool,
+    /// has a one-shot animation reached its end?
+    finished: bool,
+}
+
+impl Animation
+{
+    /// create a looping animation of the frames of `sheet` at indices
+    /// `frames`, each lasting `frame_time`
+    pub fn new(sheet: impl Into<Arc<SpriteSheet>>, frames: impl IntoIterator<Item = usize>, frame_time: Duration) -> Self
+    {
+        let frames = frames.into_iter().collect::<Vec<_>>();
+        let durations = vec![frame_time; frames.len()];
+
+        Self::from_parts(sheet.into(), frames, durations, Playback::Loop)
+    }
+
+    /// create a looping animation of every frame in `sheet`, each lasting
+    /// the duration the atlas specifies, or `frame_time` if it doesn't
+    pub fn all(sheet: impl Into<Arc<SpriteSheet>>, frame_time: Duration) -> Self
+    {
+        let sheet = sheet.into();
+
+        let frames = (0..sheet.len()).collect::<Vec<_>>();
+        let durations = sheet.frames
+            .iter()
+            .map(|frame| frame.duration.unwrap_or(frame_time))
+            .collect();
+
+        Self::from_parts(sheet, frames, durations, Playback::Loop)
+    }
+
+    /// create an animation of the frames in the tag named `name`, played
+    /// back as that tag specifies. returns `None` if there's no such tag.
+    /// frames whose duration isn't specified last 100ms
+    pub fn tagged(sheet: impl Into<Arc<SpriteSheet>>, name: &str) -> Option<Self>
+    {
+        let sheet = sheet.into();
+        let tag = sheet.tag(name)?.clone();
+
+        let mut frames = (tag.from..=tag.to).collect::<Vec<_>>();
+        if tag.reverse
+        {
+            frames.reverse();
+        }
+        let durations = frames
+            .iter()
+            .map(|&i| sheet.frames[i].duration.unwrap_or(Duration::from_millis(100)))
+            .collect();
+
+        Some(Self::from_parts(sheet, frames, durations, tag.playback))
+    }
+
+    /// set how this animation plays its frames back
+    pub fn playback(mut self, playback: Playback) -> Self
+    {
+        self.playback = playback;
+        self
+    }
+
+    /// advance this animation by `delta`, typically `Time::delta()`.
+    /// frames lasting zero seconds are skipped
+    pub fn update(&mut self, delta: Duration)
+    {
+        if self.finished || self.frames.is_empty()
+        {
+            return;
+        }
+
+        self.elapsed += delta;
+
+        // zero-length frames in a row. ping-pong passes each frame
+        // at most twice, so more than that means they all are
+        let mut skipped = 0;
+        loop
+        {
+            let duration = self.durations[self.current];
+
+            if duration == Duration::default()
+            {
+                skipped += 1;
+                if skipped > 2 * self.frames.len()
+                {
+                    break;
+                }
+            }
+            else if self.elapsed < duration
+            {
+                break;
+            }
+            else
+            {
+                skipped = 0;
+                self.elapsed -= duration;
+            }
+
+            let last = self.frames.len() - 1;
+            match self.playback
+            {
+                Playback::Loop => self.current = if self.current == last { 0 } else { self.current + 1 },
+                Playback::Once if self.current == last =>
+                {
+                    self.finished = true;
+                    self.elapsed = Duration::default();
+                    break;
+                }
+                Playback::Once => self.current += 1,
+                Playback::PingPong if last == 0 => {}
+                Playback::PingPong =>
+                {
+                    if (self.forward && self.current == last) || (!self.forward && self.current == 0)
+                    {
+                        self.forward = !self.forward;
+                    }
+                    match self.forward
+                    {
+                        true => self.current += 1,
+                        false => self.current -= 1,
+                    }
+                }
+            }
+        }
+    }
+
+    /// go back to the first frame
+    pub fn restart(&mut self)
+    {
+        self.current = 0;
+        self.elapsed = Duration::default();
+        self.forward = true;
+        self.finished = false;
+    }
+
+    /// has a one-shot animation reached its end? always false for
+    /// looping and ping-pong animations
+    #[inline]
+    pub fn is_finished(&self) -> bool
+    {
+        self.finished
+    }
+
+    /// get the index, in the sheet, of the current frame, or `None`
+    /// if this animation has no frames
+    #[inline]
+    pub fn frame(&self) -> Option<usize>
+    {
+        self.frames.get(self.current).copied()
+    }
+
+    /// get the sheet containing this animation's frames
+    #[inline]
+    pub fn sheet(&self) -> &Arc<SpriteSheet>
+    {
+        &self.sheet
+    }
+
+    /// draw the current frame onto a bitmap, with its top-left corner
+    /// at `pos`. does nothing if this animation has no frames
+    pub fn draw<I>(&self, dst: &mut Bitmap<I, impl PixelBufMut>, pos: Vec2<i32>)
+    {
+        if let Some(frame) = self.frame()
+        {
+            self.sheet.draw(frame, dst, pos);
+        }
+    }
+
+    /// create an animation from its raw parts, starting on its
+    /// first frame
+    fn from_parts(sheet: Arc<SpriteSheet>, frames: Vec<usize>, durations: Vec<Duration>, playback: Playback) -> Self
+    {
+        assert!(frames.iter().all(|&i| i < sheet.len()), "animation frame out of sheet bounds");
+
+        Self
+        {
+            sheet,
+            frames,
+            durations,
+            playback,
+
+            current: 0,
+            elapsed: Duration::default(),
+            forward: true,
+            finished: false,
+        }
+    }
+}
+
+/// reasons why loading a sprite atlas can fail
+#[derive(Debug)]
+pub enum AtlasError
+{
+    /// couldn't read the JSON file
+    Io(std::io::Error),
+    /// the JSON is malformed
+    Json(serde_json::Error),
+    /// couldn't open the image
+    Image(image::ImageError),
+    /// the JSON is well-formed but isn't a sprite atlas
+    Format(String),
+}
+
+impl fmt::Display for AtlasError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            AtlasError::Io(e) => write!(f, "can't read atlas: {}", e),
+            AtlasError::Json(e) => write!(f, "malformed atlas: {}", e),
+            AtlasError::Image(e) => write!(f, "can't open atlas image: {}", e),
+            AtlasError::Format(e) => write!(f, "invalid atlas: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AtlasError { }
+
+/// parse a frame named `name` in an Aseprite or TexturePacker atlas
+fn parse_frame(name: &str, f: &Value) -> Result<SpriteFrame, AtlasError>
+{
+    let err = |what: &str| AtlasError::Format(format!("frame \"{}\": {}", name, what));
+
+    if f["rotated"].as_bool().unwrap_or(false)
+    {
+        return Err(err("rotated frames aren't supported"));
+    }
+
+    // { "x": _, "y": _, "w": _, "h": _ } with non-negative integers
+    let rect = |v: &Value| -> Option<Rect<usize, usize>>
+    {
+        let n = |k: &str| v[k].as_u64().map(|n| n as usize);
+
+        Some(Rect::new(n("x")?, n("y")?, n("w")?, n("h")?))
+    };
+
+    let frame = rect(&f["frame"]).ok_or_else(|| err("missing or invalid \"frame\""))?;
+    let offset = match rect(&f["spriteSourceSize"])
+    {
+        Some(src) if f["trimmed"].as_bool().unwrap_or(false) => Vec2::new(src.x, src.y).as_(),
+        _ => Vec2::zero(),
+    };
+    let duration = f["duration"].as_u64().map(Duration::from_millis);
+
+    Ok(SpriteFrame { name: name.to_owned(), rect: frame, offset, duration })
+}
+
+/// parse a tag in an Aseprite atlas with `len` frames
+fn parse_tag(t: &Value, len: usize) -> Result<SpriteTag, AtlasError>
+{
+    let name = t["name"].as_str().unwrap_or_default().to_owned();
+    let err = |what: &str| AtlasError::Format(format!("tag \"{}\": {}", name, what));
+
+    let from = t["from"].as_u64().ok_or_else(|| err("missing \"from\""))? as usize;
+    let to = t["to"].as_u64().ok_or_else(|| err("missing \"to\""))? as usize;
+
+    if from > to || to >= len
+    {
+        return Err(err("frame range out of bounds"));
+    }
+
+    let (playback, reverse) = match t["direction"].as_str().unwrap_or("forward")
+    {
+        "forward" => (Playback::Loop, false),
+        "reverse" => (Playback::Loop, true),
+        "pingpong" => (Playback::PingPong, false),
+        "pingpong_reverse" => (Playback::PingPong, true),
+        dir => return Err(err(&format!("unknown direction \"{}\"", dir))),
+    };
+
+    Ok(SpriteTag { name, from, to, playback, reverse })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// blank 64x32 image
+    fn image() -> Image
+    {
+        Image::blank((64, 32))
+    }
+
+    #[test]
+    fn grid()
+    {
+        assert_eq!(SpriteSheet::grid(image(), (16, 16)).len(), 8);
+        assert_eq!(SpriteSheet::grid(image(), (20, 20)).len(), 3);
+    }
+
+    #[test]
+    fn grid_zero_size()
+    {
+        assert!(SpriteSheet::grid(image(), (0, 16)).is_empty());
+        assert!(SpriteSheet::grid(image(), (16, 0)).is_empty());
+    }
+
+    #[test]
+    fn empty_animation()
+    {
+        let mut anim = Animation::new(SpriteSheet::grid(image(), (16, 16)), None, Duration::from_millis(100));
+        let mut dst = image();
+
+        anim.update(Duration::from_secs(1));
+        anim.draw(&mut dst, Vec2::zero());
+
+        assert_eq!(anim.frame(), None);
+    }
+
+    #[test]
+    fn frame()
+    {
+        let mut anim = Animation::new(SpriteSheet::grid(image(), (16, 16)), vec![2, 5], Duration::from_millis(100));
+
+        assert_eq!(anim.frame(), Some(2));
+        anim.update(Duration::from_millis(150));
+        assert_eq!(anim.frame(), Some(5));
+        anim.update(Duration::from_millis(100));
+        assert_eq!(anim.frame(), Some(2));
+    }
+
+    #[test]
+    fn zero_duration_frames()
+    {
+        let sheet = Arc::new(SpriteSheet::grid(image(), (16, 16)));
+        let (zero, ms) = (Duration::default(), Duration::from_millis);
+        let durations = vec![ms(100), zero, zero, ms(100)];
+
+        let mut anim = Animation::from_parts(sheet.clone(), vec![0, 1, 2, 3], durations.clone(), Playback::Loop);
+        anim.update(ms(100));
+        assert_eq!(anim.frame(), Some(3));
+        anim.update(ms(150));
+        assert_eq!(anim.frame(), Some(0));
+        anim.update(ms(50));
+        assert_eq!(anim.frame(), Some(3));
+
+        let mut anim = Animation::from_parts(sheet.clone(), vec![0, 1, 2, 3], durations, Playback::PingPong);
+        anim.update(ms(200));
+        assert_eq!(anim.frame(), Some(0));
+
+        // zero-length last frame finishes right away
+        let mut anim = Animation::from_parts(sheet.clone(), vec![0, 1], vec![ms(100), zero], Playback::Once);
+        anim.update(ms(100));
+        assert_eq!(anim.frame(), Some(1));
+        assert!(anim.is_finished());
+
+        // nothing to wait for, but no hang either
+        for &playback in &[Playback::Loop, Playback::PingPong, Playback::Once]
+        {
+            let mut anim = Animation::from_parts(sheet.clone(), vec![0, 1, 2], vec![zero; 3], playback);
+            anim.update(ms(100));
+            assert!(anim.frame().is_some());
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use serde_json::Value;
+
+use std::path::Path;
+
+use crate::draw::tilemap::*;
+
+/// parse a JSON map, external tilesets and images being relative
+/// to `dir`
+pub(super) fn parse(json: &str, dir: &Path) -> Result<TileMap, MapError>
+{
+    let root: Value = serde_json::from_str(json).map_err(MapError::Json)?;
+
+    if root["orientation"].as_str().unwrap_or("orthogonal") != "orthogonal"
+    {
+        return Err(MapError::Format("only orthogonal maps are supported".into()));
+    }
+    if root["infinite"].as_bool().unwrap_or(false)
+    {
+        return Err(MapError::Format("infinite maps aren't supported".into()));
+    }
+
+    let size = Extent2::new(num(&root, "width")?, num(&root, "height")?);
+    let tile_size = Extent2::new(num(&root, "tilewidth")?, num(&root, "tileheight")?);
+    if tile_size.w == 0 || tile_size.h == 0
+    {
+        return Err(MapError::Format("tiles can't be 0 pixels wide or tall".into()));
+    }
+
+    let mut map = TileMap::new(size, tile_size);
+    map.properties = properties(&root)?;
+
+    for set in array(&root, "tilesets")
+    {
+        map.tilesets.push(tileset(set, dir)?);
+    }
+    map.tilesets.sort_by_key(|set| set.first_gid);
+
+    for layer in array(&root, "layers")
+    {
+        layers(layer, Vec2::zero(), true, size, &mut map.layers)?;
+    }
+
+    Ok(map)
+}
+
+/// parse a tileset, either inline or referring to a JSON tileset file
+fn tileset(set: &Value, dir: &Path) -> Result<Tileset, MapError>
+{
+    let first_gid = num(set, "firstgid")? as u32;
+
+    // external tileset
+    if let Some(source) = set["source"].as_str()
+    {
+        let path = dir.join(source);
+        let json = std::fs::read_to_string(&path).map_err(MapError::Io)?;
+        let ext: Value = serde_json::from_str(&json).map_err(MapError::Json)?;
+
+        let dir = path.parent().unwrap_or(dir);
+
+        return describe(&ext, first_gid)?.load(dir);
+    }
+
+    describe(set, first_gid)?.load(dir)
+}
+
+/// get the raw parts of a tileset
+fn describe(set: &Value, first_gid: u32) -> Result<TilesetDesc, MapError>
+{
+    let name = set["name"].as_str().unwrap_or_default().to_owned();
+
+    let image = set["image"]
+        .as_str()
+        .ok_or_else(|| MapError::Format(format!("tileset \"{}\" has no single image", name)))?
+        .to_owned();
+
+    Ok(TilesetDesc
+    {
+        first_gid,
+        image,
+        tile_size: Extent2::new(num(set, "tilewidth")?, num(set, "tileheight")?),
+        margin: set["margin"].as_u64().unwrap_or(0) as usize,
+        spacing: set["spacing"].as_u64().unwrap_or(0) as usize,
+        columns: num(set, "columns")?,
+        count: num(set, "tilecount")?,
+        name,
+    })
+}
+
+/// parse a tile, object or group layer into `out`, flattening groups.
+/// `offset` and `visible` are inherited from parent groups
+fn layers(layer: &Value, offset: Vec2<i32>, visible: bool, size: Extent2<usize>, out: &mut Vec<Layer>) -> Result<(), MapError>
+{
+    let offset = offset + Vec2::new
+    (
+        layer["offsetx"].as_f64().unwrap_or(0.0),
+        layer["offsety"].as_f64().unwrap_or(0.0),
+    ).as_();
+    let visible = visible && layer["visible"].as_bool().unwrap_or(true);
+    let name = layer["name"].as_str().unwrap_or_default().to_owned();
+
+    match layer["type"].as_str().unwrap_or_default()
+    {
+        "tilelayer" =>
+        {
+            let invalid = || MapError::Format(format!("invalid tile data in layer \"{}\"", name));
+
+            let tiles = match &layer["data"]
+            {
+                Value::Array(data) => data
+                    .iter()
+                    .map(|n| n.as_u64().map(|n| n as u32).ok_or_else(invalid))
+                    .collect::<Result<Vec<_>, _>>()?,
+                Value::String(data) => decode_tiles(data, layer["compression"].as_str())?,
+                _ => return Err(invalid()),
+            };
+
+            if tiles.len() != size.w * size.h
+            {
+                return Err(MapError::Format(format!("layer \"{}\" doesn't have as many tiles as the map", name)));
+            }
+
+            out.push(Layer::Tiles(TileLayer { name, visible, offset, tiles, properties: properties(layer)? }));
+        }
+        "objectgroup" =>
+        {
+            let objects = array(layer, "objects")
+                .map(|obj| object(obj, offset))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            out.push(Layer::Objects(ObjectLayer { name, visible, objects, properties: properties(layer)? }));
+        }
+        "group" => for child in array(layer, "layers")
+        {
+            layers(child, offset, visible, size, out)?;
+        },
+        // image layers and everything else
+        _ => {}
+    }
+    Ok(())
+}
+
+/// parse an object in a layer offset by `offset`
+fn object(obj: &Value, offset: Vec2<i32>) -> Result<MapObject, MapError>
+{
+    let offset = offset.as_::<f32>();
+    let f = |key: &str| obj[key].as_f64().unwrap_or(0.0) as f32;
+
+    Ok(MapObject
+    {
+        id: obj["id"].as_u64().unwrap_or(0) as u32,
+        name: obj["name"].as_str().unwrap_or_default().to_owned(),
+        kind: obj["type"].as_str().or_else(|| obj["class"].as_str()).unwrap_or_default().to_owned(),
+        rect: Rect::new(f("x") + offset.x, f("y") + offset.y, f("width"), f("height")),
+        gid: obj["gid"].as_u64().map(|gid| gid as u32),
+        visible: obj["visible"].as_bool().unwrap_or(true),
+        properties: properties(obj)?,
+    })
+}
+
+/// parse the "properties" of a map, layer or object, if any
+fn properties(v: &Value) -> Result<Properties, MapError>
+{
+    let mut props = Properties::new();
+
+    for prop in array(v, "properties")
+    {
+        let name = prop["name"].as_str().unwrap_or_default().to_owned();
+        let kind = prop["type"].as_str().unwrap_or("string");
+
+        let value = match &prop["value"]
+        {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+
+        props.insert(name, Property::parse(kind, &value)?);
+    }
+    Ok(props)
+}
+
+/// iterate the elements of an array, if any
+fn array<'a>(v: &'a Value, key: &str) -> impl Iterator<Item = &'a Value>
+{
+    v[key]
+        .as_array()
+        .into_iter()
+        .flatten()
+}
+
+/// get a required non-negative integer
+fn num(v: &Value, key: &str) -> Result<usize, MapError>
+{
+    v[key]
+        .as_u64()
+        .map(|n| n as usize)
+        .ok_or_else(|| MapError::Format(format!("missing or invalid \"{}\"", key)))
+}
@@ -0,0 +1,693 @@
+mod tmx;
+mod json;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::fmt;
+
+use crate::draw::{ Bitmap, PixelBufMut, SpriteSheet, Image };
+use crate::math::{ Vec2, Extent2, Rect, Rgba };
+
+/// bits of a Tiled global tile ID flagging flips and rotations
+const FLIP_FLAGS: u32 = 0xf000_0000;
+
+/// a grid of tiles drawn from one or more tilesets, in layers, such as
+/// a level loaded from the Tiled editor
+/// ```
+/// let mut map = TileMap::open("res/level.tmx")?;
+///
+/// // in draw: follow the player
+/// map.centre_camera(player, c.size());
+/// map.draw(c);
+/// ```
+#[derive(Clone)]
+pub struct TileMap
+{
+    /// width and height, in tiles, of this map
+    size: Extent2<usize>,
+    /// width and height, in pixels, of the map's grid cells
+    tile_size: Extent2<usize>,
+    /// tilesets, sorted by first global ID
+    tilesets: Vec<Tileset>,
+    /// tile and object layers, bottom to top
+    layers: Vec<Layer>,
+    /// custom properties of this map
+    properties: Properties,
+
+    /// position, in map pixels, of the top-left corner of the view
+    pub camera: Vec2<i32>,
+}
+
+/// tiles of a [TileMap], numbered with global IDs starting at `first_gid`
+///
+/// [TileMap]: self::TileMap
+#[derive(Clone)]
+pub struct Tileset
+{
+    /// name of this tileset
+    pub name: String,
+    /// global ID of the first tile
+    pub first_gid: u32,
+    /// every tile in this tileset
+    pub sheet: SpriteSheet,
+}
+
+/// a single layer in a [TileMap]
+///
+/// [TileMap]: self::TileMap
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layer
+{
+    /// a grid of tiles
+    Tiles(TileLayer),
+    /// free-form objects, such as spawn points or triggers
+    Objects(ObjectLayer),
+}
+
+/// a grid of tiles, the same size as its [TileMap]
+///
+/// [TileMap]: self::TileMap
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileLayer
+{
+    /// name of this layer
+    pub name: String,
+    /// is this layer drawn?
+    pub visible: bool,
+    /// offset of this layer when drawn, in pixels
+    pub offset: Vec2<i32>,
+    /// global ID of each tile, row-by-row, where 0 is empty
+    pub tiles: Vec<u32>,
+    /// custom properties of this layer
+    pub properties: Properties,
+}
+
+/// free-form objects placed on a [TileMap]
+///
+/// [TileMap]: self::TileMap
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectLayer
+{
+    /// name of this layer
+    pub name: String,
+    /// is this layer shown in the editor?
+    pub visible: bool,
+    /// objects in this layer
+    pub objects: Vec<MapObject>,
+    /// custom properties of this layer
+    pub properties: Properties,
+}
+
+/// a single object in an [ObjectLayer]
+///
+/// [ObjectLayer]: self::ObjectLayer
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapObject
+{
+    /// unique ID of this object in its map
+    pub id: u32,
+    /// name of this object
+    pub name: String,
+    /// user-defined type, or class, of this object
+    pub kind: String,
+    /// position and size of this object, in map pixels, layer offset
+    /// included. points have a size of zero
+    pub rect: Rect<f32, f32>,
+    /// global ID of this object's tile, if it's a tile object
+    pub gid: Option<u32>,
+    /// is this object shown in the editor?
+    pub visible: bool,
+    /// custom properties of this object
+    pub properties: Properties,
+}
+
+/// custom properties of a map, layer or object, by name
+pub type Properties = HashMap<String, Property>;
+
+/// a custom property value set in the Tiled editor
+#[derive(Debug, Clone, PartialEq)]
+pub enum Property
+{
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Colour(Rgba<u8>),
+    /// path to a file, relative to the map
+    File(String),
+    /// ID of an object in the same map
+    Object(u32),
+}
+
+impl TileMap
+{
+    /// create a new map, `size` tiles wide and tall, with no tilesets
+    /// and no layers. panics if `tile_size` is zero in either dimension
+    pub fn new(size: impl Into<Extent2<usize>>, tile_size: impl Into<Extent2<usize>>) -> Self
+    {
+        let tile_size = tile_size.into();
+
+        assert!(tile_size.w > 0 && tile_size.h > 0, "tiles can't be 0 pixels wide or tall");
+
+        Self
+        {
+            size: size.into(),
+            tile_size,
+            tilesets: Vec::new(),
+            layers: Vec::new(),
+            properties: Properties::new(),
+
+            camera: Vec2::zero(),
+        }
+    }
+
+    /// open a map exported from the Tiled editor, either TMX(".tmx") or
+    /// JSON(".json" or ".tmj"). tilesets and their images are located
+    /// relative to the map
+    ///
+    /// only orthogonal, finite maps are supported. flipped tiles are
+    /// drawn unflipped
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MapError>
+    {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let src = std::fs::read_to_string(path).map_err(MapError::Io)?;
+
+        match path.extension().and_then(|ext| ext.to_str())
+        {
+            Some("tmx") => Self::from_tmx(&src, dir),
+            Some("json") | Some("tmj") => Self::from_json(&src, dir),
+            _ => Err(MapError::Format(format!("unknown map format: {}", path.display()))),
+        }
+    }
+
+    /// parse a map in Tiled's TMX format. external tilesets and images
+    /// are located relative to `dir`
+    pub fn from_tmx(xml: &str, dir: impl AsRef<Path>) -> Result<Self, MapError>
+    {
+        tmx::parse(xml, dir.as_ref())
+    }
+
+    /// parse a map in Tiled's JSON format. external tilesets and images
+    /// are located relative to `dir`
+    pub fn from_json(json: &str, dir: impl AsRef<Path>) -> Result<Self, MapError>
+    {
+        json::parse(json, dir.as_ref())
+    }
+
+    /// add a tileset, numbering its tiles after those of the last one.
+    /// the first tileset starts at 1
+    pub fn tileset(mut self, name: impl Into<String>, sheet: SpriteSheet) -> Self
+    {
+        let first_gid = self.tilesets
+            .last()
+            .map_or(1, |set| set.first_gid + set.sheet.len() as u32);
+
+        self.tilesets.push(Tileset { name: name.into(), first_gid, sheet });
+        self
+    }
+
+    /// add an empty tile layer on top of the others
+    pub fn layer(mut self, name: impl Into<String>) -> Self
+    {
+        self.layers.push(Layer::Tiles(TileLayer
+        {
+            name: name.into(),
+            visible: true,
+            offset: Vec2::zero(),
+            tiles: vec![0; self.size.w * self.size.h],
+            properties: Properties::new(),
+        }));
+        self
+    }
+
+    /// get this map's width and height, in tiles
+    #[inline]
+    pub fn size(&self) -> Extent2<usize>
+    {
+        self.size
+    }
+
+    /// get the width and height, in pixels, of this map's grid cells
+    #[inline]
+    pub fn tile_size(&self) -> Extent2<usize>
+    {
+        self.tile_size
+    }
+
+    /// get this map's width and height, in pixels
+    #[inline]
+    pub fn pixel_size(&self) -> Extent2<usize>
+    {
+        self.size * self.tile_size
+    }
+
+    /// get this map's tilesets
+    #[inline]
+    pub fn tilesets(&self) -> &[Tileset]
+    {
+        &self.tilesets
+    }
+
+    /// get this map's layers, bottom to top
+    #[inline]
+    pub fn layers(&self) -> &[Layer]
+    {
+        &self.layers
+    }
+
+    /// get this map's layers, bottom to top, mutably
+    #[inline]
+    pub fn layers_mut(&mut self) -> &mut [Layer]
+    {
+        &mut self.layers
+    }
+
+    /// get the first tile layer named `name`, if any
+    pub fn tile_layer(&self, name: &str) -> Option<&TileLayer>
+    {
+        self.layers.iter().find_map(|layer| match layer
+        {
+            Layer::Tiles(tiles) if tiles.name == name => Some(tiles),
+            _ => None,
+        })
+    }
+
+    /// get the first object layer named `name`, if any
+    pub fn object_layer(&self, name: &str) -> Option<&ObjectLayer>
+    {
+        self.layers.iter().find_map(|layer| match layer
+        {
+            Layer::Objects(objects) if objects.name == name => Some(objects),
+            _ => None,
+        })
+    }
+
+    /// get this map's custom properties
+    #[inline]
+    pub fn properties(&self) -> &Properties
+    {
+        &self.properties
+    }
+
+    /// get the global ID of the tile at `pos`, in tiles, in the `layer`th
+    /// layer. returns `None` if out of bounds or not a tile layer, and
+    /// `Some(0)` for an empty tile
+    pub fn get(&self, layer: usize, pos: Vec2<i32>) -> Option<u32>
+    {
+        let i = self.index(pos)?;
+
+        match self.layers.get(layer)?
+        {
+            Layer::Tiles(tiles) => Some(tiles.tiles[i] & !FLIP_FLAGS),
+            Layer::Objects(_) => None,
+        }
+    }
+
+    /// set the global ID of the tile at `pos`, in tiles, in the `layer`th
+    /// layer, 0 being empty. does nothing if out of bounds or not a tile
+    /// layer
+    pub fn set(&mut self, layer: usize, pos: Vec2<i32>, gid: u32)
+    {
+        if let Some(i) = self.index(pos)
+        {
+            if let Some(Layer::Tiles(tiles)) = self.layers.get_mut(layer)
+            {
+                tiles.tiles[i] = gid;
+            }
+        }
+    }
+
+    /// get the tile, in tiles, at `pos`, in map pixels
+    #[inline]
+    pub fn tile_at(&self, pos: Vec2<i32>) -> Vec2<i32>
+    {
+        pos.map2(self.tile_size.as_::<i32>().into(), |p, s: i32| p.div_euclid(s))
+    }
+
+    /// move the camera so that `target`, in map pixels, is at the centre of
+    /// a view of size `view`, without showing anything past the map's edges
+    pub fn centre_camera(&mut self, target: Vec2<i32>, view: impl Into<Extent2<usize>>)
+    {
+        let view: Vec2<i32> = view.into().as_::<i32>().into();
+        let max: Vec2<i32> = Vec2::from(self.pixel_size().as_::<i32>()) - view;
+
+        self.camera = (target - view / 2)
+            .map2(max, |p, max| p.min(max).max(0));
+    }
+
+    /// draw every visible tile layer onto a bitmap, bottom to top, as seen
+    /// from `self.camera`
+    ///
+    /// note that tiles are copied as is by `Bitmap::image`, transparent
+    /// pixels included
+    pub fn draw<I>(&self, dst: &mut Bitmap<I, impl PixelBufMut>)
+    {
+        for i in 0..self.layers.len()
+        {
+            self.draw_layer(i, dst);
+        }
+    }
+
+    /// draw the `i`th layer onto a bitmap, as seen from `self.camera`,
+    /// if it's a visible tile layer. only tiles in view are drawn
+    pub fn draw_layer<I>(&self, i: usize, dst: &mut Bitmap<I, impl PixelBufMut>)
+    {
+        let layer = match &self.layers[i]
+        {
+            Layer::Tiles(layer) if layer.visible => layer,
+            _ => return,
+        };
+
+        let tile: Vec2<i32> = self.tile_size.as_::<i32>().into();
+        let view: Vec2<i32> = dst.size().as_::<i32>().into();
+
+        // top-left of the view, in layer pixels
+        let origin = self.camera - layer.offset;
+
+        // range of tiles in view, tall tiles overflowing upwards included
+        let tall = self.tilesets
+            .iter()
+            .flat_map(|set| set.sheet.frames())
+            .map(|frame| frame.rect.h as i32)
+            .max()
+            .unwrap_or(0);
+        let overflow = (tall - tile.y).max(0);
+
+        let min = origin.map2(tile, |o, t| o.div_euclid(t)).map(|n| n.max(0));
+        let max = Vec2::new(origin.x + view.x, origin.y + view.y + overflow)
+            .map2(tile, |o, t| o.div_euclid(t) + 1)
+            .map2(Vec2::from(self.size.as_::<i32>()), |n, s| n.min(s));
+
+        for y in min.y..max.y
+        {
+            for x in min.x..max.x
+            {
+                let gid = layer.tiles[y as usize * self.size.w + x as usize] & !FLIP_FLAGS;
+
+                if let Some((set, local)) = self.find_tile(gid)
+                {
+                    let frame = &set.sheet.frames()[local];
+
+                    // tiles are aligned to the bottom-left of their cell
+                    let pos = Vec2::new(x * tile.x, (y + 1) * tile.y - frame.rect.h as i32) - origin;
+
+                    dst.image(&set.sheet.frame(local), pos);
+                }
+            }
+        }
+    }
+
+    /// get the tileset containing a global tile ID and that tile's index
+    /// in it, if any
+    fn find_tile(&self, gid: u32) -> Option<(&Tileset, usize)>
+    {
+        if gid == 0
+        {
+            return None;
+        }
+
+        let set = self.tilesets
+            .iter()
+            .rev()
+            .find(|set| set.first_gid <= gid)?;
+        let local = (gid - set.first_gid) as usize;
+
+        match local < set.sheet.len()
+        {
+            true => Some((set, local)),
+            false => None,
+        }
+    }
+
+    /// index of `pos`, in tiles, in a tile layer, if in bounds
+    fn index(&self, pos: Vec2<i32>) -> Option<usize>
+    {
+        let in_bounds = pos.x >= 0
+            && pos.y >= 0
+            && (pos.x as usize) < self.size.w
+            && (pos.y as usize) < self.size.h;
+
+        match in_bounds
+        {
+            true => Some(pos.y as usize * self.size.w + pos.x as usize),
+            false => None,
+        }
+    }
+}
+
+impl Property
+{
+    /// get this property as a bool, if it is one
+    pub fn as_bool(&self) -> Option<bool>
+    {
+        match self
+        {
+            Property::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// get this property as an integer, if it is one
+    pub fn as_int(&self) -> Option<i64>
+    {
+        match self
+        {
+            Property::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// get this property as a float, if it's a number
+    pub fn as_float(&self) -> Option<f64>
+    {
+        match self
+        {
+            Property::Float(n) => Some(*n),
+            Property::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    /// get this property as a string, if it's a string or a file
+    pub fn as_str(&self) -> Option<&str>
+    {
+        match self
+        {
+            Property::String(s) | Property::File(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// parse a property from Tiled's type name and textual value
+    fn parse(kind: &str, value: &str) -> Result<Self, MapError>
+    {
+        let err = || MapError::Format(format!("invalid {} property: \"{}\"", kind, value));
+
+        Ok(match kind
+        {
+            "bool" => Property::Bool(value.parse().map_err(|_| err())?),
+            "int" => Property::Int(value.parse().map_err(|_| err())?),
+            "float" => Property::Float(value.parse().map_err(|_| err())?),
+            "color" => Property::Colour(parse_colour(value).ok_or_else(err)?),
+            "file" => Property::File(value.to_owned()),
+            "object" => Property::Object(value.parse().map_err(|_| err())?),
+            _ => Property::String(value.to_owned()),
+        })
+    }
+}
+
+/// reasons why loading a map can fail
+#[derive(Debug)]
+pub enum MapError
+{
+    /// couldn't read the map or one of its tilesets
+    Io(std::io::Error),
+    /// the TMX or TSX is malformed
+    Xml(roxmltree::Error),
+    /// the JSON is malformed
+    Json(serde_json::Error),
+    /// couldn't open a tileset's image
+    Image(image::ImageError),
+    /// the file is well-formed but isn't a supported map
+    Format(String),
+}
+
+impl fmt::Display for MapError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            MapError::Io(e) => write!(f, "can't read map: {}", e),
+            MapError::Xml(e) => write!(f, "malformed map: {}", e),
+            MapError::Json(e) => write!(f, "malformed map: {}", e),
+            MapError::Image(e) => write!(f, "can't open tileset image: {}", e),
+            MapError::Format(e) => write!(f, "invalid map: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MapError { }
+
+/// raw parts of a tileset, common to TMX and JSON
+struct TilesetDesc
+{
+    name: String,
+    first_gid: u32,
+    /// path to the image, relative to the map or tileset file
+    image: String,
+    tile_size: Extent2<usize>,
+    margin: usize,
+    spacing: usize,
+    columns: usize,
+    count: usize,
+}
+
+impl TilesetDesc
+{
+    /// open this tileset's image, relative to `dir`, and cut it into tiles
+    fn load(self, dir: &Path) -> Result<Tileset, MapError>
+    {
+        let image = Image::open(dir.join(&self.image)).map_err(MapError::Image)?;
+
+        let (tw, th) = (self.tile_size.w, self.tile_size.h);
+        let rects = (0..self.count)
+            .map(|i| Rect::new
+            (
+                self.margin + (i % self.columns.max(1)) * (tw + self.spacing),
+                self.margin + (i / self.columns.max(1)) * (th + self.spacing),
+                tw,
+                th,
+            ))
+            .collect::<Vec<_>>();
+
+        if rects.iter().any(|r| r.x + r.w > image.width() || r.y + r.h > image.height())
+        {
+            return Err(MapError::Format(format!("tileset \"{}\" is larger than its image", self.name)));
+        }
+
+        Ok(Tileset { name: self.name, first_gid: self.first_gid, sheet: SpriteSheet::new(image, rects) })
+    }
+}
+
+/// decode a tile layer's data encoded as "base64", optionally compressed
+/// with "zlib" or "gzip", into global tile IDs
+fn decode_tiles(data: &str, compression: Option<&str>) -> Result<Vec<u32>, MapError>
+{
+    use std::io::Read;
+
+    let raw = base64::decode(data.trim())
+        .map_err(|e| MapError::Format(format!("invalid base64 tile data: {}", e)))?;
+
+    let mut bytes = Vec::new();
+    let read = match compression
+    {
+        None | Some("") => { bytes = raw; Ok(0) }
+        Some("zlib") => flate2::read::ZlibDecoder::new(&raw[..]).read_to_end(&mut bytes),
+        Some("gzip") => flate2::read::GzDecoder::new(&raw[..]).read_to_end(&mut bytes),
+        Some(other) => return Err(MapError::Format(format!("unsupported tile data compression: {}", other))),
+    };
+    read.map_err(MapError::Io)?;
+
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// parse a Tiled colour, "#AARRGGBB" or "#RRGGBB"
+fn parse_colour(s: &str) -> Option<Rgba<u8>>
+{
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    let n = u32::from_str_radix(hex, 16).ok()?;
+
+    match hex.len()
+    {
+        6 => Some(Rgba::new((n >> 16) as u8, (n >> 8) as u8, n as u8, 0xff)),
+        8 => Some(Rgba::new((n >> 16) as u8, (n >> 8) as u8, n as u8, (n >> 24) as u8)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// a 4x3 map of 8x8 tiles, checkered with red(gid 1) and blue(gid 2)
+    fn map() -> TileMap
+    {
+        let mut tiles = Image::blank((16, 8));
+        tiles.fill(Rgba::red());
+        tiles.stroke(Rgba::red());
+        tiles.rect(Vec2::new(0, 0), Vec2::new(8, 8));
+        tiles.fill(Rgba::blue());
+        tiles.stroke(Rgba::blue());
+        tiles.rect(Vec2::new(8, 0), Vec2::new(8, 8));
+
+        let mut map = TileMap::new((4, 3), (8, 8))
+            .tileset("tiles", SpriteSheet::grid(tiles, (8, 8)))
+            .layer("ground");
+        for y in 0..3
+        {
+            for x in 0..4
+            {
+                map.set(0, Vec2::new(x, y), 1 + ((x + y) % 2) as u32);
+            }
+        }
+        map
+    }
+
+    /// colour of the map at a pixel of the view, computed one pixel at a
+    /// time, or black outside the map
+    fn expected(map: &TileMap, pos: Vec2<i32>, offset: Vec2<i32>) -> Rgba<u8>
+    {
+        let tile = map.tile_at(pos + map.camera - offset);
+
+        match map.get(0, tile)
+        {
+            Some(1) => Rgba::red(),
+            Some(2) => Rgba::blue(),
+            _ => Rgba::black(),
+        }
+    }
+
+    #[test]
+    fn draw_offset_camera()
+    {
+        let offset = Vec2::new(3, -2);
+
+        for &camera in &[Vec2::new(-5, -7), Vec2::new(5, 3), Vec2::new(21, 13), Vec2::new(-40, 50)]
+        {
+            let mut map = map();
+            let mut dst = Image::blank((20, 12));
+
+            if let Layer::Tiles(layer) = &mut map.layers_mut()[0]
+            {
+                layer.offset = offset;
+            }
+            map.camera = camera;
+            dst.background(Rgba::black());
+            map.draw(&mut dst);
+
+            for y in 0..12
+            {
+                for x in 0..20
+                {
+                    let pos = Vec2::new(x, y);
+
+                    assert_eq!(dst[pos], expected(&map, pos, offset), "camera {:?}, pixel {:?}", camera, pos);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_tile_size()
+    {
+        let json = r#"{ "width": 1, "height": 1, "tilewidth": 0, "tileheight": 8, "tilesets": [], "layers": [] }"#;
+
+        assert!(matches!(TileMap::from_json(json, "."), Err(MapError::Format(_))));
+    }
+}
@@ -0,0 +1,227 @@
+use roxmltree::{ Document, Node };
+
+use std::str::FromStr;
+use std::path::Path;
+
+use crate::draw::tilemap::*;
+
+/// parse a TMX map, external tilesets and images being relative
+/// to `dir`
+pub(super) fn parse(xml: &str, dir: &Path) -> Result<TileMap, MapError>
+{
+    let doc = Document::parse(xml).map_err(MapError::Xml)?;
+    let root = doc.root_element();
+
+    if root.tag_name().name() != "map"
+    {
+        return Err(MapError::Format("root element isn't <map>".into()));
+    }
+    if attr(root, "orientation").unwrap_or("orthogonal") != "orthogonal"
+    {
+        return Err(MapError::Format("only orthogonal maps are supported".into()));
+    }
+    if attr(root, "infinite") == Some("1")
+    {
+        return Err(MapError::Format("infinite maps aren't supported".into()));
+    }
+
+    let size = Extent2::new(num(root, "width")?, num(root, "height")?);
+    let tile_size = Extent2::new(num(root, "tilewidth")?, num(root, "tileheight")?);
+    if tile_size.w == 0 || tile_size.h == 0
+    {
+        return Err(MapError::Format("tiles can't be 0 pixels wide or tall".into()));
+    }
+
+    let mut map = TileMap::new(size, tile_size);
+    map.properties = properties(root)?;
+
+    for node in root.children().filter(Node::is_element)
+    {
+        match node.tag_name().name()
+        {
+            "tileset" => map.tilesets.push(tileset(node, dir)?),
+            _ => layers(node, Vec2::zero(), true, size, &mut map.layers)?,
+        }
+    }
+    map.tilesets.sort_by_key(|set| set.first_gid);
+
+    Ok(map)
+}
+
+/// parse a <tileset>, either inline or referring to a TSX file
+fn tileset(node: Node, dir: &Path) -> Result<Tileset, MapError>
+{
+    let first_gid = num(node, "firstgid")?;
+
+    // external tileset
+    if let Some(source) = attr(node, "source")
+    {
+        let path = dir.join(source);
+        let tsx = std::fs::read_to_string(&path).map_err(MapError::Io)?;
+        let doc = Document::parse(&tsx).map_err(MapError::Xml)?;
+
+        let dir = path.parent().unwrap_or(dir);
+
+        return describe(doc.root_element(), first_gid)?.load(dir);
+    }
+
+    describe(node, first_gid)?.load(dir)
+}
+
+/// get the raw parts of a <tileset> element
+fn describe(node: Node, first_gid: u32) -> Result<TilesetDesc, MapError>
+{
+    let name = attr(node, "name").unwrap_or_default().to_owned();
+
+    let image = node
+        .children()
+        .find(|n| n.has_tag_name("image"))
+        .and_then(|n| attr(n, "source"))
+        .ok_or_else(|| MapError::Format(format!("tileset \"{}\" has no single image", name)))?
+        .to_owned();
+
+    Ok(TilesetDesc
+    {
+        first_gid,
+        image,
+        tile_size: Extent2::new(num(node, "tilewidth")?, num(node, "tileheight")?),
+        margin: opt(node, "margin", 0)?,
+        spacing: opt(node, "spacing", 0)?,
+        columns: num(node, "columns")?,
+        count: num(node, "tilecount")?,
+        name,
+    })
+}
+
+/// parse a <layer>, <objectgroup> or <group> into `out`, flattening
+/// groups. `offset` and `visible` are inherited from parent groups
+fn layers(node: Node, offset: Vec2<i32>, visible: bool, size: Extent2<usize>, out: &mut Vec<Layer>) -> Result<(), MapError>
+{
+    let offset = offset + Vec2::new(opt::<f32>(node, "offsetx", 0.0)?, opt(node, "offsety", 0.0)?).as_();
+    let visible = visible && attr(node, "visible") != Some("0");
+    let name = attr(node, "name").unwrap_or_default().to_owned();
+
+    match node.tag_name().name()
+    {
+        "layer" =>
+        {
+            let data = node
+                .children()
+                .find(|n| n.has_tag_name("data"))
+                .ok_or_else(|| MapError::Format(format!("layer \"{}\" has no data", name)))?;
+
+            let tiles = match attr(data, "encoding")
+            {
+                None => data
+                    .children()
+                    .filter(|n| n.has_tag_name("tile"))
+                    .map(|n| opt(n, "gid", 0))
+                    .collect::<Result<Vec<_>, _>>()?,
+                Some("csv") => data
+                    .text()
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|n| n.trim().parse().map_err(|_| MapError::Format(format!("invalid tile in layer \"{}\"", name))))
+                    .collect::<Result<Vec<_>, _>>()?,
+                Some("base64") => decode_tiles(data.text().unwrap_or_default(), attr(data, "compression"))?,
+                Some(other) => return Err(MapError::Format(format!("unsupported tile data encoding: {}", other))),
+            };
+
+            if tiles.len() != size.w * size.h
+            {
+                return Err(MapError::Format(format!("layer \"{}\" doesn't have as many tiles as the map", name)));
+            }
+
+            out.push(Layer::Tiles(TileLayer { name, visible, offset, tiles, properties: properties(node)? }));
+        }
+        "objectgroup" =>
+        {
+            let objects = node
+                .children()
+                .filter(|n| n.has_tag_name("object"))
+                .map(|n| object(n, offset))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            out.push(Layer::Objects(ObjectLayer { name, visible, objects, properties: properties(node)? }));
+        }
+        "group" => for child in node.children().filter(Node::is_element)
+        {
+            layers(child, offset, visible, size, out)?;
+        },
+        // image layers and everything else
+        _ => {}
+    }
+    Ok(())
+}
+
+/// parse an <object> in a layer offset by `offset`
+fn object(node: Node, offset: Vec2<i32>) -> Result<MapObject, MapError>
+{
+    let offset = offset.as_::<f32>();
+
+    Ok(MapObject
+    {
+        id: opt(node, "id", 0)?,
+        name: attr(node, "name").unwrap_or_default().to_owned(),
+        kind: attr(node, "type").or_else(|| attr(node, "class")).unwrap_or_default().to_owned(),
+        rect: Rect::new
+        (
+            opt::<f32>(node, "x", 0.0)? + offset.x,
+            opt::<f32>(node, "y", 0.0)? + offset.y,
+            opt(node, "width", 0.0)?,
+            opt(node, "height", 0.0)?,
+        ),
+        gid: attr(node, "gid").map(|_| num(node, "gid")).transpose()?,
+        visible: attr(node, "visible") != Some("0"),
+        properties: properties(node)?,
+    })
+}
+
+/// parse the <properties> child of an element, if any
+fn properties(node: Node) -> Result<Properties, MapError>
+{
+    let mut props = Properties::new();
+
+    let list = match node.children().find(|n| n.has_tag_name("properties"))
+    {
+        Some(list) => list,
+        None => return Ok(props),
+    };
+    for prop in list.children().filter(|n| n.has_tag_name("property"))
+    {
+        let name = attr(prop, "name").unwrap_or_default().to_owned();
+        // multi-line strings are stored as text
+        let value = attr(prop, "value").or_else(|| prop.text()).unwrap_or_default();
+
+        props.insert(name, Property::parse(attr(prop, "type").unwrap_or("string"), value)?);
+    }
+    Ok(props)
+}
+
+/// get an attribute's value
+fn attr<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str>
+{
+    node.attribute(name)
+}
+
+/// parse a required attribute
+fn num<T: FromStr>(node: Node, name: &str) -> Result<T, MapError>
+{
+    let value = attr(node, name)
+        .ok_or_else(|| MapError::Format(format!("<{}> is missing \"{}\"", node.tag_name().name(), name)))?;
+
+    value
+        .trim()
+        .parse()
+        .map_err(|_| MapError::Format(format!("<{}> has an invalid \"{}\": {}", node.tag_name().name(), name, value)))
+}
+
+/// parse an optional attribute
+fn opt<T: FromStr>(node: Node, name: &str, default: T) -> Result<T, MapError>
+{
+    match attr(node, name)
+    {
+        Some(_) => num(node, name),
+        None => Ok(default),
+    }
+}
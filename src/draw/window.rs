@@ -1,12 +1,15 @@
-use winit::window::{ WindowBuilder, Window as WinitWindow };
-use winit::dpi::{ LogicalSize, PhysicalSize };
+use std::any::Any;
+
+use winit::window::{ WindowBuilder, Window as WinitWindow, Fullscreen, Icon };
+use winit::dpi::{ LogicalSize, LogicalPosition, PhysicalSize };
 use winit::event_loop::EventLoopWindowTarget;
 
-use pixels::{ Pixels, SurfaceTexture };
+use pixels::{ Pixels, PixelsBuilder, SurfaceTexture };
 
-use crate::draw::{ Canvas, CanvasId, Bitmap };
+use crate::draw::{ Canvas, CanvasId, Bitmap, CanvasConfig, Image, ResizePolicy, Viewport };
 use crate::gui::{ GuiCtx, Gui };
-use crate::math::Extent2;
+use crate::math::{ Extent2, Vec2 };
+use crate::error::{ Error, WindowError };
 
 /// represents a window and a pixel buffer
 /// attached to its swapchain
@@ -16,48 +19,164 @@ pub(crate) struct Window
     pub pixels: Pixels,
     /// winit window
     pub winit: WinitWindow,
-    /// pixel buffer size, in pixels
+    /// canvas size, in pixels
     pub size: Extent2<usize>,
+    /// size of a canvas pixel, in logical pixels, when the
+    /// canvas follows the window's size
+    pub scale: usize,
+    /// how the canvas fills the window
+    pub policy: ResizePolicy,
+    /// where the canvas is shown in the window
+    pub viewport: Viewport,
+    /// size of the pixel buffer, which lags behind `size` until
+    /// the buffer is rebuilt
+    pub buffer: Extent2<usize>,
+    /// the canvas' pixels, when they're resampled to the
+    /// window-sized pixel buffer. empty otherwise
+    pub offscreen: Vec<u8>,
+    /// does the pixel buffer need recreating before drawing?
+    pub rebuild: bool,
     /// this window's egui context
     pub gui: GuiCtx,
     /// identifier passes to canvases
     pub id: CanvasId,
+    /// is presenting synced to the display's refresh rate?
+    pub vsync: bool,
+    /// does the window have keyboard focus?
+    pub focused: bool,
+    /// is the cursor locked inside the window?
+    pub cursor_locked: bool,
+    /// this canvas' own `DrawFn`, if any
+    pub draw: Option<Box<dyn Any>>,
 }
 
 impl Window
 {
     /// create a new window
-    pub(crate) fn new(target: &EventLoopWindowTarget<()>, title: impl Into<String>, size: Extent2<usize>, id: CanvasId) -> Self
+    pub(crate) fn new(target: &EventLoopWindowTarget<()>, config: CanvasConfig, id: CanvasId) -> Result<Self, Error>
     {
-        let logical_size = LogicalSize::new(size.w as f64, size.h as f64);
+        let logical = |size: Extent2<usize>| LogicalSize::new(size.w as f64, size.h as f64);
+
+        let mut builder = WindowBuilder::new()
+            .with_inner_size(logical(config.window_size()))
+            .with_resizable(config.resizable)
+            .with_decorations(config.decorations)
+            .with_always_on_top(config.always_on_top)
+            .with_title(config.title);
+
+        if let Some(min) = config.min_size
+        {
+            builder = builder.with_min_inner_size(logical(min));
+        }
+        if let Some(max) = config.max_size
+        {
+            builder = builder.with_max_inner_size(logical(max));
+        }
+        if config.fullscreen
+        {
+            builder = builder.with_fullscreen(Some(Fullscreen::Borderless(None)));
+        }
+        if let Some(icon) = &config.icon
+        {
+            builder = builder.with_window_icon(Some(icon_from(icon)?));
+        }
 
-        let winit = WindowBuilder::new()
-            //.with_min_inner_size(logical_size)
-            .with_inner_size(logical_size)
-            .with_title(title)
+        let winit = builder
             .build(target)
-            .unwrap();
+            .map_err(WindowError::Os)?;
 
-        let pixels =
+        if let Some(pos) = config.position
         {
-            let PhysicalSize { width, height } = winit.inner_size();
+            winit.set_outer_position(LogicalPosition::new(pos.x, pos.y));
+        }
 
-            let surf_tex = SurfaceTexture::new(width, height, &winit);
-            
-            Pixels::new(size.w as u32, size.h as u32, surf_tex).unwrap()
+        let (size, vsync, policy) = (config.size, config.vsync, config.policy);
+        let surface = physical_size(&winit);
+        let buffer = match policy.is_resampled()
+        {
+            true => surface,
+            false => size,
+        };
+        let pixels = create_pixels(&winit, buffer, vsync)?;
+        let offscreen = match policy.is_resampled()
+        {
+            true => vec![0; size.w * size.h * 4],
+            false => Vec::new(),
         };
         let gui = GuiCtx::new(&winit, &pixels);
 
-        Self
+        Ok(Self
         {
-            pixels, winit, size, gui, id
+            pixels,
+            winit,
+            size,
+            scale: config.scale,
+            policy,
+            viewport: policy.viewport(size, surface),
+            buffer,
+            offscreen,
+            rebuild: false,
+            gui,
+            id,
+            vsync,
+            focused: false,
+            cursor_locked: false,
+            draw: config.draw,
+        })
+    }
+
+    /// recreate the pixel buffer if its size changed since the
+    /// last frame. this should be called before `get_frame`
+    pub(crate) fn prepare_frame(&mut self) -> Result<(), Error>
+    {
+        let surface = physical_size(&self.winit);
+
+        // minimised
+        if !self.rebuild || surface.w == 0 || surface.h == 0
+        {
+            return Ok(());
         }
+        let size = match self.policy.is_resampled()
+        {
+            true => surface,
+            false => self.size,
+        };
+        self.pixels = create_pixels(&self.winit, size, self.vsync)?;
+        self.gui.reattach(&self.pixels);
+        self.buffer = size;
+        self.rebuild = false;
+
+        Ok(())
     }
 
     /// get the next canvas to draw to
     pub(crate) fn get_frame(&mut self) -> Canvas
     {
-        Bitmap::new(self.id, self.pixels.get_frame(), self.size)
+        let (size, buffer) = (self.size, self.buffer);
+
+        frame(self.id, self.policy, &mut self.offscreen[..], self.pixels.get_frame(), size, buffer)
+    }
+
+    /// borrow the canvas' pixels, as last drawn
+    pub(crate) fn canvas(&mut self) -> Bitmap<CanvasId, &[u8]>
+    {
+        let (size, buffer) = (self.size, self.buffer);
+
+        frame(self.id, self.policy, &self.offscreen[..], &*self.pixels.get_frame(), size, buffer)
+    }
+
+    /// scale the canvas drawn to the window-sized pixel buffer,
+    /// if it isn't drawn there directly. this should be called
+    /// after drawing to `get_frame`
+    pub(crate) fn finish_frame(&mut self)
+    {
+        // the pixel buffer is only window-sized once rebuilt
+        if self.policy.is_resampled() && !self.rebuild
+        {
+            let surface = physical_size(&self.winit);
+
+            self.viewport.resample(&self.offscreen, self.size, self.pixels.get_frame(), surface);
+        }
     }
 
     /// get the canvas to draw gui to
@@ -66,10 +185,137 @@ impl Window
         Gui::new(self.id, &self.gui.context())
     }
 
-    /// resize the inner pixel and GUI framebuffer
+    /// canvas pixel under a position in the window, in physical
+    /// pixels, clamped to the canvas' bounds
+    pub(crate) fn window_to_canvas(&self, pos: (f32, f32)) -> Vec2<i32>
+    {
+        if self.policy.is_resampled()
+        {
+            return self.viewport.to_canvas(pos.into(), self.size);
+        }
+        let (x, y) = self.pixels
+            .window_pos_to_pixel(pos)
+            .unwrap_or_else(|pos| self.pixels.clamp_pixel_pos(pos));
+
+        Vec2::new(x as i32, y as i32)
+    }
+
+    /// the window was resized to `w`x`h` physical pixels: resize
+    /// the surface and GUI, and the canvas too if it follows the
+    /// window's size. minimising leaves everything as it was, so
+    /// the canvas is intact once the window is restored
     pub(crate) fn resize(&mut self, w: u32, h: u32)
     {
+        if w == 0 || h == 0
+        {
+            return;
+        }
+        let surface = Extent2::new(w, h).as_::<usize>();
+        let logical = (surface.as_::<f64>() / self.winit.scale_factor()).as_::<usize>();
+
         self.pixels.resize(w, h);
         self.gui.resize(w, h);
+
+        let size = self.policy.canvas_size(self.size, logical, self.scale);
+        if size != self.size
+        {
+            self.resize_canvas(size);
+        }
+        // the pixel buffer is as big as the window
+        if self.policy.is_resampled()
+        {
+            self.rebuild = true;
+        }
+        self.viewport = self.policy.viewport(self.size, surface);
+    }
+
+    /// change the canvas' size. the pixel buffer is recreated
+    /// before the next frame
+    pub(crate) fn resize_canvas(&mut self, size: Extent2<usize>)
+    {
+        self.size = size;
+        self.viewport = self.policy.viewport(size, physical_size(&self.winit));
+
+        match self.policy.is_resampled()
+        {
+            true => self.offscreen = vec![0; size.w * size.h * 4],
+            false => self.rebuild = true,
+        }
     }
-}
\ No newline at end of file
+}
+
+/// the canvas of size `size`: `offscreen` if it's resampled, or
+/// else the pixel buffer, at the buffer's size. that's the old
+/// size until the buffer is rebuilt, which waits while minimised
+fn frame<B>(id: CanvasId, policy: ResizePolicy, offscreen: B, pixels: B, size: Extent2<usize>, buffer: Extent2<usize>) -> Bitmap<CanvasId, B>
+{
+    match policy.is_resampled()
+    {
+        true => Bitmap::new(id, offscreen, size),
+        false => Bitmap::new(id, pixels, buffer),
+    }
+}
+
+/// inner size of a window, in physical pixels
+fn physical_size(winit: &WinitWindow) -> Extent2<usize>
+{
+    let PhysicalSize { width, height } = winit.inner_size();
+
+    Extent2::new(width, height).as_()
+}
+
+/// create a pixel buffer of `size`, drawn to the whole window
+fn create_pixels(winit: &WinitWindow, size: Extent2<usize>, vsync: bool) -> Result<Pixels, WindowError>
+{
+    let surface = physical_size(winit);
+    let surf_tex = SurfaceTexture::new(surface.w as u32, surface.h as u32, winit);
+
+    PixelsBuilder::new(size.w as u32, size.h as u32, surf_tex)
+        .enable_vsync(vsync)
+        .build()
+        .map_err(WindowError::Pixels)
+}
+
+/// convert an image to a window icon
+fn icon_from(image: &Image) -> Result<Icon, WindowError>
+{
+    let mut rgba = Vec::with_capacity(image.area() * 4);
+    for px in image.pixels()
+    {
+        rgba.extend_from_slice(&[px.r, px.g, px.b, px.a]);
+    }
+
+    Icon::from_rgba(rgba, image.width() as u32, image.height() as u32).map_err(WindowError::Icon)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    use crate::math::Rgba;
+
+    #[test]
+    fn frame_before_rebuild()
+    {
+        // canvas grown to 8x8 while minimised, so the pixel buffer
+        // is still 4x4
+        let (size, buffer) = (Extent2::new(8, 8), Extent2::new(4, 4));
+        let mut offscreen = vec![0; 8 * 8 * 4];
+        let mut pixels = vec![0; 4 * 4 * 4];
+
+        for &policy in &[ResizePolicy::Integer, ResizePolicy::FollowWindow]
+        {
+            let mut canvas = frame(CanvasId::zero(), policy, &mut offscreen[..], &mut pixels[..], size, buffer);
+
+            assert_eq!(canvas.size(), buffer);
+            canvas.background(Rgba::red());
+            canvas.rect(Vec2::new(0, 0), Vec2::new(8, 8));
+        }
+        assert!(pixels.chunks_exact(4).all(|px| px != [0, 0, 0, 0]));
+
+        // resampled canvases are resized right away
+        let canvas = frame(CanvasId::zero(), ResizePolicy::Letterbox, &mut offscreen[..], &mut pixels[..], size, buffer);
+        assert_eq!(canvas.size(), size);
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+use std::io;
+
+use image::ImageError;
+
+use crate::draw::{ AtlasError, MapError, FontError };
+
+/// anything that can go wrong while running a sketch or
+/// loading its assets
+#[derive(Debug)]
+pub enum Error
+{
+    /// an image couldn't be opened, decoded or saved
+    Image(ImageError),
+    /// a sprite atlas couldn't be loaded
+    Atlas(AtlasError),
+    /// a tile map couldn't be loaded
+    Map(MapError),
+    /// a font couldn't be loaded
+    Font(FontError),
+    /// an audio file couldn't be read or played
+    #[cfg(feature = "sound")]
+    Audio(AudioError),
+    /// a window or its pixel buffer couldn't be created or
+    /// rendered to
+    Window(WindowError),
+    /// reading or writing a file failed
+    Io(io::Error),
+}
+
+/// what went wrong with audio
+#[cfg(feature = "sound")]
+#[derive(Debug)]
+pub enum AudioError
+{
+    /// the file couldn't be opened or decoded
+    Read(audrey::read::ReadError),
+    /// no output device could be connected to
+    Stream(rodio::StreamError),
+    /// sounds can't be played without an output device
+    NoOutput,
+    /// playback couldn't start on the output device
+    Play(rodio::PlayError),
+}
+
+/// what went wrong with a window
+#[derive(Debug)]
+pub enum WindowError
+{
+    /// the platform refused to create the window
+    Os(winit::error::OsError),
+    /// the pixel buffer couldn't be created or rendered
+    Pixels(pixels::Error),
+    /// the window icon's image was invalid
+    Icon(winit::window::BadIcon),
+    /// the canvas, by title, has a draw function for a different
+    /// sketch than the one running
+    DrawFn(String),
+}
+
+/// shorthand for results of this crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Error::Image(e) => write!(f, "image error: {}", e),
+            Error::Atlas(e) => write!(f, "{}", e),
+            Error::Map(e) => write!(f, "{}", e),
+            Error::Font(e) => write!(f, "{}", e),
+            #[cfg(feature = "sound")]
+            Error::Audio(e) => write!(f, "audio error: {}", e),
+            Error::Window(e) => write!(f, "window error: {}", e),
+            Error::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            Error::Image(e) => Some(e),
+            Error::Atlas(e) => Some(e),
+            Error::Map(e) => Some(e),
+            Error::Font(e) => Some(e),
+            #[cfg(feature = "sound")]
+            Error::Audio(e) => Some(e),
+            Error::Window(e) => Some(e),
+            Error::Io(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "sound")]
+impl fmt::Display for AudioError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            AudioError::Read(e) => write!(f, "couldn't read audio file: {}", e),
+            AudioError::Stream(e) => write!(f, "couldn't open audio output: {}", e),
+            AudioError::NoOutput => write!(f, "no audio output device"),
+            AudioError::Play(e) => write!(f, "couldn't start playback: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "sound")]
+impl std::error::Error for AudioError { }
+
+impl fmt::Display for WindowError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            WindowError::Os(e) => write!(f, "couldn't create window: {}", e),
+            WindowError::Pixels(e) => write!(f, "pixel buffer failed: {}", e),
+            WindowError::Icon(e) => write!(f, "invalid window icon: {}", e),
+            WindowError::DrawFn(title) => write!(f, "canvas \"{}\" has a draw function for a different sketch", title),
+        }
+    }
+}
+
+impl std::error::Error for WindowError { }
+
+impl From<ImageError> for Error
+{
+    fn from(e: ImageError) -> Self
+    {
+        Error::Image(e)
+    }
+}
+
+impl From<AtlasError> for Error
+{
+    fn from(e: AtlasError) -> Self
+    {
+        Error::Atlas(e)
+    }
+}
+
+impl From<MapError> for Error
+{
+    fn from(e: MapError) -> Self
+    {
+        Error::Map(e)
+    }
+}
+
+impl From<FontError> for Error
+{
+    fn from(e: FontError) -> Self
+    {
+        Error::Font(e)
+    }
+}
+
+#[cfg(feature = "sound")]
+impl From<AudioError> for Error
+{
+    fn from(e: AudioError) -> Self
+    {
+        Error::Audio(e)
+    }
+}
+
+impl From<WindowError> for Error
+{
+    fn from(e: WindowError) -> Self
+    {
+        Error::Window(e)
+    }
+}
+
+impl From<io::Error> for Error
+{
+    fn from(e: io::Error) -> Self
+    {
+        Error::Io(e)
+    }
+}
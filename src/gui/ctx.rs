@@ -53,8 +53,9 @@ impl GuiCtx
     /// `render` and any UI drawing logic
     pub(crate) fn prepare(&mut self, time: &Time)
     {
-        // update time for animations
-        self.platform.update_time(time.elapsed().as_secs_f64());
+        // update time for animations, which keep going while
+        // the sketch is paused or slowed down
+        self.platform.update_time(time.real_elapsed().as_secs_f64());
         // begin GUI frame
         self.platform.begin_frame();
     }
@@ -105,6 +106,13 @@ impl GuiCtx
         self.screen_desc.physical_height = h;
     }
 
+    /// draw with a new pixel buffer's device, keeping the GUI's
+    /// state
+    pub(crate) fn reattach(&mut self, pixels: &Pixels)
+    {
+        self.rpass = RenderPass::new(pixels.device(), pixels::wgpu::TextureFormat::Bgra8UnormSrgb);
+    }
+
     /// get the egui context reference
     pub(crate) fn context(&self) -> CtxRef
     {
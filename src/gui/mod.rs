@@ -2,10 +2,12 @@ mod canvas;
 mod window;
 mod ctx;
 mod ext;
+mod stats;
 
 pub use canvas::Gui;
 pub use ext::UiExt;
 
 pub(crate) use ctx::GuiCtx;
+pub(crate) use stats::overlay;
 
 pub use egui;
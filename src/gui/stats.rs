@@ -0,0 +1,66 @@
+use egui::{ Color32, Sense, Stroke, pos2, vec2 };
+
+use crate::input::{ FrameStats, Phase };
+use crate::gui::Gui;
+
+/// height of the frame time graph, in points
+const GRAPH_HEIGHT: f32 = 80.0;
+/// frame time at the top of the graph, in milliseconds
+const GRAPH_MAX_MS: f32 = 50.0;
+
+/// show a window with a frame time graph and each phase's stats
+pub(crate) fn overlay(gui: &Gui, stats: &FrameStats)
+{
+    gui
+        .window("frame time")
+        .default_pos((8.0, 8.0))
+        .resizable(false)
+        .build(|ui|
+        {
+            // one bar per frame, newest on the right
+            let (rect, _) = ui.allocate_exact_size(vec2(stats.len().max(1) as f32, GRAPH_HEIGHT), Sense::hover());
+            let painter = ui.painter();
+            let y = |ms: f32| rect.bottom() - ms.min(GRAPH_MAX_MS) / GRAPH_MAX_MS * rect.height();
+
+            painter.rect_filled(rect, 0.0, Color32::from_black_alpha(160));
+            for (i, frame) in stats.history(Phase::Frame).enumerate()
+            {
+                let ms = frame.as_secs_f32() * 1000.0;
+                let colour = match ms
+                {
+                    ms if ms <= 1000.0 / 55.0 => Color32::from_rgb(100, 200, 100),
+                    ms if ms <= 1000.0 / 28.0 => Color32::from_rgb(230, 180, 60),
+                    _ => Color32::from_rgb(220, 80, 80),
+                };
+                let x = rect.left() + i as f32 + 0.5;
+
+                painter.line_segment([pos2(x, rect.bottom()), pos2(x, y(ms))], Stroke::new(1.0, colour));
+            }
+            // 60 and 30 fps
+            for &fps in &[60.0, 30.0]
+            {
+                let at = y(1000.0 / fps);
+                painter.line_segment([pos2(rect.left(), at), pos2(rect.right(), at)], Stroke::new(1.0, Color32::from_white_alpha(60)));
+            }
+
+            // per-phase stats, in milliseconds
+            let ms = |d: std::time::Duration| d.as_secs_f32() * 1000.0;
+            for &(name, phase) in &
+            [
+                ("frame", Phase::Frame),
+                ("update", Phase::Update),
+                ("draw", Phase::Draw),
+                ("gui", Phase::Gui),
+                ("present", Phase::Present),
+            ]
+            {
+                let s = stats.summary(phase);
+
+                ui.monospace(format!
+                (
+                    "{:<8}{:>6.2} avg {:>6.2} p95 {:>6.2} max",
+                    name, ms(s.mean), ms(s.p95), ms(s.max)
+                ));
+            }
+        });
+}
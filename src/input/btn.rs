@@ -16,159 +16,159 @@
 macro_rules! btn
 {
     // mouse
-    ("lmb") => { framework::input::MouseButton::Left };
-    ("LMB") => { framework::input::MouseButton::Left };
-    ("rmb") => { framework::input::MouseButton::Right };
-    ("RMB") => { framework::input::MouseButton::Right };
-    ("mmb") => { framework::input::MouseButton::Middle };
-    ("MMB") => { framework::input::MouseButton::Middle };
+    ("lmb") => { $crate::input::MouseButton::Left };
+    ("LMB") => { $crate::input::MouseButton::Left };
+    ("rmb") => { $crate::input::MouseButton::Right };
+    ("RMB") => { $crate::input::MouseButton::Right };
+    ("mmb") => { $crate::input::MouseButton::Middle };
+    ("MMB") => { $crate::input::MouseButton::Middle };
 
     // keyboard
-    ("1") => { framework::input::KeyCode::Key1 };
-    ("2") => { framework::input::KeyCode::Key2 };
-    ("3") => { framework::input::KeyCode::Key3 };
-    ("4") => { framework::input::KeyCode::Key4 };
-    ("5") => { framework::input::KeyCode::Key5 };
-    ("6") => { framework::input::KeyCode::Key6 };
-    ("7") => { framework::input::KeyCode::Key7 };
-    ("8") => { framework::input::KeyCode::Key8 };
-    ("9") => { framework::input::KeyCode::Key9 };
-    ("0") => { framework::input::KeyCode::Key0 };
+    ("1") => { $crate::input::KeyCode::Key1 };
+    ("2") => { $crate::input::KeyCode::Key2 };
+    ("3") => { $crate::input::KeyCode::Key3 };
+    ("4") => { $crate::input::KeyCode::Key4 };
+    ("5") => { $crate::input::KeyCode::Key5 };
+    ("6") => { $crate::input::KeyCode::Key6 };
+    ("7") => { $crate::input::KeyCode::Key7 };
+    ("8") => { $crate::input::KeyCode::Key8 };
+    ("9") => { $crate::input::KeyCode::Key9 };
+    ("0") => { $crate::input::KeyCode::Key0 };
 
-    ("a") => { framework::input::KeyCode::A };
-    ("b") => { framework::input::KeyCode::B };
-    ("c") => { framework::input::KeyCode::C };
-    ("d") => { framework::input::KeyCode::D };
-    ("e") => { framework::input::KeyCode::E };
-    ("f") => { framework::input::KeyCode::F };
-    ("g") => { framework::input::KeyCode::G };
-    ("h") => { framework::input::KeyCode::H };
-    ("i") => { framework::input::KeyCode::I };
-    ("j") => { framework::input::KeyCode::J };
-    ("k") => { framework::input::KeyCode::K };
-    ("l") => { framework::input::KeyCode::L };
-    ("m") => { framework::input::KeyCode::M };
-    ("n") => { framework::input::KeyCode::N };
-    ("o") => { framework::input::KeyCode::O };
-    ("p") => { framework::input::KeyCode::P };
-    ("q") => { framework::input::KeyCode::Q };
-    ("r") => { framework::input::KeyCode::R };
-    ("s") => { framework::input::KeyCode::S };
-    ("t") => { framework::input::KeyCode::T };
-    ("u") => { framework::input::KeyCode::U };
-    ("v") => { framework::input::KeyCode::V };
-    ("w") => { framework::input::KeyCode::W };
-    ("x") => { framework::input::KeyCode::X };
-    ("y") => { framework::input::KeyCode::Y };
-    ("z") => { framework::input::KeyCode::Z };
+    ("a") => { $crate::input::KeyCode::A };
+    ("b") => { $crate::input::KeyCode::B };
+    ("c") => { $crate::input::KeyCode::C };
+    ("d") => { $crate::input::KeyCode::D };
+    ("e") => { $crate::input::KeyCode::E };
+    ("f") => { $crate::input::KeyCode::F };
+    ("g") => { $crate::input::KeyCode::G };
+    ("h") => { $crate::input::KeyCode::H };
+    ("i") => { $crate::input::KeyCode::I };
+    ("j") => { $crate::input::KeyCode::J };
+    ("k") => { $crate::input::KeyCode::K };
+    ("l") => { $crate::input::KeyCode::L };
+    ("m") => { $crate::input::KeyCode::M };
+    ("n") => { $crate::input::KeyCode::N };
+    ("o") => { $crate::input::KeyCode::O };
+    ("p") => { $crate::input::KeyCode::P };
+    ("q") => { $crate::input::KeyCode::Q };
+    ("r") => { $crate::input::KeyCode::R };
+    ("s") => { $crate::input::KeyCode::S };
+    ("t") => { $crate::input::KeyCode::T };
+    ("u") => { $crate::input::KeyCode::U };
+    ("v") => { $crate::input::KeyCode::V };
+    ("w") => { $crate::input::KeyCode::W };
+    ("x") => { $crate::input::KeyCode::X };
+    ("y") => { $crate::input::KeyCode::Y };
+    ("z") => { $crate::input::KeyCode::Z };
 
-    ("A") => { framework::input::KeyCode::A };
-    ("B") => { framework::input::KeyCode::B };
-    ("C") => { framework::input::KeyCode::C };
-    ("D") => { framework::input::KeyCode::D };
-    ("E") => { framework::input::KeyCode::E };
-    ("F") => { framework::input::KeyCode::F };
-    ("G") => { framework::input::KeyCode::G };
-    ("H") => { framework::input::KeyCode::H };
-    ("I") => { framework::input::KeyCode::I };
-    ("J") => { framework::input::KeyCode::J };
-    ("K") => { framework::input::KeyCode::K };
-    ("L") => { framework::input::KeyCode::L };
-    ("M") => { framework::input::KeyCode::M };
-    ("N") => { framework::input::KeyCode::N };
-    ("O") => { framework::input::KeyCode::O };
-    ("P") => { framework::input::KeyCode::P };
-    ("Q") => { framework::input::KeyCode::Q };
-    ("R") => { framework::input::KeyCode::R };
-    ("S") => { framework::input::KeyCode::S };
-    ("T") => { framework::input::KeyCode::T };
-    ("U") => { framework::input::KeyCode::U };
-    ("V") => { framework::input::KeyCode::V };
-    ("W") => { framework::input::KeyCode::W };
-    ("X") => { framework::input::KeyCode::X };
-    ("Y") => { framework::input::KeyCode::Y };
-    ("Z") => { framework::input::KeyCode::Z };
+    ("A") => { $crate::input::KeyCode::A };
+    ("B") => { $crate::input::KeyCode::B };
+    ("C") => { $crate::input::KeyCode::C };
+    ("D") => { $crate::input::KeyCode::D };
+    ("E") => { $crate::input::KeyCode::E };
+    ("F") => { $crate::input::KeyCode::F };
+    ("G") => { $crate::input::KeyCode::G };
+    ("H") => { $crate::input::KeyCode::H };
+    ("I") => { $crate::input::KeyCode::I };
+    ("J") => { $crate::input::KeyCode::J };
+    ("K") => { $crate::input::KeyCode::K };
+    ("L") => { $crate::input::KeyCode::L };
+    ("M") => { $crate::input::KeyCode::M };
+    ("N") => { $crate::input::KeyCode::N };
+    ("O") => { $crate::input::KeyCode::O };
+    ("P") => { $crate::input::KeyCode::P };
+    ("Q") => { $crate::input::KeyCode::Q };
+    ("R") => { $crate::input::KeyCode::R };
+    ("S") => { $crate::input::KeyCode::S };
+    ("T") => { $crate::input::KeyCode::T };
+    ("U") => { $crate::input::KeyCode::U };
+    ("V") => { $crate::input::KeyCode::V };
+    ("W") => { $crate::input::KeyCode::W };
+    ("X") => { $crate::input::KeyCode::X };
+    ("Y") => { $crate::input::KeyCode::Y };
+    ("Z") => { $crate::input::KeyCode::Z };
 
-    ("esc") => { framework::input::KeyCode::Escape };
-    ("ESC") => { framework::input::KeyCode::Escape };
+    ("esc") => { $crate::input::KeyCode::Escape };
+    ("ESC") => { $crate::input::KeyCode::Escape };
 
-    ("f1")  => { framework::input::KeyCode::F1 };
-    ("f2")  => { framework::input::KeyCode::F2 };
-    ("f3")  => { framework::input::KeyCode::F3 };
-    ("f4")  => { framework::input::KeyCode::F4 };
-    ("f5")  => { framework::input::KeyCode::F5 };
-    ("f6")  => { framework::input::KeyCode::F6 };
-    ("f7")  => { framework::input::KeyCode::F7 };
-    ("f8")  => { framework::input::KeyCode::F8 };
-    ("f9")  => { framework::input::KeyCode::F9 };
-    ("f10") => { framework::input::KeyCode::F10 };
-    ("f11") => { framework::input::KeyCode::F11 };
-    ("f12") => { framework::input::KeyCode::F12 };
-    ("f13") => { framework::input::KeyCode::F13 };
-    ("f14") => { framework::input::KeyCode::F14 };
-    ("f15") => { framework::input::KeyCode::F15 };
-    ("f16") => { framework::input::KeyCode::F16 };
-    ("f17") => { framework::input::KeyCode::F17 };
-    ("f18") => { framework::input::KeyCode::F18 };
-    ("f19") => { framework::input::KeyCode::F19 };
-    ("f20") => { framework::input::KeyCode::F20 };
-    ("f21") => { framework::input::KeyCode::F21 };
-    ("f22") => { framework::input::KeyCode::F22 };
-    ("f23") => { framework::input::KeyCode::F23 };
-    ("f24") => { framework::input::KeyCode::F24 };
-    ("F1")  => { framework::input::KeyCode::F1 };
-    ("F2")  => { framework::input::KeyCode::F2 };
-    ("F3")  => { framework::input::KeyCode::F3 };
-    ("F4")  => { framework::input::KeyCode::F4 };
-    ("F5")  => { framework::input::KeyCode::F5 };
-    ("F6")  => { framework::input::KeyCode::F6 };
-    ("F7")  => { framework::input::KeyCode::F7 };
-    ("F8")  => { framework::input::KeyCode::F8 };
-    ("F9")  => { framework::input::KeyCode::F9 };
-    ("F10") => { framework::input::KeyCode::F10 };
-    ("F11") => { framework::input::KeyCode::F11 };
-    ("F12") => { framework::input::KeyCode::F12 };
-    ("F13") => { framework::input::KeyCode::F13 };
-    ("F14") => { framework::input::KeyCode::F14 };
-    ("F15") => { framework::input::KeyCode::F15 };
-    ("F16") => { framework::input::KeyCode::F16 };
-    ("F17") => { framework::input::KeyCode::F17 };
-    ("F18") => { framework::input::KeyCode::F18 };
-    ("F19") => { framework::input::KeyCode::F19 };
-    ("F20") => { framework::input::KeyCode::F20 };
-    ("F21") => { framework::input::KeyCode::F21 };
-    ("F22") => { framework::input::KeyCode::F22 };
-    ("F23") => { framework::input::KeyCode::F23 };
-    ("F24") => { framework::input::KeyCode::F24 };
+    ("f1")  => { $crate::input::KeyCode::F1 };
+    ("f2")  => { $crate::input::KeyCode::F2 };
+    ("f3")  => { $crate::input::KeyCode::F3 };
+    ("f4")  => { $crate::input::KeyCode::F4 };
+    ("f5")  => { $crate::input::KeyCode::F5 };
+    ("f6")  => { $crate::input::KeyCode::F6 };
+    ("f7")  => { $crate::input::KeyCode::F7 };
+    ("f8")  => { $crate::input::KeyCode::F8 };
+    ("f9")  => { $crate::input::KeyCode::F9 };
+    ("f10") => { $crate::input::KeyCode::F10 };
+    ("f11") => { $crate::input::KeyCode::F11 };
+    ("f12") => { $crate::input::KeyCode::F12 };
+    ("f13") => { $crate::input::KeyCode::F13 };
+    ("f14") => { $crate::input::KeyCode::F14 };
+    ("f15") => { $crate::input::KeyCode::F15 };
+    ("f16") => { $crate::input::KeyCode::F16 };
+    ("f17") => { $crate::input::KeyCode::F17 };
+    ("f18") => { $crate::input::KeyCode::F18 };
+    ("f19") => { $crate::input::KeyCode::F19 };
+    ("f20") => { $crate::input::KeyCode::F20 };
+    ("f21") => { $crate::input::KeyCode::F21 };
+    ("f22") => { $crate::input::KeyCode::F22 };
+    ("f23") => { $crate::input::KeyCode::F23 };
+    ("f24") => { $crate::input::KeyCode::F24 };
+    ("F1")  => { $crate::input::KeyCode::F1 };
+    ("F2")  => { $crate::input::KeyCode::F2 };
+    ("F3")  => { $crate::input::KeyCode::F3 };
+    ("F4")  => { $crate::input::KeyCode::F4 };
+    ("F5")  => { $crate::input::KeyCode::F5 };
+    ("F6")  => { $crate::input::KeyCode::F6 };
+    ("F7")  => { $crate::input::KeyCode::F7 };
+    ("F8")  => { $crate::input::KeyCode::F8 };
+    ("F9")  => { $crate::input::KeyCode::F9 };
+    ("F10") => { $crate::input::KeyCode::F10 };
+    ("F11") => { $crate::input::KeyCode::F11 };
+    ("F12") => { $crate::input::KeyCode::F12 };
+    ("F13") => { $crate::input::KeyCode::F13 };
+    ("F14") => { $crate::input::KeyCode::F14 };
+    ("F15") => { $crate::input::KeyCode::F15 };
+    ("F16") => { $crate::input::KeyCode::F16 };
+    ("F17") => { $crate::input::KeyCode::F17 };
+    ("F18") => { $crate::input::KeyCode::F18 };
+    ("F19") => { $crate::input::KeyCode::F19 };
+    ("F20") => { $crate::input::KeyCode::F20 };
+    ("F21") => { $crate::input::KeyCode::F21 };
+    ("F22") => { $crate::input::KeyCode::F22 };
+    ("F23") => { $crate::input::KeyCode::F23 };
+    ("F24") => { $crate::input::KeyCode::F24 };
 
-    ("left")  => { framework::input::KeyCode::Left  };
-    ("up")    => { framework::input::KeyCode::Up    };
-    ("right") => { framework::input::KeyCode::Right };
-    ("down")  => { framework::input::KeyCode::Down  };
+    ("left")  => { $crate::input::KeyCode::Left  };
+    ("up")    => { $crate::input::KeyCode::Up    };
+    ("right") => { $crate::input::KeyCode::Right };
+    ("down")  => { $crate::input::KeyCode::Down  };
 
-    ("back")      => { framework::input::KeyCode::Back };
-    ("backspace") => { framework::input::KeyCode::Back };
-    ("delete")    => { framework::input::KeyCode::Back };
+    ("back")      => { $crate::input::KeyCode::Back };
+    ("backspace") => { $crate::input::KeyCode::Back };
+    ("delete")    => { $crate::input::KeyCode::Back };
 
-    ("return") => { framework::input::KeyCode::Return };
-    ("enter")  => { framework::input::KeyCode::Return };
-    ("\n)")    => { framework::input::KeyCode::Return };
+    ("return") => { $crate::input::KeyCode::Return };
+    ("enter")  => { $crate::input::KeyCode::Return };
+    ("\n)")    => { $crate::input::KeyCode::Return };
     
-    (" ")  => { framework::input::KeyCode::Space };
-    ("'")  => { framework::input::KeyCode::Apostrophe };
-    ("*")  => { framework::input::KeyCode::Asterisk };
-    ("\\") => { framework::input::KeyCode::Backslash };
-    (":")  => { framework::input::KeyCode::Colon };
-    (",")  => { framework::input::KeyCode::Comma };
-    ("=")  => { framework::input::KeyCode::Equals };
-    ("[")  => { framework::input::KeyCode::LBracket };
-    ("]")  => { framework::input::KeyCode::RBracket };
-    ("-")  => { framework::input::KeyCode::Minus };
-    (".")  => { framework::input::KeyCode::Period };
-    ("+")  => { framework::input::KeyCode::Plus };
-    (";")  => { framework::input::KeyCode::Semicolon };
-    ("/")  => { framework::input::KeyCode::Slash };
+    (" ")  => { $crate::input::KeyCode::Space };
+    ("'")  => { $crate::input::KeyCode::Apostrophe };
+    ("*")  => { $crate::input::KeyCode::Asterisk };
+    ("\\") => { $crate::input::KeyCode::Backslash };
+    (":")  => { $crate::input::KeyCode::Colon };
+    (",")  => { $crate::input::KeyCode::Comma };
+    ("=")  => { $crate::input::KeyCode::Equals };
+    ("[")  => { $crate::input::KeyCode::LBracket };
+    ("]")  => { $crate::input::KeyCode::RBracket };
+    ("-")  => { $crate::input::KeyCode::Minus };
+    (".")  => { $crate::input::KeyCode::Period };
+    ("+")  => { $crate::input::KeyCode::Plus };
+    (";")  => { $crate::input::KeyCode::Semicolon };
+    ("/")  => { $crate::input::KeyCode::Slash };
 
-    ("    ") => { framework::input::KeyCode::Tab };
-    ("\t")   => { framework::input::KeyCode::Tab };
+    ("    ") => { $crate::input::KeyCode::Tab };
+    ("\t")   => { $crate::input::KeyCode::Tab };
 }
\ No newline at end of file
@@ -2,10 +2,14 @@ mod btn;
 mod mouse;
 mod keys;
 mod time;
+mod timer;
+mod stats;
 
 pub use self::mouse::{ Mouse, MouseButton };
 pub use self::keys::{ Keys, KeyCode };
 pub use self::time::Time;
+pub use self::timer::{ Timers, TimerId };
+pub use self::stats::{ FrameStats, Phase, Summary };
 
 use crate::app::Windows;
 
@@ -28,6 +32,9 @@ pub struct Input
     /// indicating that a frame has passed and inputs haven't
     /// been missed
     pub(super) reset: bool,
+    /// has any window event arrived since this was last
+    /// cleared? used to wake sketches that aren't looping
+    pub(crate) received: bool,
 }
 
 /// enumeration to cache the state of input keys and buttons
@@ -72,9 +79,18 @@ impl Input
             keys: Keys::new(),
 
             reset: false,
+            received: false,
         }
     }
 
+    /// reset the mouse and keyboard right away, rather than on
+    /// the next frame
+    pub(crate) fn consume(&mut self)
+    {
+        self.mouse.reset();
+        self.keys.reset();
+    }
+
     /// process a raw incoming winit event
     pub(crate) fn process(&mut self, event: &Event<()>, windows: &mut Windows) -> ProcessedEvent
     {
@@ -103,6 +119,8 @@ impl Input
                 // its mouse/keyboard state
                 if let Some(window) = windows.get_mut(&window_id)
                 {
+                    self.received = true;
+
                     // process mouse and keyboard
                     self.mouse.process(&evt, window);
                     self.keys.process(&evt);
@@ -70,14 +70,8 @@ impl Mouse
         {
             WindowEvent::CursorMoved { position, .. } =>
             {
-                // raw physical position
-                let pos = (position.x as f32, position.y as f32);
-                // clip to canvas
-                let (x, y) = window.pixels
-                    .window_pos_to_pixel(pos)
-                    .unwrap_or_else(|pos| window.pixels.clamp_pixel_pos(pos));
-                // convert
-                let pos = Vec2::new(x as i32, y as i32);
+                // raw physical position, clipped to canvas
+                let pos = window.window_to_canvas((position.x as f32, position.y as f32));
 
                 self.del = pos - self.pos;
                 self.pos = pos;
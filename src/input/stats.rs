@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// number of frames kept in `FrameStats`
+const HISTORY: usize = 240;
+
+/// what a frame spends its time on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase
+{
+    /// every `Sketch::update` call of the frame
+    Update,
+    /// `Sketch::draw`, for every canvas
+    Draw,
+    /// `Sketch::gui`, for every canvas
+    Gui,
+    /// rendering the canvases and GUI to their windows
+    Present,
+    /// the whole frame, from the start of one to the next,
+    /// including time spent sleeping
+    Frame,
+}
+
+/// timing statistics of the last few hundred frames, split by
+/// `Phase`. see `App::stats`
+/// ```
+/// let draw = app.stats().summary(Phase::Draw);
+/// println!("draw: {:?} on average, {:?} at worst", draw.mean, draw.max);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameStats
+{
+    /// durations of each phase, oldest frame first
+    history: VecDeque<[Duration; 5]>,
+    /// durations of each phase in the current frame so far
+    current: [Duration; 5],
+}
+
+/// statistics of a single phase over the recorded frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary
+{
+    /// shortest
+    pub min: Duration,
+    /// longest
+    pub max: Duration,
+    /// average
+    pub mean: Duration,
+    /// median
+    pub p50: Duration,
+    /// 95th percentile, ie. 1 frame in 20 is slower
+    pub p95: Duration,
+    /// 99th percentile, ie. 1 frame in 100 is slower
+    pub p99: Duration,
+}
+
+impl FrameStats
+{
+    /// create empty stats
+    pub(crate) fn new() -> Self
+    {
+        Self
+        {
+            history: VecDeque::with_capacity(HISTORY),
+            current: Default::default(),
+        }
+    }
+
+    /// add time spent on a phase of the current frame
+    pub(crate) fn add(&mut self, phase: Phase, duration: Duration)
+    {
+        self.current[phase as usize] += duration;
+    }
+
+    /// end the current frame, which took `total` in all, and
+    /// start recording the next
+    pub(crate) fn end_frame(&mut self, total: Duration)
+    {
+        let mut frame = std::mem::take(&mut self.current);
+        frame[Phase::Frame as usize] = total;
+
+        if self.history.len() == HISTORY
+        {
+            self.history.pop_front();
+        }
+        self.history.push_back(frame);
+    }
+
+    /// number of frames recorded
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.history.len()
+    }
+
+    /// have no frames been recorded yet?
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.history.is_empty()
+    }
+
+    /// time spent on a phase in the last recorded frame
+    pub fn last(&self, phase: Phase) -> Duration
+    {
+        self.history.back().map_or(Duration::default(), |frame| frame[phase as usize])
+    }
+
+    /// time spent on a phase in each recorded frame, oldest first
+    pub fn history(&self, phase: Phase) -> impl Iterator<Item = Duration> + '_
+    {
+        self.history.iter().map(move |frame| frame[phase as usize])
+    }
+
+    /// the time a phase takes in `p` percent of the recorded frames
+    /// or less, ie. `percentile(Phase::Frame, 99.0)`
+    pub fn percentile(&self, phase: Phase, p: f32) -> Duration
+    {
+        let mut sorted = self.history(phase).collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        percentile(&sorted, p)
+    }
+
+    /// min, max, mean and percentiles of a phase over the recorded
+    /// frames
+    pub fn summary(&self, phase: Phase) -> Summary
+    {
+        let mut sorted = self.history(phase).collect::<Vec<_>>();
+        if sorted.is_empty()
+        {
+            return Summary::default();
+        }
+        sorted.sort_unstable();
+
+        Summary
+        {
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            p50: percentile(&sorted, 50.0),
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+        }
+    }
+}
+
+/// nearest-rank percentile of sorted durations
+fn percentile(sorted: &[Duration], p: f32) -> Duration
+{
+    if sorted.is_empty()
+    {
+        return Duration::default();
+    }
+    let rank = (p.max(0.0).min(100.0) / 100.0 * sorted.len() as f32).ceil() as usize;
+
+    sorted[rank.max(1) - 1]
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn ms(ms: u64) -> Duration
+    {
+        Duration::from_millis(ms)
+    }
+
+    /// stats of 100 frames drawing in 1ms to 100ms, out of order
+    fn window() -> FrameStats
+    {
+        let mut stats = FrameStats::new();
+        for i in 0..100
+        {
+            stats.add(Phase::Draw, ms((i * 37) % 100 + 1));
+            stats.end_frame(ms(200));
+        }
+        stats
+    }
+
+    #[test]
+    fn summary()
+    {
+        let stats = window();
+
+        assert_eq!(stats.len(), 100);
+        assert_eq!(stats.summary(Phase::Draw), Summary
+        {
+            min: ms(1),
+            max: ms(100),
+            mean: Duration::from_micros(50_500),
+            p50: ms(50),
+            p95: ms(95),
+            p99: ms(99),
+        });
+        assert_eq!(stats.percentile(Phase::Draw, 0.0), ms(1));
+        assert_eq!(stats.percentile(Phase::Draw, 100.0), ms(100));
+        assert_eq!(stats.percentile(Phase::Draw, 250.0), ms(100));
+    }
+
+    #[test]
+    fn empty()
+    {
+        let stats = FrameStats::new();
+
+        assert!(stats.is_empty());
+        assert_eq!(stats.last(Phase::Frame), Duration::default());
+        assert_eq!(stats.percentile(Phase::Frame, 50.0), Duration::default());
+        assert_eq!(stats.summary(Phase::Frame), Summary::default());
+    }
+
+    #[test]
+    fn single_frame()
+    {
+        let mut stats = FrameStats::new();
+        stats.end_frame(ms(16));
+
+        let frame = stats.summary(Phase::Frame);
+        for d in [frame.min, frame.max, frame.mean, frame.p50, frame.p95, frame.p99]
+        {
+            assert_eq!(d, ms(16));
+        }
+        assert_eq!(stats.percentile(Phase::Frame, 1.0), ms(16));
+        // phases with nothing added took no time
+        assert_eq!(stats.summary(Phase::Gui), Summary::default());
+    }
+
+    #[test]
+    fn phases()
+    {
+        let mut stats = FrameStats::new();
+        stats.add(Phase::Update, ms(1));
+        stats.add(Phase::Update, ms(2));
+        stats.add(Phase::Frame, ms(5));
+        stats.end_frame(ms(16));
+
+        assert_eq!(stats.last(Phase::Update), ms(3));
+        assert_eq!(stats.last(Phase::Frame), ms(16));
+
+        // the next frame starts empty
+        stats.end_frame(ms(17));
+        assert_eq!(stats.last(Phase::Update), Duration::default());
+        assert_eq!(stats.history(Phase::Frame).collect::<Vec<_>>(), [ms(16), ms(17)]);
+    }
+
+    #[test]
+    fn history_limit()
+    {
+        let mut stats = FrameStats::new();
+        for i in 0..HISTORY as u64 + 10
+        {
+            stats.end_frame(ms(i));
+        }
+
+        assert_eq!(stats.len(), HISTORY);
+        assert_eq!(stats.history(Phase::Frame).next(), Some(ms(10)));
+        assert_eq!(stats.last(Phase::Frame), ms(HISTORY as u64 + 9));
+    }
+}
@@ -1,7 +1,11 @@
 use std::time::*;
 
+use crate::input::KeyCode;
+
 /// number of samples used to determine Time::fps()
 const FPS_SAMPLE_SIZE: u64 = 200;
+/// default maximum number of fixed updates per frame
+const MAX_FIXED_STEPS: u32 = 8;
 
 /// represents a snapshot of time in the app
 #[derive(Debug, Clone)]
@@ -9,18 +13,48 @@ pub struct Time
 {
     /// frame rate artificial limit, if any
     pub limit: Option<Duration>,
-    /// delta time between the beginnings of the last frame and
-    /// of this frame
+    /// simulated delta time of the last update, scaled and zero
+    /// while paused
     delta: Duration,
-    /// total time since the app has started
+    /// total simulated time since the app has started
     total: Duration,
+    /// real delta time between the beginnings of the last frame
+    /// and of this frame
+    real_delta: Duration,
+    /// total real time since the app has started
+    real_total: Duration,
+    /// how fast simulated time passes compared to real time
+    scale: f32,
+    /// is simulated time frozen?
+    paused: bool,
+    /// single updates left to run while paused
+    steps: u32,
+    /// are the debugging hotkeys enabled?
+    hotkeys: bool,
     /// start time of this frame
     start: Instant,
-    /// current tick, where each frame is 1 tick and the first
+    /// current tick, where each update is 1 tick and the first
     /// update call is tick 1
     tick: u64,
+    /// number of frames so far
+    frame: u64,
     /// (sums of delta time in the current fps sample, fps)
     fps: (f32, f32),
+    /// fixed timestep state, if enabled
+    fixed: Option<FixedStep>,
+}
+
+/// state of a fixed timestep
+#[derive(Debug, Clone)]
+struct FixedStep
+{
+    /// simulated time per update
+    step: Duration,
+    /// scaled time not yet simulated
+    accumulator: Duration,
+    /// most updates in a single frame before giving up on
+    /// catching up
+    max_steps: u32,
 }
 
 impl Time
@@ -35,55 +69,128 @@ impl Time
 
             delta: Duration::default(),
             total: Duration::default(),
+            real_delta: Duration::default(),
+            real_total: Duration::default(),
+            scale: 1.0,
+            paused: false,
+            steps: 0,
+            hotkeys: false,
             start: Instant::now(),
 
             tick: 0,
+            frame: 0,
             fps: (0.0, 0.0),
+            fixed: None,
         }
     }
 
-    /// updates this instance of Time and returns whether
-    /// update should be called
-    pub(crate) fn update(&mut self) -> bool
+    /// when the next frame should start, if there's an artificial
+    /// frame rate limit
+    pub(crate) fn next_frame(&self) -> Option<Instant>
     {
-        // time since last frame
-        self.delta = self.start.elapsed();
+        self.limit.map(|limit| self.start + limit)
+    }
 
-        // has artificial frame rate limit?
-        if let Some(limit) = self.limit
-        {
-            // update not ready to be called
-            if self.delta < limit
-            {
-                return false;
-            }
-        }
+    /// start a new frame, updating this instance of Time, and
+    /// return how many times update should be called this frame.
+    /// the frame rate limit is up to the caller, see `next_frame`
+    pub(crate) fn update(&mut self) -> u32
+    {
+        // time since last frame
+        let real = self.start.elapsed();
 
-        // update
-        self.tick += 1;
-        self.total += self.delta;
         self.start = Instant::now();
+        self.advance(real)
+    }
+
+    /// start a new frame `real` after the last one. see `update`
+    fn advance(&mut self, real: Duration) -> u32
+    {
+        // new frame
+        self.frame += 1;
+        self.real_delta = real;
+        self.real_total += real;
 
         // fps monitoring
-        self.fps.0 += self.dt();
+        self.fps.0 += real.as_secs_f32();
         if self.fps_was_updated()
         {
             self.fps.1 = FPS_SAMPLE_SIZE as f32 / self.fps.0;
             self.fps.0 = 0.0;
         }
 
-        return true;
+        // frozen, unless single-stepping
+        let stepping = self.paused && self.steps > 0;
+        if self.paused && !stepping
+        {
+            self.delta = Duration::default();
+            return 0;
+        }
+        if stepping
+        {
+            self.steps -= 1;
+        }
+
+        // in f64, so a scale of 1.0 is exactly real time
+        let elapsed = real.mul_f64(self.scale as f64);
+        let fixed = match &mut self.fixed
+        {
+            // single step: exactly one update
+            Some(fixed) if stepping =>
+            {
+                self.tick += 1;
+                self.delta = fixed.step;
+                self.total += fixed.step;
+
+                return 1;
+            }
+            Some(fixed) => fixed,
+            // variable timestep: update once with the scaled delta
+            None =>
+            {
+                self.tick += 1;
+                self.delta = elapsed;
+                self.total += elapsed;
+
+                return 1;
+            }
+        };
+
+        // simulate as many whole steps as fit in the real time
+        fixed.accumulator += elapsed;
+
+        let mut steps = 0;
+        while fixed.accumulator >= fixed.step && steps < fixed.max_steps
+        {
+            fixed.accumulator -= fixed.step;
+            steps += 1;
+        }
+        // too far behind to ever catch up, ie. after a breakpoint
+        // or a long frame, so drop the time that's left rather than
+        // spiraling into ever longer frames
+        if fixed.accumulator >= fixed.step
+        {
+            fixed.accumulator = Duration::default();
+        }
+
+        self.tick += steps as u64;
+        self.delta = fixed.step;
+        self.total += fixed.step * steps;
+
+        steps
     }
 
     /// delta time between the beginnings of the last frame and
-    /// of this frame    
+    /// of this frame, scaled by `Time::scale()`, or the step size
+    /// with a fixed timestep. zero while paused
     pub fn delta(&self) -> Duration
     {
         self.delta
     }
 
     /// delta seconds between the beginnings of the last frame and
-    /// of this frame
+    /// of this frame, scaled by `Time::scale()`, or the step size
+    /// with a fixed timestep. zero while paused
     ///
     /// this is the exact same as doing:
     /// ```
@@ -94,19 +201,41 @@ impl Time
         self.delta.as_secs_f32()
     }
 
-    /// total time since the app has started
+    /// total simulated time since the app has started, which
+    /// is scaled and doesn't pass while paused
     pub fn elapsed(&self) -> Duration
     {
         self.total
     }
 
+    /// real, unscaled time between the beginnings of the last
+    /// frame and of this frame, even while paused
+    pub fn real_delta(&self) -> Duration
+    {
+        self.real_delta
+    }
+
+    /// real, unscaled seconds between the beginnings of the last
+    /// frame and of this frame, even while paused
+    pub fn real_dt(&self) -> f32
+    {
+        self.real_delta.as_secs_f32()
+    }
+
+    /// total real time since the app has started, even while
+    /// paused
+    pub fn real_elapsed(&self) -> Duration
+    {
+        self.real_total
+    }
+
     /// start time of this frame
     pub fn start(&self) -> Instant
     {
         self.start
     }
 
-    /// current tick, where each frame is 1 tick and the first
+    /// current tick, where each update is 1 tick and the first
     /// update call is tick 1
     pub fn tick(&self) -> u64
     {
@@ -116,7 +245,8 @@ impl Time
     /// sets the target frame rate. note that this rate may not
     /// be reached(if the hardware isn't powerful enough) and is
     /// likely to be innacurate. Use `Time::delta()` for time
-    /// sensitive cases
+    /// sensitive cases. between frames, the app sleeps rather
+    /// than spinning
     ///
     /// this is a shorthand for:
     /// ```
@@ -152,6 +282,303 @@ impl Time
     /// was it calculated this frame?
     fn fps_was_updated(&self) -> bool
     {
-        self.tick % FPS_SAMPLE_SIZE == 0
+        self.frame % FPS_SAMPLE_SIZE == 0
     }
-}
\ No newline at end of file
+
+    /// switch to a fixed timestep of `secs` seconds, so `update` runs
+    /// zero or more times per frame and always sees the same delta,
+    /// no matter the frame rate. ideal for physics:
+    /// ```
+    /// app.time().fixed_step(1.0 / 60.0);
+    /// ```
+    /// use `Time::alpha()` in `Sketch::pre_draw` to interpolate
+    /// between updates, and `Time::max_steps` for what happens when
+    /// frames are too slow. panics unless `secs` is positive and
+    /// finite
+    pub fn fixed_step(&mut self, secs: f32)
+    {
+        assert!(secs > 0.0 && secs.is_finite(), "fixed timestep must be a positive, finite number of seconds, got {}", secs);
+
+        self.fixed = Some(FixedStep
+        {
+            step: Duration::from_secs_f32(secs),
+            accumulator: Duration::default(),
+            max_steps: MAX_FIXED_STEPS,
+        });
+    }
+
+    /// switch back to a variable timestep, where `update` runs once
+    /// per frame with the real delta. this is the default
+    pub fn variable_step(&mut self)
+    {
+        self.fixed = None;
+    }
+
+    /// is the timestep fixed?
+    pub fn is_fixed_step(&self) -> bool
+    {
+        self.fixed.is_some()
+    }
+
+    /// set the most updates a fixed timestep runs in a single frame,
+    /// after which the sketch slows down rather than trying to catch
+    /// up: once the limit is hit, the time that's left to simulate is
+    /// discarded rather than carried over to the next frame, so
+    /// simulated time falls behind real time. defaults to 8, no
+    /// effect with a variable timestep
+    pub fn max_steps(&mut self, n: u32)
+    {
+        if let Some(fixed) = &mut self.fixed
+        {
+            fixed.max_steps = n.max(1);
+        }
+    }
+
+    /// how far between the last update and the next one this frame
+    /// is, from 0.0 to 1.0, with a fixed timestep. interpolate with it
+    /// in `Sketch::pre_draw` for smooth motion:
+    /// ```
+    /// let pos = self.prev_pos + (self.pos - self.prev_pos) * time.alpha();
+    /// ```
+    /// always 1.0 with a variable timestep
+    pub fn alpha(&self) -> f32
+    {
+        match &self.fixed
+        {
+            Some(fixed) => fixed.accumulator.as_secs_f32() / fixed.step.as_secs_f32(),
+            None => 1.0,
+        }
+    }
+
+    /// how fast simulated time passes compared to real time,
+    /// where 1.0 is real time
+    pub fn scale(&self) -> f32
+    {
+        self.scale
+    }
+
+    /// set how fast simulated time passes compared to real time,
+    /// ie. 0.25 for slow-motion or 2.0 for double speed. negative
+    /// scales are treated as 0.0
+    pub fn set_scale(&mut self, scale: f32)
+    {
+        self.scale = scale.max(0.0);
+    }
+
+    /// freeze simulated time: `Sketch::update` stops being called,
+    /// though `Sketch::pre_draw` and `Sketch::draw` still are
+    pub fn pause(&mut self)
+    {
+        self.paused = true;
+    }
+
+    /// unfreeze simulated time after `Time::pause`, dropping any
+    /// single steps left
+    pub fn resume(&mut self)
+    {
+        self.paused = false;
+        self.steps = 0;
+    }
+
+    /// pause if running, or resume if paused
+    pub fn toggle_pause(&mut self)
+    {
+        match self.paused
+        {
+            true => self.resume(),
+            false => self.pause(),
+        }
+    }
+
+    /// is simulated time frozen?
+    pub fn is_paused(&self) -> bool
+    {
+        self.paused
+    }
+
+    /// pause, then run `n` more updates, one per frame, to step
+    /// through a simulation
+    pub fn step(&mut self, n: u32)
+    {
+        self.paused = true;
+        self.steps += n;
+    }
+
+    /// enable or disable the debugging hotkeys, off by default:
+    /// - F5 pauses or resumes
+    /// - F6 steps a single update
+    /// - F7 halves the time scale
+    /// - F8 doubles the time scale
+    pub fn hotkeys(&mut self, enabled: bool)
+    {
+        self.hotkeys = enabled;
+    }
+
+    /// react to a key press if it's a debugging hotkey
+    pub(crate) fn hotkey(&mut self, key: KeyCode)
+    {
+        if !self.hotkeys
+        {
+            return;
+        }
+        match key
+        {
+            KeyCode::F5 => self.toggle_pause(),
+            KeyCode::F6 => self.step(1),
+            KeyCode::F7 => self.set_scale(self.scale * 0.5),
+            KeyCode::F8 => self.set_scale(self.scale * 2.0),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn ms(n: u64) -> Duration
+    {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn fixed_step_invalid()
+    {
+        for &secs in &[0.0, -1.0 / 60.0, f32::NAN, f32::INFINITY]
+        {
+            assert!(std::panic::catch_unwind(|| Time::new().fixed_step(secs)).is_err(), "{} seconds", secs);
+        }
+    }
+
+    #[test]
+    fn variable_step()
+    {
+        let mut time = Time::new();
+
+        assert_eq!(time.advance(ms(40)), 1);
+        assert_eq!(time.advance(ms(60)), 1);
+        assert_eq!(time.delta(), ms(60));
+        assert_eq!(time.elapsed(), ms(100));
+        assert_eq!(time.tick(), 2);
+        assert_eq!(time.alpha(), 1.0);
+    }
+
+    #[test]
+    fn fixed_step_accumulates()
+    {
+        let mut time = Time::new();
+        time.fixed_step(0.25);
+
+        assert_eq!(time.advance(ms(1000)), 4);
+        assert_eq!(time.delta(), ms(250));
+        assert_eq!(time.elapsed(), ms(1000));
+        assert_eq!(time.alpha(), 0.0);
+
+        // not enough for a step, carried over
+        assert_eq!(time.advance(ms(100)), 0);
+        assert!((time.alpha() - 0.4).abs() < 1e-6);
+        assert_eq!(time.advance(ms(200)), 1);
+        assert!((time.alpha() - 0.2).abs() < 1e-6);
+        assert_eq!(time.elapsed(), ms(1250));
+        assert_eq!(time.tick(), 5);
+
+        time.variable_step();
+        assert_eq!(time.advance(ms(10)), 1);
+        assert_eq!(time.delta(), ms(10));
+    }
+
+    #[test]
+    fn fixed_step_max_steps()
+    {
+        let mut time = Time::new();
+        time.fixed_step(0.25);
+        time.max_steps(3);
+
+        // way behind: the leftover 9.25s are dropped
+        assert_eq!(time.advance(ms(10_000)), 3);
+        assert_eq!(time.elapsed(), ms(750));
+        assert_eq!(time.alpha(), 0.0);
+        assert_eq!(time.advance(ms(300)), 1);
+
+        // at least one step
+        time.max_steps(0);
+        assert_eq!(time.advance(ms(1000)), 1);
+    }
+
+    #[test]
+    fn scale()
+    {
+        let mut time = Time::new();
+
+        time.set_scale(0.5);
+        time.advance(ms(100));
+        assert_eq!(time.delta(), ms(50));
+        assert_eq!(time.elapsed(), ms(50));
+        assert_eq!(time.real_delta(), ms(100));
+        assert_eq!(time.real_elapsed(), ms(100));
+
+        time.fixed_step(0.25);
+        time.set_scale(2.0);
+        assert_eq!(time.advance(ms(500)), 4);
+
+        time.set_scale(-1.0);
+        assert_eq!(time.scale(), 0.0);
+        assert_eq!(time.advance(ms(1000)), 0);
+    }
+
+    #[test]
+    fn pause_and_step()
+    {
+        let mut time = Time::new();
+        time.fixed_step(0.25);
+
+        time.pause();
+        assert_eq!(time.advance(ms(1000)), 0);
+        assert_eq!(time.delta(), Duration::default());
+        assert_eq!(time.elapsed(), Duration::default());
+        assert_eq!(time.real_elapsed(), ms(1000));
+
+        // one update per frame, however long the frame
+        time.step(2);
+        assert_eq!(time.advance(ms(1000)), 1);
+        assert_eq!(time.advance(ms(10)), 1);
+        assert_eq!(time.delta(), ms(250));
+        assert_eq!(time.advance(ms(1000)), 0);
+        assert_eq!(time.elapsed(), ms(500));
+        assert!(time.is_paused());
+
+        // steps left are dropped
+        time.step(5);
+        time.toggle_pause();
+        assert!(!time.is_paused());
+        assert_eq!(time.advance(ms(250)), 1);
+        time.pause();
+        assert_eq!(time.advance(ms(250)), 0);
+    }
+
+    #[test]
+    fn hotkeys()
+    {
+        let mut time = Time::new();
+
+        // off by default
+        time.hotkey(KeyCode::F5);
+        assert!(!time.is_paused());
+
+        time.hotkeys(true);
+        time.hotkey(KeyCode::F5);
+        assert!(time.is_paused());
+        time.hotkey(KeyCode::F6);
+        assert_eq!(time.advance(ms(10)), 1);
+        assert_eq!(time.advance(ms(10)), 0);
+        time.hotkey(KeyCode::F5);
+        assert!(!time.is_paused());
+
+        time.hotkey(KeyCode::F7);
+        time.hotkey(KeyCode::F7);
+        assert_eq!(time.scale(), 0.25);
+        time.hotkey(KeyCode::F8);
+        assert_eq!(time.scale(), 0.5);
+    }
+}
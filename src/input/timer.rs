@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+/// identifies a timer started with `App::after` or `App::every`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+/// timers scheduled on the `App`, advanced by `Time::delta()` right
+/// before every update, so they follow the time scale and stop while
+/// paused. timers are polled rather than calling back:
+/// ```
+/// // setup
+/// let spawn = app.every(Duration::from_secs(3));
+///
+/// // update
+/// if app.fired(self.spawn)
+/// {
+///     self.spawn_enemy();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Timers
+{
+    /// next timer ID
+    next: u64,
+    /// timers currently running
+    timers: Vec<Timer>,
+}
+
+/// a single timer
+#[derive(Debug)]
+struct Timer
+{
+    /// this timer's ID
+    id: TimerId,
+    /// time left until it fires
+    remaining: Duration,
+    /// time between firings, for repeating timers
+    period: Option<Duration>,
+    /// number of times it fired during the current update
+    fired: u32,
+}
+
+impl Timers
+{
+    /// create an empty set of timers
+    pub(crate) fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// start a timer that fires once, `delay` from now
+    pub fn after(&mut self, delay: Duration) -> TimerId
+    {
+        self.start(delay, None)
+    }
+
+    /// start a timer that fires every `period`, starting `period`
+    /// from now, until it's cancelled. a zero period fires every
+    /// update
+    pub fn every(&mut self, period: Duration) -> TimerId
+    {
+        self.start(period, Some(period))
+    }
+
+    /// start a new timer
+    fn start(&mut self, remaining: Duration, period: Option<Duration>) -> TimerId
+    {
+        let id = TimerId(self.next);
+
+        self.next += 1;
+        self.timers.push(Timer { id, remaining, period, fired: 0 });
+
+        id
+    }
+
+    /// stop a timer. does nothing if it already stopped
+    pub fn cancel(&mut self, id: TimerId)
+    {
+        self.timers.retain(|timer| timer.id != id);
+    }
+
+    /// stop every timer
+    pub fn clear(&mut self)
+    {
+        self.timers.clear();
+    }
+
+    /// did the timer fire right before the current update?
+    pub fn fired(&self, id: TimerId) -> bool
+    {
+        self.fired_count(id) > 0
+    }
+
+    /// number of times the timer fired right before the current
+    /// update. a repeating timer can fire more than once if the
+    /// update's delta is longer than its period
+    pub fn fired_count(&self, id: TimerId) -> u32
+    {
+        self.get(id).map_or(0, |timer| timer.fired)
+    }
+
+    /// time left until the timer fires next, or `None` if it stopped
+    pub fn remaining(&self, id: TimerId) -> Option<Duration>
+    {
+        self.get(id)
+            .filter(|timer| timer.period.is_some() || timer.fired == 0)
+            .map(|timer| timer.remaining)
+    }
+
+    /// is the timer still going to fire?
+    pub fn is_active(&self, id: TimerId) -> bool
+    {
+        self.remaining(id).is_some()
+    }
+
+    /// get a timer from its ID
+    fn get(&self, id: TimerId) -> Option<&Timer>
+    {
+        self.timers.iter().find(|timer| timer.id == id)
+    }
+
+    /// advance every timer by `delta`, firing those that run out
+    pub(crate) fn advance(&mut self, delta: Duration)
+    {
+        // one-shot timers that fired on the last update are done
+        self.timers.retain(|timer| timer.period.is_some() || timer.fired == 0);
+
+        for timer in &mut self.timers
+        {
+            timer.fired = 0;
+
+            let mut left = delta;
+            loop
+            {
+                if timer.remaining > left
+                {
+                    timer.remaining -= left;
+                    break;
+                }
+                left -= timer.remaining;
+                timer.fired += 1;
+
+                match timer.period
+                {
+                    Some(period) if period > Duration::default() => timer.remaining = period,
+                    // one-shot, or firing every update
+                    _ =>
+                    {
+                        timer.remaining = Duration::default();
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn ms(ms: u64) -> Duration
+    {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn after()
+    {
+        let mut timers = Timers::new();
+        let id = timers.after(ms(100));
+
+        timers.advance(ms(60));
+        assert!(!timers.fired(id));
+        assert_eq!(timers.remaining(id), Some(ms(40)));
+
+        // fires once, even if the delta is much longer
+        timers.advance(ms(1000));
+        assert_eq!(timers.fired_count(id), 1);
+        assert!(!timers.is_active(id));
+
+        // then it's gone
+        timers.advance(ms(100));
+        assert!(!timers.fired(id));
+        assert_eq!(timers.remaining(id), None);
+    }
+
+    #[test]
+    fn every()
+    {
+        let mut timers = Timers::new();
+        let id = timers.every(ms(100));
+
+        timers.advance(ms(100));
+        assert_eq!(timers.fired_count(id), 1);
+        assert_eq!(timers.remaining(id), Some(ms(100)));
+
+        // a long delta fires it once per period, carrying the rest
+        timers.advance(ms(350));
+        assert_eq!(timers.fired_count(id), 3);
+        assert_eq!(timers.remaining(id), Some(ms(50)));
+
+        timers.advance(ms(10));
+        assert!(!timers.fired(id));
+        assert_eq!(timers.remaining(id), Some(ms(40)));
+        assert!(timers.is_active(id));
+    }
+
+    #[test]
+    fn zero_period()
+    {
+        let mut timers = Timers::new();
+        let id = timers.every(Duration::default());
+
+        for delta in [ms(0), ms(16), ms(1000)]
+        {
+            timers.advance(delta);
+            assert_eq!(timers.fired_count(id), 1);
+        }
+    }
+
+    #[test]
+    fn cancel()
+    {
+        let mut timers = Timers::new();
+        let a = timers.every(ms(100));
+        let b = timers.after(ms(100));
+
+        assert_ne!(a, b);
+        timers.cancel(a);
+        timers.advance(ms(100));
+
+        assert!(!timers.fired(a));
+        assert!(!timers.is_active(a));
+        assert!(timers.fired(b));
+
+        // cancelling twice is fine
+        timers.cancel(a);
+        timers.clear();
+        assert!(!timers.fired(b));
+    }
+}
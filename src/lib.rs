@@ -1,4 +1,4 @@
-#[cfg(feature = "audio")]
+#[cfg(feature = "sound")]
 pub mod sound;
 pub mod input;
 pub mod draw;
@@ -8,9 +8,15 @@ pub mod math;
 pub mod gui;
 
 mod sketch;
+mod error;
+mod assets;
 mod app;
 
 pub use crate::sketch::Sketch;
+pub use crate::error::{ Error, WindowError, Result };
+#[cfg(feature = "sound")]
+pub use crate::error::AudioError;
+pub use crate::assets::{ Assets, Asset, Handle, Pending, LoadContext };
 pub use crate::app::App;
 
 pub mod prelude
@@ -23,7 +29,17 @@ pub mod prelude
         Rgba,
         Extent2,
     };
-    #[cfg(feature = "audio")]
+    pub use crate::math::
+    {
+        Colour,
+        ColourMode,
+        ColourSpace,
+        Hsv,
+        Hsl,
+        Oklab,
+        Oklch,
+    };
+    #[cfg(feature = "sound")]
     pub use crate::sound::
     {
         Track,
@@ -33,8 +49,26 @@ pub mod prelude
         IndexedParallelIterator,
         ParallelIterator,
         Image,
+        AnimatedImage,
         Canvas,
         CanvasId,
+        Canvases,
+        ResizePolicy,
+        Paint,
+        Gradient,
+        Spread,
+        Pattern,
+        Sampling,
+        Mask,
+        Connectivity,
+        Palette,
+        Dither,
+        Diff,
+        SpriteSheet,
+        Animation,
+        Playback,
+        TileMap,
+        Font,
     };
     pub use crate::gui::
     {
@@ -45,36 +79,57 @@ pub mod prelude
     {
         MouseButton,
         KeyCode,
+        TimerId,
+        Phase,
+    };
+    pub use crate::util::
+    {
+        Tween,
+        Ease,
+        Random,
     };
     pub use crate::
     {
         run,
         Sketch,
         App,
+        Error,
+        Handle,
+        Pending,
     };
     // macros...
-    pub use crate::{ c, v, btn };
+    pub use crate::{ c, v, btn, assert_image_eq, include_image };
 
     // re-exports
     pub use rand::Rng;
 }
 
-// run the sketch, hyjacking the main thread until all the
-/// windows are closed
-pub fn run<T: sketch::Sketch>()
+/// run the sketch, hyjacking the main thread until all the
+/// windows are closed or something goes wrong, in which case
+/// every window is closed and the error is returned
+pub fn run<T: sketch::Sketch>() -> Result<()>
 {
+    use winit::platform::run_return::EventLoopExtRunReturn;
+    use winit::event_loop::ControlFlow;
+
     // create event loop
-    let events = winit::event_loop::EventLoop::new();
+    let mut events = winit::event_loop::EventLoop::new();
 
     // app & sketch
-    let mut app = app::App::new();
-    let mut sketch = T::setup(&mut app);
+    let mut app = app::App::new()?;
+    let mut sketch = T::setup(&mut app)?;
     
     // create initial windows
-    app.process_requests(&events);
+    app.process_requests::<T>(&events)?;
 
-    events.run(move |event, window_target, control_flow|
+    let mut result = Ok(());
+    events.run_return(|event, window_target, control_flow|
     {
-        app.process_event(&mut sketch, event, window_target, control_flow);
+        if let Err(e) = app.process_event(&mut sketch, event, window_target, control_flow)
+        {
+            result = Err(e);
+            *control_flow = ControlFlow::Exit;
+        }
     });
+    result
 }
\ No newline at end of file
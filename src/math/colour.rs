@@ -0,0 +1,766 @@
+use crate::math::{ Rgba, Vec3, ParseColourError };
+
+/// a colour in the HSV(hue, saturation, value) colour space, also
+/// known as HSB. `h` is in degrees [0, 360), while `s`, `v` and
+/// `a`(alpha) are in [0, 1]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Hsv
+{
+    /// hue, in degrees [0, 360)
+    pub h: f32,
+    /// saturation, [0, 1]
+    pub s: f32,
+    /// value(brightness), [0, 1]
+    pub v: f32,
+    /// alpha, [0, 1]
+    pub a: f32,
+}
+
+/// a colour in the HSL(hue, saturation, lightness) colour space.
+/// `h` is in degrees [0, 360), while `s`, `l` and `a`(alpha) are
+/// in [0, 1]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Hsl
+{
+    /// hue, in degrees [0, 360)
+    pub h: f32,
+    /// saturation, [0, 1]
+    pub s: f32,
+    /// lightness, [0, 1]
+    pub l: f32,
+    /// alpha, [0, 1]
+    pub a: f32,
+}
+
+/// a colour in the [OKLab] perceptual colour space. `l` is in
+/// [0, 1], while `a` and `b` are roughly in [-0.4, 0.4]
+///
+/// [OKLab]: https://bottosson.github.io/posts/oklab/
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Oklab
+{
+    /// perceived lightness, [0, 1]
+    pub l: f32,
+    /// green/red axis
+    pub a: f32,
+    /// blue/yellow axis
+    pub b: f32,
+    /// alpha, [0, 1]
+    pub alpha: f32,
+}
+
+/// a colour in the OKLCH colour space, which is the polar form
+/// of [Oklab]. `l` is in [0, 1], `c` is roughly in [0, 0.4] and
+/// `h` is in degrees [0, 360)
+///
+/// [Oklab]: self::Oklab
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Oklch
+{
+    /// perceived lightness, [0, 1]
+    pub l: f32,
+    /// chroma
+    pub c: f32,
+    /// hue, in degrees [0, 360)
+    pub h: f32,
+    /// alpha, [0, 1]
+    pub alpha: f32,
+}
+
+/// how colour components passed to [Bitmap::colour] are interpreted,
+/// similarly to p5's `colorMode()`
+///
+/// [Bitmap::colour]: crate::draw::Bitmap::colour
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColourMode
+{
+    /// red, green and blue in [0, 255]
+    Rgb,
+    /// hue in [0, 360), saturation and brightness in [0, 100]
+    Hsb,
+    /// hue in [0, 360), saturation and lightness in [0, 100]
+    Hsl,
+}
+
+/// colour space in which two colours are interpolated, see
+/// `ColourSpace::lerp`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColourSpace
+{
+    /// per-channel interpolation of the sRGB encoded components,
+    /// like most CSS gradients
+    Srgb,
+    /// per-channel interpolation in linear light, which avoids the
+    /// dark band between saturated colours
+    LinearSrgb,
+    /// interpolation of hue, saturation and lightness, taking the
+    /// shortest path around the hue circle
+    Hsl,
+    /// perceptually uniform interpolation
+    Oklab,
+    /// perceptually uniform interpolation of lightness, chroma and
+    /// hue, taking the shortest path around the hue circle
+    Oklch,
+}
+
+/// extension trait for `Rgba<u8>` with colour space conversions and
+/// colour math
+pub trait Colour: Sized
+{
+    /// parse a colour at runtime from a CSS string. supports:
+    /// - named colours, same as the `c!` macro(`"royalblue"`)
+    /// - hexadecimal(`"#1e90ff"`, `"#1e90ff80"`, `"#f0a"`)
+    /// - rgb(`"rgb(30, 144, 255)"`, `"rgba(30 144 255 / 50%)"`)
+    /// - hsl(`"hsl(210deg, 100%, 56%)"`, `"hsla(0.5turn, 80%, 40%, 0.5)"`)
+    /// ```
+    /// let a = Rgba::parse("#1e90ff")?;
+    /// let b = Rgba::parse("DodgerBlue")?;
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    /// note that `Rgba` can't implement `FromStr`, as neither it nor
+    /// the trait are defined in this crate
+    fn parse(s: &str) -> Result<Self, ParseColourError>;
+
+    /// convert this colour to the HSV(HSB) colour space
+    fn to_hsv(self) -> Hsv;
+
+    /// convert this colour to the HSL colour space
+    fn to_hsl(self) -> Hsl;
+
+    /// convert this colour from sRGB to linear sRGB, with
+    /// components in [0, 1]
+    fn to_linear(self) -> Rgba<f32>;
+
+    /// convert a linear sRGB colour, with components in [0, 1], to
+    /// this colour. components out of range are clamped
+    fn from_linear(col: Rgba<f32>) -> Self;
+
+    /// convert this colour to the OKLab colour space
+    fn to_oklab(self) -> Oklab;
+
+    /// convert this colour to the OKLCH colour space
+    fn to_oklch(self) -> Oklch;
+
+    /// perceptually interpolate between `self` and `other` in the
+    /// OKLab colour space, where `t` is in [0, 1]
+    fn lerp_oklab(self, other: Self, t: f32) -> Self;
+
+    /// increase the perceived lightness of this colour by `amt`, in
+    /// [0, 1]
+    fn lighten(self, amt: f32) -> Self;
+
+    /// decrease the perceived lightness of this colour by `amt`, in
+    /// [0, 1]
+    ///
+    /// this is the same as `col.lighten(-amt)`
+    fn darken(self, amt: f32) -> Self
+    {
+        self.lighten(-amt)
+    }
+
+    /// scale the chroma of this colour by `1.0 + amt`. negative
+    /// values of `amt` desaturate it, and `-1.0` makes it grey
+    fn saturate(self, amt: f32) -> Self;
+}
+
+impl Colour for Rgba<u8>
+{
+    #[inline]
+    fn parse(s: &str) -> Result<Self, ParseColourError>
+    {
+        super::parse::parse(s)
+    }
+
+    #[inline]
+    fn to_hsv(self) -> Hsv
+    {
+        self.into()
+    }
+
+    #[inline]
+    fn to_hsl(self) -> Hsl
+    {
+        self.into()
+    }
+
+    fn to_linear(self) -> Rgba<f32>
+    {
+        Rgba::new
+        (
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a as f32 / 255.0,
+        )
+    }
+
+    fn from_linear(col: Rgba<f32>) -> Self
+    {
+        Rgba::new
+        (
+            linear_to_srgb(col.r),
+            linear_to_srgb(col.g),
+            linear_to_srgb(col.b),
+            unit_to_u8(col.a),
+        )
+    }
+
+    #[inline]
+    fn to_oklab(self) -> Oklab
+    {
+        self.into()
+    }
+
+    #[inline]
+    fn to_oklch(self) -> Oklch
+    {
+        self.into()
+    }
+
+    fn lerp_oklab(self, other: Self, t: f32) -> Self
+    {
+        let a = self.to_oklab();
+        let b = other.to_oklab();
+
+        Oklab
+        {
+            l: a.l + (b.l - a.l) * t,
+            a: a.a + (b.a - a.a) * t,
+            b: a.b + (b.b - a.b) * t,
+            alpha: a.alpha + (b.alpha - a.alpha) * t,
+        }
+        .into()
+    }
+
+    fn lighten(self, amt: f32) -> Self
+    {
+        let mut lch = self.to_oklch();
+
+        lch.l = (lch.l + amt).max(0.0).min(1.0);
+        lch.into()
+    }
+
+    fn saturate(self, amt: f32) -> Self
+    {
+        let mut lch = self.to_oklch();
+
+        lch.c = (lch.c * (1.0 + amt)).max(0.0);
+        lch.into()
+    }
+}
+
+impl ColourMode
+{
+    /// create a colour from its components, interpreted in this
+    /// mode, and an alpha in [0, 255]
+    pub fn colour(self, comps: Vec3<f32>, alpha: f32) -> Rgba<u8>
+    {
+        let a = alpha / 255.0;
+
+        match self
+        {
+            ColourMode::Rgb => Rgba::new
+            (
+                unit_to_u8(comps.x / 255.0),
+                unit_to_u8(comps.y / 255.0),
+                unit_to_u8(comps.z / 255.0),
+                unit_to_u8(a),
+            ),
+            ColourMode::Hsb => Hsv::with_alpha(comps.x, comps.y / 100.0, comps.z / 100.0, a).into(),
+            ColourMode::Hsl => Hsl::with_alpha(comps.x, comps.y / 100.0, comps.z / 100.0, a).into(),
+        }
+    }
+}
+
+impl ColourSpace
+{
+    /// interpolate between colours `a` and `b` in this colour space,
+    /// where `t` is in [0, 1]
+    pub fn lerp(self, a: Rgba<u8>, b: Rgba<u8>, t: f32) -> Rgba<u8>
+    {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        match self
+        {
+            ColourSpace::Srgb =>
+            {
+                a.map2(b, |a, b| lerp(a as f32, b as f32).round() as u8)
+            }
+            ColourSpace::LinearSrgb =>
+            {
+                Rgba::from_linear(a.to_linear().map2(b.to_linear(), lerp))
+            }
+            ColourSpace::Hsl =>
+            {
+                let (a, b) = (a.to_hsl(), b.to_hsl());
+
+                Hsl
+                {
+                    h: lerp_hue(a.h, a.s, b.h, b.s, t),
+                    s: lerp(a.s, b.s),
+                    l: lerp(a.l, b.l),
+                    a: lerp(a.a, b.a),
+                }
+                .into()
+            }
+            ColourSpace::Oklab => a.lerp_oklab(b, t),
+            ColourSpace::Oklch =>
+            {
+                let (a, b) = (a.to_oklch(), b.to_oklch());
+
+                Oklch
+                {
+                    l: lerp(a.l, b.l),
+                    c: lerp(a.c, b.c),
+                    h: lerp_hue(a.h, a.c, b.h, b.c, t),
+                    alpha: lerp(a.alpha, b.alpha),
+                }
+                .into()
+            }
+        }
+    }
+}
+
+impl Default for ColourSpace
+{
+    fn default() -> Self
+    {
+        ColourSpace::Srgb
+    }
+}
+
+impl Default for ColourMode
+{
+    fn default() -> Self
+    {
+        ColourMode::Rgb
+    }
+}
+
+impl Hsv
+{
+    /// create a new, fully opaque, HSV colour
+    #[inline]
+    pub fn new(h: f32, s: f32, v: f32) -> Self
+    {
+        Self::with_alpha(h, s, v, 1.0)
+    }
+
+    /// create a new HSV colour with alpha
+    #[inline]
+    pub fn with_alpha(h: f32, s: f32, v: f32, a: f32) -> Self
+    {
+        Self { h, s, v, a }
+    }
+}
+
+impl Hsl
+{
+    /// create a new, fully opaque, HSL colour
+    #[inline]
+    pub fn new(h: f32, s: f32, l: f32) -> Self
+    {
+        Self::with_alpha(h, s, l, 1.0)
+    }
+
+    /// create a new HSL colour with alpha
+    #[inline]
+    pub fn with_alpha(h: f32, s: f32, l: f32, a: f32) -> Self
+    {
+        Self { h, s, l, a }
+    }
+}
+
+impl Oklab
+{
+    /// create a new, fully opaque, OKLab colour
+    #[inline]
+    pub fn new(l: f32, a: f32, b: f32) -> Self
+    {
+        Self { l, a, b, alpha: 1.0 }
+    }
+}
+
+impl Oklch
+{
+    /// create a new, fully opaque, OKLCH colour
+    #[inline]
+    pub fn new(l: f32, c: f32, h: f32) -> Self
+    {
+        Self { l, c, h, alpha: 1.0 }
+    }
+}
+
+impl From<Rgba<u8>> for Hsv
+{
+    fn from(col: Rgba<u8>) -> Self
+    {
+        let (h, min, max) = hue_min_max(col);
+
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        Self::with_alpha(h, s, max, col.a as f32 / 255.0)
+    }
+}
+
+impl From<Hsv> for Rgba<u8>
+{
+    fn from(col: Hsv) -> Self
+    {
+        let c = col.v * col.s;
+        let m = col.v - c;
+
+        hue_to_rgb(col.h, c, m, col.a)
+    }
+}
+
+impl From<Rgba<u8>> for Hsl
+{
+    fn from(col: Rgba<u8>) -> Self
+    {
+        let (h, min, max) = hue_min_max(col);
+
+        let l = (max + min) * 0.5;
+        let s = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * l - 1.0).abs()) };
+
+        Self::with_alpha(h, s, l, col.a as f32 / 255.0)
+    }
+}
+
+impl From<Hsl> for Rgba<u8>
+{
+    fn from(col: Hsl) -> Self
+    {
+        let c = (1.0 - (2.0 * col.l - 1.0).abs()) * col.s;
+        let m = col.l - c * 0.5;
+
+        hue_to_rgb(col.h, c, m, col.a)
+    }
+}
+
+impl From<Rgba<u8>> for Oklab
+{
+    fn from(col: Rgba<u8>) -> Self
+    {
+        let Rgba { r, g, b, a } = col.to_linear();
+
+        // linear sRGB -> LMS cone response
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self
+        {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+            alpha: a,
+        }
+    }
+}
+
+impl From<Oklab> for Rgba<u8>
+{
+    fn from(col: Oklab) -> Self
+    {
+        // OKLab -> LMS cone response
+        let l = col.l + 0.3963377774 * col.a + 0.2158037573 * col.b;
+        let m = col.l - 0.1055613458 * col.a - 0.0638541728 * col.b;
+        let s = col.l - 0.0894841775 * col.a - 1.2914855480 * col.b;
+
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        Rgba::from_linear(Rgba::new
+        (
+             4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+            col.alpha,
+        ))
+    }
+}
+
+impl From<Oklab> for Oklch
+{
+    fn from(col: Oklab) -> Self
+    {
+        let c = (col.a * col.a + col.b * col.b).sqrt();
+        let h = col.b.atan2(col.a).to_degrees().rem_euclid(360.0);
+
+        Self { l: col.l, c, h, alpha: col.alpha }
+    }
+}
+
+impl From<Oklch> for Oklab
+{
+    fn from(col: Oklch) -> Self
+    {
+        let (sin, cos) = col.h.to_radians().sin_cos();
+
+        Self { l: col.l, a: col.c * cos, b: col.c * sin, alpha: col.alpha }
+    }
+}
+
+impl From<Rgba<u8>> for Oklch
+{
+    #[inline]
+    fn from(col: Rgba<u8>) -> Self
+    {
+        Oklab::from(col).into()
+    }
+}
+
+impl From<Oklch> for Rgba<u8>
+{
+    #[inline]
+    fn from(col: Oklch) -> Self
+    {
+        Oklab::from(col).into()
+    }
+}
+
+/// convert an sRGB encoded component to linear light, in [0, 1]
+#[inline]
+pub fn srgb_to_linear(n: u8) -> f32
+{
+    let n = n as f32 / 255.0;
+
+    if n <= 0.04045 { n / 12.92 } else { ((n + 0.055) / 1.055).powf(2.4) }
+}
+
+/// convert a linear light component, in [0, 1], to its sRGB
+/// encoding. values out of range are clamped
+#[inline]
+pub fn linear_to_srgb(n: f32) -> u8
+{
+    let n = n.max(0.0).min(1.0);
+
+    unit_to_u8(if n <= 0.0031308 { n * 12.92 } else { 1.055 * n.powf(1.0 / 2.4) - 0.055 })
+}
+
+/// interpolate between hues `a` and `b`, in degrees, taking the shortest
+/// path around the hue circle. the hue of an achromatic colour(zero
+/// saturation or chroma) is meaningless, so the other's is used instead
+fn lerp_hue(a: f32, a_sat: f32, b: f32, b_sat: f32, t: f32) -> f32
+{
+    let (a, b) = match (a_sat == 0.0, b_sat == 0.0)
+    {
+        (true, false) => (b, b),
+        (false, true) => (a, a),
+        _ => (a, b),
+    };
+    // signed shortest distance, in (-180, 180]
+    let d = (b - a + 180.0).rem_euclid(360.0) - 180.0;
+
+    (a + d * t).rem_euclid(360.0)
+}
+
+/// convert a number in [0, 1] to [0, 255], clamping and rounding
+#[inline]
+fn unit_to_u8(n: f32) -> u8
+{
+    (n.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+/// common part of the HSV and HSL conversions: returns (hue in degrees,
+/// smallest component, largest component) of `col`, where components are
+/// in [0, 1]
+fn hue_min_max(col: Rgba<u8>) -> (f32, f32, f32)
+{
+    let r = col.r as f32 / 255.0;
+    let g = col.g as f32 / 255.0;
+    let b = col.b as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+
+    let h = if d == 0.0
+    {
+        0.0
+    }
+    else if max == r
+    {
+        60.0 * ((g - b) / d)
+    }
+    else if max == g
+    {
+        60.0 * ((b - r) / d + 2.0)
+    }
+    else
+    {
+        60.0 * ((r - g) / d + 4.0)
+    };
+
+    (h.rem_euclid(360.0), min, max)
+}
+
+/// common part of the HSV and HSL conversions: builds a colour from its
+/// hue(in degrees), chroma `c`, lightness offset `m` and alpha, where all
+/// but the hue are in [0, 1]
+fn hue_to_rgb(h: f32, c: f32, m: f32, a: f32) -> Rgba<u8>
+{
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match h as u32
+    {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    Rgba::new(unit_to_u8(r + m), unit_to_u8(g + m), unit_to_u8(b + m), unit_to_u8(a))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// a sample of every 17th value of each channel
+    fn colours() -> impl Iterator<Item = Rgba<u8>>
+    {
+        (0..=255u16).step_by(17).flat_map(|r| (0..=255u16).step_by(17).flat_map(move |g|
+        {
+            (0..=255u16).step_by(17).map(move |b| Rgba::new(r as u8, g as u8, b as u8, 0xff))
+        }))
+    }
+
+    /// are both colours within ±1 per channel?
+    fn close(a: Rgba<u8>, b: Rgba<u8>) -> bool
+    {
+        a.map2(b, |a, b| (a as i16 - b as i16).abs() <= 1).reduce_and()
+    }
+
+    /// are both numbers within `eps`?
+    fn near(a: f32, b: f32, eps: f32) -> bool
+    {
+        (a - b).abs() <= eps
+    }
+
+    #[test]
+    fn round_trip()
+    {
+        for col in colours()
+        {
+            assert!(close(Rgba::<u8>::from(col.to_hsv()), col), "hsv {:?}", col);
+            assert!(close(Rgba::<u8>::from(col.to_hsl()), col), "hsl {:?}", col);
+            assert!(close(Rgba::<u8>::from(col.to_oklab()), col), "oklab {:?}", col);
+            assert!(close(Rgba::<u8>::from(col.to_oklch()), col), "oklch {:?}", col);
+            assert!(close(Rgba::from_linear(col.to_linear()), col), "linear {:?}", col);
+        }
+    }
+
+    #[test]
+    fn reference_values()
+    {
+        let red = Rgba::new(0xff, 0, 0, 0xff);
+
+        assert_eq!(red.to_hsv(), Hsv::new(0.0, 1.0, 1.0));
+        assert_eq!(red.to_hsl(), Hsl::new(0.0, 1.0, 0.5));
+        assert_eq!(Rgba::new(0, 0xff, 0xff, 0xff).to_hsv().h, 180.0);
+        assert_eq!(Rgba::new(0x80, 0x80, 0x80, 0x80).to_hsl().s, 0.0);
+
+        let lab = red.to_oklab();
+        assert!(near(lab.l, 0.628, 1e-3) && near(lab.a, 0.225, 1e-3) && near(lab.b, 0.126, 1e-3), "{:?}", lab);
+
+        let lab = Rgba::new(0xff, 0xff, 0xff, 0xff).to_oklab();
+        assert!(near(lab.l, 1.0, 1e-3) && near(lab.a, 0.0, 1e-3) && near(lab.b, 0.0, 1e-3), "{:?}", lab);
+        assert_eq!(Rgba::new(0, 0, 0, 0xff).to_oklab().l, 0.0);
+
+        let lch = Rgba::new(0, 0, 0xff, 0xff).to_oklch();
+        assert!(near(lch.l, 0.452, 1e-3) && near(lch.c, 0.313, 1e-3) && near(lch.h, 264.05, 0.1), "{:?}", lch);
+    }
+
+    #[test]
+    fn hue_wraps()
+    {
+        let red = Rgba::new(0xff, 0, 0, 0xff);
+        let green = Rgba::new(0, 0xff, 0, 0xff);
+        let blue = Rgba::new(0, 0, 0xff, 0xff);
+
+        assert_eq!(Rgba::<u8>::from(Hsv::new(360.0, 1.0, 1.0)), red);
+        assert_eq!(Rgba::<u8>::from(Hsv::new(-120.0, 1.0, 1.0)), blue);
+        assert_eq!(Rgba::<u8>::from(Hsl::new(840.0, 1.0, 0.5)), green);
+        assert_eq!(Rgba::<u8>::from(Oklch::new(0.628, 0.258, 29.23 + 360.0)), Rgba::<u8>::from(Oklch::new(0.628, 0.258, 29.23)));
+
+        // always in [0, 360)
+        let h = Rgba::new(0xff, 0, 0x80, 0xff).to_hsv().h;
+        assert!(h >= 0.0 && h < 360.0 && near(h, 329.9, 0.1), "{}", h);
+        for col in colours()
+        {
+            let h = col.to_oklch().h;
+            assert!(h >= 0.0 && h < 360.0, "{:?}: {}", col, h);
+        }
+    }
+
+    #[test]
+    fn hue_lerp()
+    {
+        // the short way round
+        assert!(near(lerp_hue(350.0, 1.0, 10.0, 1.0, 0.5), 0.0, 1e-3));
+        assert!(near(lerp_hue(10.0, 1.0, 350.0, 1.0, 0.25), 5.0, 1e-3));
+        // greys take the other colour's hue
+        assert!(near(lerp_hue(0.0, 0.0, 120.0, 1.0, 0.5), 120.0, 1e-3));
+
+        let (red, blue) = (Rgba::new(0xff, 0, 0, 0xff), Rgba::new(0, 0, 0xff, 0xff));
+        assert!(close(ColourSpace::Hsl.lerp(red, blue, 0.5), Rgba::new(0xff, 0, 0xff, 0xff)));
+        assert_eq!(ColourSpace::Srgb.lerp(red, blue, 0.5), Rgba::new(0x80, 0, 0x80, 0xff));
+        for &space in &[ColourSpace::Srgb, ColourSpace::LinearSrgb, ColourSpace::Hsl, ColourSpace::Oklab, ColourSpace::Oklch]
+        {
+            assert!(close(space.lerp(red, blue, 0.0), red), "{:?}", space);
+            assert!(close(space.lerp(red, blue, 1.0), blue), "{:?}", space);
+        }
+    }
+
+    #[test]
+    fn clamping()
+    {
+        // out of range components are clamped rather than wrapped
+        assert_eq!(Rgba::<u8>::from(Hsv::with_alpha(0.0, 2.0, 1.5, 3.0)), Rgba::new(0xff, 0, 0, 0xff));
+        assert_eq!(Rgba::<u8>::from(Hsl::with_alpha(0.0, 0.0, -1.0, -1.0)), Rgba::new(0, 0, 0, 0));
+        assert_eq!(Rgba::<u8>::from(Oklab::new(2.0, 0.0, 0.0)), Rgba::new(0xff, 0xff, 0xff, 0xff));
+        assert_eq!(linear_to_srgb(-0.5), 0);
+        assert_eq!(linear_to_srgb(1.5), 0xff);
+    }
+
+    #[test]
+    fn colour_mode()
+    {
+        assert_eq!(ColourMode::Rgb.colour(Vec3::new(255.0, 128.0, 0.0), 255.0), Rgba::new(0xff, 0x80, 0, 0xff));
+        assert_eq!(ColourMode::Rgb.colour(Vec3::new(300.0, -5.0, 0.0), 255.0), Rgba::new(0xff, 0, 0, 0xff));
+        assert_eq!(ColourMode::Hsb.colour(Vec3::new(120.0, 100.0, 100.0), 255.0), Rgba::new(0, 0xff, 0, 0xff));
+        assert_eq!(ColourMode::Hsl.colour(Vec3::new(240.0, 100.0, 50.0), 127.5), Rgba::new(0, 0, 0xff, 0x80));
+        assert_eq!(ColourMode::default(), ColourMode::Rgb);
+    }
+
+    #[test]
+    fn lighten_darken_saturate()
+    {
+        let col = Rgba::new(0x40, 0x80, 0xc0, 0xff);
+
+        assert!(col.lighten(0.1).to_oklab().l > col.to_oklab().l);
+        assert!(col.darken(0.1).to_oklab().l < col.to_oklab().l);
+        // lightness is clamped
+        assert_eq!(col.lighten(1.0), col.lighten(5.0));
+        assert_eq!(col.darken(1.0), col.darken(5.0));
+        let grey = Rgba::new(0x80, 0x80, 0x80, 0xff);
+        assert!(close(grey.lighten(1.0), Rgba::new(0xff, 0xff, 0xff, 0xff)));
+        assert!(close(grey.darken(1.0), Rgba::new(0, 0, 0, 0xff)));
+        assert!(close(col.lighten(0.0), col));
+
+        assert!(col.saturate(0.2).to_oklch().c > col.to_oklch().c);
+        // fully desaturated is grey, and can't go further
+        for &amt in &[-1.0, -2.0]
+        {
+            let grey = col.saturate(amt);
+            assert!(close(Rgba::new(grey.r, grey.r, grey.r, 0xff), grey), "{:?}", grey);
+        }
+        assert_eq!(col.lighten(0.2).a, 0xff);
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt;
+
+use crate::math::{ Rgba, Hsl, named_colour };
+
+/// an error while parsing a colour from a string, holding the
+/// string that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColourError(String);
+
+/// parse a CSS colour, see `Colour::parse`
+pub(super) fn parse(s: &str) -> Result<Rgba<u8>, ParseColourError>
+{
+    let lower = s.trim().to_ascii_lowercase();
+
+    // hexadecimal notation
+    let col = if let Some(hex) = lower.strip_prefix('#')
+    {
+        parse_hex(hex)
+    }
+    // functional notation
+    else if let (Some(open), true) = (lower.find('('), lower.ends_with(')'))
+    {
+        parse_fn(lower[..open].trim(), &lower[open + 1..lower.len() - 1])
+    }
+    // keywords
+    else if lower == "transparent"
+    {
+        Some(Rgba::new(0, 0, 0, 0))
+    }
+    else
+    {
+        named_colour(&lower)
+    };
+
+    col.ok_or_else(|| ParseColourError(s.to_owned()))
+}
+
+/// parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, without the `#`
+fn parse_hex(hex: &str) -> Option<Rgba<u8>>
+{
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    // digits are all ascii past this point
+    let digit = |i: usize, n: usize| u8::from_str_radix(&hex[i * n..(i + 1) * n], 16).ok();
+
+    match hex.len()
+    {
+        // short notation, where each digit is doubled(`f` -> `ff`)
+        3 | 4 =>
+        {
+            let mut col = [0xff; 4];
+            for (i, c) in col.iter_mut().enumerate().take(hex.len())
+            {
+                *c = digit(i, 1)? * 0x11;
+            }
+            Some(col.into())
+        }
+        6 | 8 =>
+        {
+            let mut col = [0xff; 4];
+            for (i, c) in col.iter_mut().enumerate().take(hex.len() / 2)
+            {
+                *c = digit(i, 2)?;
+            }
+            Some(col.into())
+        }
+        _ => None
+    }
+}
+
+/// parse `rgb(...)`, `rgba(...)`, `hsl(...)` or `hsla(...)`, given the
+/// function name and the arguments within the parentheses. arguments
+/// can be separated by commas or whitespace, with an optional `/` before
+/// alpha
+fn parse_fn(name: &str, args: &str) -> Option<Rgba<u8>>
+{
+    let args = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<_>>();
+
+    // optional alpha
+    let alpha = match args.len()
+    {
+        3 => 1.0,
+        4 => parse_alpha(args[3])?,
+        _ => return None,
+    };
+
+    match name
+    {
+        "rgb" | "rgba" =>
+        {
+            let r = parse_channel(args[0])?;
+            let g = parse_channel(args[1])?;
+            let b = parse_channel(args[2])?;
+
+            Some(Rgba::new(r, g, b, (alpha * 255.0).round() as u8))
+        }
+        "hsl" | "hsla" =>
+        {
+            let h = parse_hue(args[0])?;
+            let s = parse_percentage(args[1])?;
+            let l = parse_percentage(args[2])?;
+
+            Some(Hsl::with_alpha(h, s, l, alpha).into())
+        }
+        _ => None
+    }
+}
+
+/// parse an rgb channel, either in [0, 255] or a percentage
+fn parse_channel(arg: &str) -> Option<u8>
+{
+    let n = match arg.strip_suffix('%')
+    {
+        Some(pct) => pct.parse::<f32>().ok()? * 2.55,
+        None => arg.parse::<f32>().ok()?,
+    };
+    Some(n.max(0.0).min(255.0).round() as u8)
+}
+
+/// parse an alpha, either in [0, 1] or a percentage, into [0, 1]
+fn parse_alpha(arg: &str) -> Option<f32>
+{
+    let n = match arg.strip_suffix('%')
+    {
+        Some(pct) => pct.parse::<f32>().ok()? / 100.0,
+        None => arg.parse::<f32>().ok()?,
+    };
+    Some(n.max(0.0).min(1.0))
+}
+
+/// parse a percentage, with or without its `%`, into [0, 1]
+fn parse_percentage(arg: &str) -> Option<f32>
+{
+    let n = arg.strip_suffix('%').unwrap_or(arg).parse::<f32>().ok()?;
+
+    Some((n / 100.0).max(0.0).min(1.0))
+}
+
+/// parse a hue into degrees. supports the `deg`, `rad`, `grad` and
+/// `turn` units, and defaults to degrees
+fn parse_hue(arg: &str) -> Option<f32>
+{
+    let units: [(&str, fn(f32) -> f32); 4] =
+    [
+        ("deg", |n| n),
+        ("grad", |n| n * 0.9),
+        ("rad", f32::to_degrees),
+        ("turn", |n| n * 360.0),
+    ];
+
+    for (unit, to_deg) in units.iter()
+    {
+        if let Some(n) = arg.strip_suffix(unit)
+        {
+            return n.parse().ok().map(to_deg);
+        }
+    }
+    arg.parse().ok()
+}
+
+impl fmt::Display for ParseColourError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "invalid colour: \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseColourError { }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn rgba(r: u8, g: u8, b: u8, a: u8) -> Result<Rgba<u8>, ParseColourError>
+    {
+        Ok(Rgba::new(r, g, b, a))
+    }
+
+    #[test]
+    fn hex()
+    {
+        assert_eq!(parse("#f80"), rgba(0xff, 0x88, 0x00, 0xff));
+        assert_eq!(parse("#f808"), rgba(0xff, 0x88, 0x00, 0x88));
+        assert_eq!(parse("#12ab3c"), rgba(0x12, 0xab, 0x3c, 0xff));
+        assert_eq!(parse("#12ab3c80"), rgba(0x12, 0xab, 0x3c, 0x80));
+    }
+
+    #[test]
+    fn rgb()
+    {
+        assert_eq!(parse("rgb(255, 128, 0)"), rgba(255, 128, 0, 0xff));
+        assert_eq!(parse("rgba(255, 128, 0, 0.5)"), rgba(255, 128, 0, 0x80));
+        assert_eq!(parse("rgb(255 128 0 / 25%)"), rgba(255, 128, 0, 0x40));
+        assert_eq!(parse("rgb(100%, 50%, 0%)"), rgba(255, 128, 0, 0xff));
+        assert_eq!(parse("rgb(12.4, 12.6, 0)"), rgba(12, 13, 0, 0xff));
+        // out of range
+        assert_eq!(parse("rgb(300, -20, 0)"), rgba(255, 0, 0, 0xff));
+        assert_eq!(parse("rgb(150%, -50%, 0%)"), rgba(255, 0, 0, 0xff));
+        assert_eq!(parse("rgba(0, 0, 0, 2)"), rgba(0, 0, 0, 0xff));
+        assert_eq!(parse("rgba(0, 0, 0, -1)"), rgba(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn hsl()
+    {
+        assert_eq!(parse("hsl(120, 100%, 50%)"), rgba(0, 255, 0, 0xff));
+        assert_eq!(parse("hsla(240, 100%, 50%, 0.5)"), rgba(0, 0, 255, 0x80));
+        assert_eq!(parse("hsl(240deg 100% 50% / 50%)"), rgba(0, 0, 255, 0x80));
+        assert_eq!(parse("hsl(0.5turn, 100%, 50%)"), rgba(0, 255, 255, 0xff));
+        assert_eq!(parse("hsl(200grad, 100%, 50%)"), rgba(0, 255, 255, 0xff));
+        assert_eq!(parse("hsl(-120, 100%, 50%)"), rgba(0, 0, 255, 0xff));
+        assert_eq!(parse("hsl(0, 0%, 100%)"), rgba(255, 255, 255, 0xff));
+        // percent signs are optional, and out of range values clamped
+        assert_eq!(parse("hsl(0, 150, 50)"), rgba(255, 0, 0, 0xff));
+
+        let cyan = parse("hsl(3.14159rad, 100%, 50%)").unwrap();
+        assert!(cyan.r <= 1 && cyan.g == 255 && cyan.b >= 254, "{:?}", cyan);
+    }
+
+    #[test]
+    fn whitespace_and_case()
+    {
+        assert_eq!(parse("  #FFF\n"), rgba(255, 255, 255, 0xff));
+        assert_eq!(parse("#AbCdEf"), rgba(0xab, 0xcd, 0xef, 0xff));
+        assert_eq!(parse(" RGB( 255 ,0,  0 ) "), rgba(255, 0, 0, 0xff));
+        assert_eq!(parse("HSL (120, 100%, 50%)"), rgba(0, 255, 0, 0xff));
+        assert_eq!(parse("RoyalBlue"), rgba(65, 105, 225, 0xff));
+        assert_eq!(parse(" grey "), rgba(128, 128, 128, 0xff));
+    }
+
+    #[test]
+    fn transparent()
+    {
+        assert_eq!(parse("transparent"), rgba(0, 0, 0, 0));
+        assert_eq!(parse("Transparent"), rgba(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn invalid()
+    {
+        let invalid =
+        [
+            "", " ", "#", "#ff", "#fffff", "#fffffffff", "#ggg", "#ÿÿÿ", "ff0000",
+            "rgb()", "rgb(1, 2)", "rgb(1, 2, 3, 4, 5)", "rgb(a, b, c)", "rgb(1, 2, 3",
+            "rgb 1, 2, 3)", "hsl(1x, 50%, 50%)", "cmyk(0, 0, 0)", "notacolour", "gray grey",
+        ];
+        for s in invalid.iter()
+        {
+            assert_eq!(parse(s), Err(ParseColourError(s.to_string())), "{:?}", s);
+        }
+        assert_eq!(parse(" nope ").unwrap_err().to_string(), "invalid colour: \" nope \"");
+    }
+}
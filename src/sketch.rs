@@ -1,22 +1,31 @@
+use std::path::Path;
+
 use crate::gui::Gui;
-use crate::draw::Canvas;
-use crate::App;
+use crate::draw::{ Canvas, CanvasId };
+use crate::math::{ Rgba, Extent2 };
+use crate::{ App, Error };
 
 /// represents a sketch that can be run by the framework
-pub trait Sketch: 'static
+pub trait Sketch: Sized + 'static
 {
     /// setup this sketch, creating canvas(es)
-    /// and loading additional resources
-    fn setup(app: &mut App) -> Self;
+    /// and loading additional resources. An error
+    /// stops the sketch before any window opens,
+    /// and is returned by [run]
+    ///
+    /// [run]: crate::run
+    fn setup(app: &mut App) -> Result<Self, Error>;
 
     /// draw on the given [Canvas]. This method
     /// is called for every existing canvas on
-    /// every frame
+    /// every frame, except canvases with their
+    /// own draw function from [CanvasBuilder::draw]
     ///
     /// [Canvas]: crate::draw::Canvas
+    /// [CanvasBuilder::draw]: crate::draw::CanvasBuilder::draw
     fn draw(&mut self, c: &mut Canvas)
     {
-        c.background(Default::default());
+        c.background(Rgba::default());
     }
 
     /// update the state of this [Sketch] and its
@@ -30,6 +39,17 @@ pub trait Sketch: 'static
         app.time().print_current_frame_rate();
     }
 
+    /// called once every frame after any updates and
+    /// right before drawing, even when [Time] skips
+    /// updating. This is where to interpolate between
+    /// fixed updates with `Time::alpha()`
+    ///
+    /// [Time]: crate::input::Time
+    fn pre_draw(&mut self, _: &mut App)
+    {
+
+    }
+
     /// draw on the given [Gui] which wraps around a
     /// [Canvas]
     ///
@@ -39,4 +59,29 @@ pub trait Sketch: 'static
     {
 
     }
-}
\ No newline at end of file
+
+    /// called when an asset loaded through [Assets] was
+    /// reloaded because its file changed on disk. `path`
+    /// is the asset's resolved path. On success, every
+    /// [Handle] to it already sees the new value. On
+    /// error, ie. if the file is only partly written, the
+    /// asset keeps its previous value
+    ///
+    /// [Assets]: crate::Assets
+    /// [Handle]: crate::Handle
+    fn asset_reloaded(&mut self, _: &mut App, _: &Path, _: Result<(), Error>)
+    {
+
+    }
+
+    /// called when a [Canvas]' window was resized. `size` is
+    /// the canvas' size, which only changes with
+    /// [ResizePolicy::FollowWindow]
+    ///
+    /// [Canvas]: crate::draw::Canvas
+    /// [ResizePolicy::FollowWindow]: crate::draw::ResizePolicy::FollowWindow
+    fn resized(&mut self, _: &mut App, _: CanvasId, _: Extent2<usize>)
+    {
+
+    }
+}
@@ -4,22 +4,24 @@ mod track;
 pub use self::sample::{ Sample, SampleType };
 pub use self::track::Track;
 
+use crate::error::{ Error, AudioError };
+
 /// audio context. this is a dead simple wrapper around `rodio`'s
 /// types
 pub struct Audio
 {
     _stream: rodio::OutputStream,
-    handle: rodio::OutputStreamHandle,
+    pub(crate) handle: rodio::OutputStreamHandle,
 }
 
 impl Audio
 {
     /// create a new audio context and connect to the endpoint,
     /// maintaining that connection until dropped
-    pub(crate) fn new() -> Self
+    pub(crate) fn new() -> Result<Self, Error>
     {
-        let (_stream, handle) = rodio::OutputStream::try_default().unwrap();
+        let (_stream, handle) = rodio::OutputStream::try_default().map_err(AudioError::Stream)?;
 
-        Self { _stream, handle }
+        Ok(Self { _stream, handle })
     }
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::sound::Sample;
+use crate::error::{ Error, AudioError };
 
 use super::Audio;
 
@@ -41,24 +42,27 @@ struct TrackSource<S: Sample>
     sample_rate: u32,   
 }
 
-/// an error while reading an audio file, either io or format
-pub type TrackError = audrey::read::ReadError;
-
 impl<S: Sample> Track<S>
 {
     /// Attempts to open a `Track` at the specified `Path`.
     ///
     /// The format is determined from the path's file extension.
-    pub fn open(path: impl AsRef<Path>, audio: &Audio) -> Result<Self, TrackError>
+    pub fn open(path: impl AsRef<Path>, audio: &Audio) -> Result<Self, Error>
+    {
+        Self::open_on(path, &audio.handle)
+    }
+
+    /// open a `Track` that plays on the output `handle`
+    pub(crate) fn open_on(path: impl AsRef<Path>, handle: &rodio::OutputStreamHandle) -> Result<Self, Error>
     {
         // read the audio file
-        let mut reader = audrey::open(path)?;
+        let mut reader = audrey::open(path).map_err(AudioError::Read)?;
 
-        // collet the samples
+        // collect the samples, stopping at the first bad one
         let samples = reader
             .samples()
-            .map(Result::unwrap)
-            .collect::<Arc<[_]>>();
+            .collect::<Result<Arc<[_]>, _>>()
+            .map_err(AudioError::Read)?;
         
         // description
         let desc = reader.description();
@@ -66,7 +70,7 @@ impl<S: Sample> Track<S>
         let channel_count = desc.channel_count() as usize;
 
         // sink
-        let sink = rodio::Sink::try_new(&audio.handle).unwrap();
+        let sink = rodio::Sink::try_new(handle).map_err(AudioError::Play)?;
         sink.append(TrackSource
         {
             ind: 0,
@@ -176,7 +180,7 @@ impl<S: Sample> Track<S>
     pub fn duration(&self) -> Duration
     {
         let ms = self.samples.len() as u64 * 1000;
-        let div = (self.channel_count * self.sample_rate) as u64;
+        let div = (self.channel_count * self.sample_rate).max(1) as u64;
         
         Duration::from_millis(ms / div)
     }
@@ -228,7 +232,8 @@ impl<S: Sample> rodio::Source for TrackSource<S>
 
     fn total_duration(&self) -> Option<Duration>
     {
-        let ms = self.len() as u64 * 1000 / (self.channel_count as u64 * self.sample_rate as u64);
+        let div = (self.channel_count as u64 * self.sample_rate as u64).max(1);
+        let ms = self.len() as u64 * 1000 / div;
         Some(Duration::from_millis(ms))
     }
 }
\ No newline at end of file
@@ -2,8 +2,12 @@ mod ref_counted;
 mod barycentric;
 mod bresenham;
 mod triangle;
+mod tween;
+mod random;
 
 pub use self::barycentric::Barycentric;
 pub use self::ref_counted::RefCounted;
 pub use self::bresenham::Bresenham;
-pub use self::triangle::Triangle;
\ No newline at end of file
+pub use self::triangle::Triangle;
+pub use self::tween::{ Tween, Ease };
+pub use self::random::Random;
\ No newline at end of file
@@ -0,0 +1,260 @@
+use std::f32::consts::TAU;
+
+use rand::{ Rng, RngCore, SeedableRng };
+use rand::distributions::uniform::{ SampleRange, SampleUniform };
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+
+use crate::math::{ Rgba, Vec2 };
+
+/// seedable random number generator, so generated output can be
+/// re-created from its seed. the same seed gives the same numbers
+/// on every run and every platform, except for `usize` and `isize`
+/// ranges whose numbers depend on the platform's pointer width. use
+/// `u64` and `i64` ranges for portable output. implements `rand::Rng`
+/// too
+/// ```
+/// // setup
+/// app.random_seed(1234);
+///
+/// // draw
+/// println!("seed: {}", app.random().seed());
+/// let pos = app.random_in_rect(v![0.0, 0.0], v![400.0, 300.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Random
+{
+    /// underlying generator
+    rng: ChaCha8Rng,
+    /// seed the generator started from
+    seed: u64,
+}
+
+impl Random
+{
+    /// create a generator with a random seed
+    pub fn new() -> Self
+    {
+        Self::from_seed(rand::random())
+    }
+
+    /// create a generator from a seed
+    pub fn from_seed(seed: u64) -> Self
+    {
+        Self
+        {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            seed,
+        }
+    }
+
+    /// the seed this generator last started from
+    #[inline]
+    pub fn seed(&self) -> u64
+    {
+        self.seed
+    }
+
+    /// start over from a seed
+    pub fn reseed(&mut self, seed: u64)
+    {
+        *self = Self::from_seed(seed);
+    }
+
+    /// random number in a range, ie. `range(0..10)` or
+    /// `range(0.0..=1.0)`. `usize` and `isize` ranges give different
+    /// numbers on 32 and 64-bit platforms
+    pub fn range<T: SampleUniform, R: SampleRange<T>>(&mut self, range: R) -> T
+    {
+        self.rng.gen_range(range)
+    }
+
+    /// normally distributed random number, with most results close
+    /// to `mean` and about two thirds within `std_dev` of it
+    pub fn gaussian(&mut self, mean: f32, std_dev: f32) -> f32
+    {
+        // box-muller transform, keeping the log away from zero
+        let u = 1.0 - self.rng.gen::<f32>();
+        let v = self.rng.gen::<f32>();
+
+        mean + std_dev * (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+    }
+
+    /// random item of a slice, or `None` if it's empty
+    pub fn choice<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T>
+    {
+        items.choose(&mut self.rng)
+    }
+
+    /// random, opaque colour
+    pub fn colour(&mut self) -> Rgba<u8>
+    {
+        Rgba::new(self.rng.gen(), self.rng.gen(), self.rng.gen(), 0xff)
+    }
+
+    /// random point inside a circle, spread evenly over its area
+    pub fn in_circle(&mut self, centre: Vec2<f32>, radius: f32) -> Vec2<f32>
+    {
+        let angle = self.rng.gen::<f32>() * TAU;
+        let dist = radius * self.rng.gen::<f32>().sqrt();
+
+        centre + Vec2::new(angle.cos(), angle.sin()) * dist
+    }
+
+    /// random point inside a rectangle, given its top-left corner
+    /// and size
+    pub fn in_rect(&mut self, pos: Vec2<f32>, size: Vec2<f32>) -> Vec2<f32>
+    {
+        pos + size * Vec2::new(self.rng.gen(), self.rng.gen())
+    }
+
+    /// shuffle a slice in place
+    pub fn shuffle<T>(&mut self, items: &mut [T])
+    {
+        items.shuffle(&mut self.rng)
+    }
+}
+
+impl Default for Random
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl RngCore for Random
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32
+    {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64
+    {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8])
+    {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error>
+    {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn same_seed()
+    {
+        let mut a = Random::from_seed(1234);
+        let mut b = Random::from_seed(1234);
+
+        for _ in 0..100
+        {
+            assert_eq!(a.range(0u64..1000), b.range(0u64..1000));
+            assert_eq!(a.gaussian(0.0, 1.0), b.gaussian(0.0, 1.0));
+            assert_eq!(a.colour(), b.colour());
+        }
+
+        // reseeding starts the sequence over
+        a.reseed(1234);
+        b = Random::from_seed(1234);
+
+        assert_eq!(a.seed(), 1234);
+        assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn different_seeds()
+    {
+        let mut a = Random::from_seed(1);
+        let mut b = Random::from_seed(2);
+
+        assert_ne!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn range_bounds()
+    {
+        let mut rng = Random::from_seed(0);
+
+        for _ in 0..1000
+        {
+            assert!((-5..5).contains(&rng.range(-5i64..5)));
+            assert!((0..=3).contains(&rng.range(0u8..=3)));
+            assert!((0.0..1.0).contains(&rng.range(0.0f32..1.0)));
+            assert_eq!(rng.range(7u32..=7), 7);
+        }
+
+        // both ends of an inclusive range come up
+        let ints = (0..1000).map(|_| rng.range(0u8..=3)).collect::<Vec<_>>();
+        assert!(ints.contains(&0) && ints.contains(&3));
+    }
+
+    #[test]
+    fn choice()
+    {
+        let mut rng = Random::from_seed(0);
+        let items = [1, 2, 3];
+
+        assert_eq!(rng.choice::<i32>(&[]), None);
+        for _ in 0..100
+        {
+            assert!(items.contains(rng.choice(&items).unwrap()));
+        }
+    }
+
+    #[test]
+    fn shuffle()
+    {
+        let mut rng = Random::from_seed(0);
+        let mut items = (0..100).collect::<Vec<_>>();
+
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..100).collect::<Vec<_>>());
+
+        items.sort_unstable();
+        assert_eq!(items, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn in_shapes()
+    {
+        let mut rng = Random::from_seed(0);
+        let centre = Vec2::new(10.0, -10.0);
+
+        for _ in 0..1000
+        {
+            assert!(rng.in_circle(centre, 5.0).distance(centre) <= 5.0 + 1e-4);
+
+            let p = rng.in_rect(Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0));
+            assert!((1.0..4.0).contains(&p.x) && (2.0..6.0).contains(&p.y));
+        }
+    }
+
+    #[test]
+    fn gaussian()
+    {
+        let mut rng = Random::from_seed(0);
+        let samples = (0..10_000).map(|_| rng.gaussian(5.0, 2.0)).collect::<Vec<_>>();
+
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let within = samples.iter().filter(|s| (*s - 5.0).abs() < 2.0).count();
+
+        assert!((mean - 5.0).abs() < 0.1);
+        // about 68%
+        assert!((6_500..7_100).contains(&within));
+    }
+}
@@ -0,0 +1,336 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use crate::math::Lerp;
+
+/// easing curves, mapping linear progress from 0.0 to 1.0 to
+/// eased progress. elastic and back curves overshoot outside
+/// of that range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ease
+{
+    /// constant speed
+    Linear,
+    /// starts slow, t²
+    QuadIn,
+    /// ends slow, t²
+    QuadOut,
+    /// starts and ends slow, t²
+    QuadInOut,
+    /// starts slow, t³
+    CubicIn,
+    /// ends slow, t³
+    CubicOut,
+    /// starts and ends slow, t³
+    CubicInOut,
+    /// wobbles around the start before going
+    ElasticIn,
+    /// overshoots and wobbles around the end, like a spring
+    ElasticOut,
+    /// wobbles around both ends
+    ElasticInOut,
+    /// bounces off the start before going
+    BounceIn,
+    /// bounces on the end, like a dropped ball
+    BounceOut,
+    /// bounces on both ends
+    BounceInOut,
+    /// pulls back a little before going
+    BackIn,
+    /// overshoots the end a little, then settles
+    BackOut,
+    /// pulls back and overshoots
+    BackInOut,
+}
+
+/// animates a value from one end to another over time, with an
+/// easing curve. anything that can be linearly interpolated can
+/// be tweened, ie. `f32`s or `Vec2<f32>`s
+/// ```
+/// // setup
+/// let slide = Tween::new(v![0.0, 0.0], v![200.0, 0.0], Duration::from_secs(1))
+///     .ease(Ease::BounceOut);
+///
+/// // update
+/// self.slide.update(app.time().delta());
+/// self.pos = self.slide.value();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tween<T>
+{
+    /// value at the start
+    from: T,
+    /// value at the end
+    to: T,
+    /// how long the whole tween takes
+    duration: Duration,
+    /// time the tween has been running
+    elapsed: Duration,
+    /// easing curve
+    ease: Ease,
+}
+
+impl Ease
+{
+    /// ease linear progress `t`, clamped from 0.0 to 1.0
+    pub fn apply(self, t: f32) -> f32
+    {
+        let t = t.max(0.0).min(1.0);
+
+        match self
+        {
+            Ease::Linear => t,
+            Ease::QuadIn => t * t,
+            Ease::QuadOut => out(t, |t| t * t),
+            Ease::QuadInOut => in_out(t, |t| t * t),
+            Ease::CubicIn => t * t * t,
+            Ease::CubicOut => out(t, |t| t * t * t),
+            Ease::CubicInOut => in_out(t, |t| t * t * t),
+            Ease::ElasticIn => elastic(t),
+            Ease::ElasticOut => out(t, elastic),
+            Ease::ElasticInOut => in_out(t, elastic),
+            Ease::BounceIn => out(t, bounce),
+            Ease::BounceOut => bounce(t),
+            Ease::BounceInOut => in_out(t, |t| out(t, bounce)),
+            Ease::BackIn => back(t),
+            Ease::BackOut => out(t, back),
+            Ease::BackInOut => in_out(t, back),
+        }
+    }
+}
+
+impl Default for Ease
+{
+    fn default() -> Self
+    {
+        Ease::Linear
+    }
+}
+
+/// mirror an ease-in curve into an ease-out curve
+fn out(t: f32, f: impl Fn(f32) -> f32) -> f32
+{
+    1.0 - f(1.0 - t)
+}
+
+/// ease in for the first half and out for the second half
+fn in_out(t: f32, f: impl Fn(f32) -> f32) -> f32
+{
+    match t < 0.5
+    {
+        true => f(t * 2.0) / 2.0,
+        false => 1.0 - f((1.0 - t) * 2.0) / 2.0,
+    }
+}
+
+/// elastic ease-in, a sine wave growing from nothing
+fn elastic(t: f32) -> f32
+{
+    if t == 0.0 || t == 1.0
+    {
+        return t;
+    }
+    -(2f32).powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * (2.0 * PI) / 3.0).sin()
+}
+
+/// bounce ease-out, like a ball dropped on the floor
+fn bounce(t: f32) -> f32
+{
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D
+    {
+        N * t * t
+    }
+    else if t < 2.0 / D
+    {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    }
+    else if t < 2.5 / D
+    {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    }
+    else
+    {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// back ease-in, pulling back a little before going
+fn back(t: f32) -> f32
+{
+    const C: f32 = 1.70158;
+
+    (C + 1.0) * t * t * t - C * t * t
+}
+
+impl<T: Lerp<f32, Output = T> + Clone> Tween<T>
+{
+    /// create a new, linear tween going `from` one value `to`
+    /// another over `duration`
+    pub fn new(from: T, to: T, duration: Duration) -> Self
+    {
+        Self
+        {
+            from,
+            to,
+            duration,
+            elapsed: Duration::default(),
+            ease: Ease::Linear,
+        }
+    }
+
+    /// builder method to set the easing curve
+    pub fn ease(mut self, ease: Ease) -> Self
+    {
+        self.ease = ease;
+        self
+    }
+
+    /// advance the tween by `delta`, typically `Time::delta()`
+    pub fn update(&mut self, delta: Duration)
+    {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+    }
+
+    /// get the current, eased value
+    pub fn value(&self) -> T
+    {
+        T::lerp_unclamped(self.from.clone(), self.to.clone(), self.ease.apply(self.progress()))
+    }
+
+    /// get the linear progress, from 0.0 to 1.0
+    pub fn progress(&self) -> f32
+    {
+        match self.duration.as_secs_f32()
+        {
+            d if d > 0.0 => self.elapsed.as_secs_f32() / d,
+            _ => 1.0,
+        }
+    }
+
+    /// has the tween reached its end?
+    pub fn is_finished(&self) -> bool
+    {
+        self.elapsed >= self.duration
+    }
+
+    /// start over from the beginning
+    pub fn restart(&mut self)
+    {
+        self.elapsed = Duration::default();
+    }
+
+    /// start a new tween from the current value to `to`, keeping
+    /// the duration and easing curve
+    pub fn retarget(&mut self, to: T)
+    {
+        self.from = self.value();
+        self.to = to;
+        self.elapsed = Duration::default();
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const ALL: [Ease; 16] =
+    [
+        Ease::Linear,
+        Ease::QuadIn, Ease::QuadOut, Ease::QuadInOut,
+        Ease::CubicIn, Ease::CubicOut, Ease::CubicInOut,
+        Ease::ElasticIn, Ease::ElasticOut, Ease::ElasticInOut,
+        Ease::BounceIn, Ease::BounceOut, Ease::BounceInOut,
+        Ease::BackIn, Ease::BackOut, Ease::BackInOut,
+    ];
+
+    #[test]
+    fn endpoints()
+    {
+        for ease in ALL
+        {
+            assert!(ease.apply(0.0).abs() < 1e-5, "{:?}(0) = {}", ease, ease.apply(0.0));
+            assert!((ease.apply(1.0) - 1.0).abs() < 1e-5, "{:?}(1) = {}", ease, ease.apply(1.0));
+        }
+    }
+
+    #[test]
+    fn clamped()
+    {
+        for ease in ALL
+        {
+            assert_eq!(ease.apply(-0.5), ease.apply(0.0), "{:?}", ease);
+            assert_eq!(ease.apply(1.5), ease.apply(1.0), "{:?}", ease);
+        }
+    }
+
+    #[test]
+    fn in_out_symmetric()
+    {
+        for ease in [Ease::QuadInOut, Ease::CubicInOut, Ease::ElasticInOut, Ease::BounceInOut, Ease::BackInOut]
+        {
+            assert!((ease.apply(0.5) - 0.5).abs() < 1e-5, "{:?}", ease);
+            assert!((ease.apply(0.25) + ease.apply(0.75) - 1.0).abs() < 1e-5, "{:?}", ease);
+        }
+    }
+
+    #[test]
+    fn overshoot()
+    {
+        let samples = |ease: Ease| (0..=100).map(move |i| ease.apply(i as f32 / 100.0));
+
+        assert!(samples(Ease::BackIn).any(|v| v < 0.0));
+        assert!(samples(Ease::BackOut).any(|v| v > 1.0));
+        assert!(samples(Ease::ElasticOut).any(|v| v > 1.0));
+        assert!(samples(Ease::BounceOut).all(|v| (0.0..=1.0 + 1e-5).contains(&v)));
+    }
+
+    #[test]
+    fn tween()
+    {
+        let mut tween = Tween::new(10.0f32, 20.0, Duration::from_secs(2));
+
+        assert_eq!(tween.value(), 10.0);
+        tween.update(Duration::from_secs(1));
+        assert_eq!(tween.progress(), 0.5);
+        assert_eq!(tween.value(), 15.0);
+        assert!(!tween.is_finished());
+
+        // stops at the end
+        tween.update(Duration::from_secs(5));
+        assert_eq!(tween.progress(), 1.0);
+        assert_eq!(tween.value(), 20.0);
+        assert!(tween.is_finished());
+
+        tween.restart();
+        assert_eq!(tween.value(), 10.0);
+    }
+
+    #[test]
+    fn retarget()
+    {
+        let mut tween = Tween::new(0.0f32, 10.0, Duration::from_secs(1)).ease(Ease::QuadIn);
+
+        tween.update(Duration::from_millis(500));
+        tween.retarget(0.0);
+
+        assert_eq!(tween.value(), 2.5);
+        tween.update(Duration::from_secs(1));
+        assert_eq!(tween.value(), 0.0);
+    }
+
+    #[test]
+    fn zero_duration()
+    {
+        let tween = Tween::new(0.0f32, 1.0, Duration::default());
+
+        assert!(tween.is_finished());
+        assert_eq!(tween.value(), 1.0);
+    }
+}
@@ -0,0 +1,59 @@
+use framework::prelude::*;
+
+/// blank 32x24 image to draw on
+fn blank() -> Image
+{
+    let mut img = Image::blank((32, 24));
+
+    img.background(c!("black"));
+    img
+}
+
+#[test]
+fn line()
+{
+    let mut img = blank();
+
+    img.stroke(c!("white"));
+    img.line(v![2, 2], v![29, 21]);
+    img.line(v![2, 21], v![29, 2]);
+    img.line(v![16, 0], v![16, 23]);
+    // partly out of bounds
+    img.stroke(c!("red"));
+    img.line(v![-10, 12], v![40, 12]);
+
+    assert_image_eq!(img, "tests/golden/line.png");
+}
+
+#[test]
+fn triangle()
+{
+    let mut img = blank();
+
+    img.stroke(c!("white"));
+    img.fill(c!("royalblue"));
+    img.triangle(v![3, 20], v![16, 2], v![28, 18]);
+    // partly out of bounds
+    img.stroke(c!("yellow"));
+    img.fill(c!("crimson"));
+    img.triangle(v![-6, -4], v![10, 0], v![0, 10]);
+
+    assert_image_eq!(img, "tests/golden/triangle.png");
+}
+
+#[test]
+fn rect()
+{
+    let mut img = blank();
+
+    img.stroke(c!("white"));
+    img.fill(c!("seagreen"));
+    img.rect(v![4, 4], v![10, 8]);
+    // gradient fill, partly out of bounds
+    img.fill(Gradient::linear(v![16.0, 0.0], v![32.0, 0.0])
+        .stop(0.0, c!("orange"))
+        .stop(1.0, c!("purple")));
+    img.rect(v![18, 12], v![20, 20]);
+
+    assert_image_eq!(img, "tests/golden/rect.png");
+}